@@ -1,22 +1,33 @@
 use astroport::asset::{Asset, AssetInfo};
-use astroport::pair::ExecuteMsg as PairExecuteMsg;
+use astroport::pair::{
+    CumulativePricesResponse, ExecuteMsg as PairExecuteMsg, QueryMsg as PairQueryMsg,
+};
 use astroport::router::ExecuteMsg as RouterExecuteMsg;
 use astroport::router::SwapOperation;
 use cosmwasm_std::{
-    attr, entry_point, to_json_binary, Attribute, CosmosMsg, Decimal, Deps, Uint128, WasmMsg,
+    attr, ensure, entry_point, to_json_binary, Attribute, CosmosMsg, Decimal, Deps, Uint128,
+    WasmMsg,
 };
-use cosmwasm_std::{Binary, DepsMut, Env, MessageInfo, Response, StdResult};
+use cosmwasm_std::{Binary, DepsMut, Env, MessageInfo, Response, StdError, StdResult};
 use cw_ownable::{get_ownership, update_ownership};
 use drop_helpers::answer::{attr_coin, response};
 use drop_staking_base::error::astroport_exchange_handler::{ContractError, ContractResult};
 use drop_staking_base::msg::astroport_exchange_handler::{
     ConfigResponse, ExecuteMsg, InstantiateMsg, MigrateMsg, QueryMsg,
 };
-use drop_staking_base::state::astroport_exchange_handler::{Config, CONFIG, SWAP_OPERATIONS};
+use drop_staking_base::msg::price_provider::{
+    PriceInfoResponse, QueryMsg as PriceProviderQueryMsg,
+};
+use drop_staking_base::state::astroport_exchange_handler::{
+    Config, TwapObservation, CONFIG, SWAP_OPERATIONS, TWAP_OBSERVATIONS,
+};
 
 const CONTRACT_NAME: &str = concat!("crates.io:drop-staking__", env!("CARGO_PKG_NAME"));
 const CONTRACT_VERSION: &str = env!("CARGO_PKG_VERSION");
 
+/// Precision cumulative prices of Astroport pairs are scaled by
+const TWAP_PRECISION: u32 = 6;
+
 #[cfg_attr(not(feature = "library"), entry_point)]
 pub fn instantiate(
     deps: DepsMut,
@@ -49,6 +60,8 @@ pub fn instantiate(
         from_denom: msg.from_denom.clone(),
         min_rewards: msg.min_rewards,
         max_spread: msg.max_spread,
+        twap_window: msg.twap_window.filter(|window| *window > 0),
+        price_max_age: msg.price_max_age.filter(|max_age| *max_age > 0),
     };
 
     CONFIG.save(deps.storage, &config)?;
@@ -73,6 +86,11 @@ pub fn query(deps: Deps, env: Env, msg: QueryMsg) -> StdResult<Binary> {
     match msg {
         QueryMsg::Ownership {} => Ok(to_json_binary(&get_ownership(deps.storage)?)?),
         QueryMsg::Config {} => query_config(deps, env),
+        QueryMsg::TwapObservations {} => to_json_binary(
+            &TWAP_OBSERVATIONS
+                .may_load(deps.storage)?
+                .unwrap_or_default(),
+        ),
     }
 }
 
@@ -89,6 +107,8 @@ fn query_config(deps: Deps, _env: Env) -> StdResult<Binary> {
         from_denom: config.from_denom,
         min_rewards: config.min_rewards,
         swap_operations,
+        twap_window: config.twap_window,
+        price_max_age: config.price_max_age,
     })
 }
 
@@ -113,6 +133,8 @@ pub fn execute(
             from_denom,
             min_rewards,
             max_spread,
+            twap_window,
+            price_max_age,
         } => exec_update_config(
             deps,
             info,
@@ -124,8 +146,11 @@ pub fn execute(
             from_denom,
             min_rewards,
             max_spread,
+            twap_window,
+            price_max_age,
         ),
         ExecuteMsg::Exchange {} => exec_exchange(deps, env),
+        ExecuteMsg::ObserveTwap {} => exec_observe_twap(deps, env),
         ExecuteMsg::UpdateSwapOperations { operations } => {
             exec_update_swap_operations(deps, info, operations)
         }
@@ -144,6 +169,8 @@ fn exec_update_config(
     from_denom: Option<String>,
     min_rewards: Option<Uint128>,
     max_spread: Option<Decimal>,
+    twap_window: Option<u64>,
+    price_max_age: Option<u64>,
 ) -> ContractResult<Response> {
     cw_ownable::assert_owner(deps.storage, &info.sender)?;
 
@@ -177,11 +204,13 @@ fn exec_update_config(
     if let Some(pair_contract) = pair_contract {
         let pair_contract = deps.api.addr_validate(&pair_contract)?;
         config.pair_contract = pair_contract.to_string();
+        TWAP_OBSERVATIONS.remove(deps.storage);
         attrs.push(attr("pair_contract", pair_contract))
     }
 
     if let Some(from_denom) = from_denom {
         config.from_denom = from_denom.to_string();
+        TWAP_OBSERVATIONS.remove(deps.storage);
         attrs.push(attr("from_denom", from_denom))
     }
 
@@ -195,6 +224,17 @@ fn exec_update_config(
         attrs.push(attr("max_spread", max_spread.to_string()))
     }
 
+    if let Some(twap_window) = twap_window {
+        config.twap_window = Some(twap_window).filter(|window| *window > 0);
+        TWAP_OBSERVATIONS.remove(deps.storage);
+        attrs.push(attr("twap_window", twap_window.to_string()))
+    }
+
+    if let Some(price_max_age) = price_max_age {
+        config.price_max_age = Some(price_max_age).filter(|max_age| *max_age > 0);
+        attrs.push(attr("price_max_age", price_max_age.to_string()))
+    }
+
     CONFIG.save(deps.storage, &config)?;
 
     Ok(response("config_update", CONTRACT_NAME, attrs))
@@ -204,10 +244,10 @@ fn exec_exchange(deps: DepsMut, env: Env) -> ContractResult<Response> {
     let swap_operations = SWAP_OPERATIONS.may_load(deps.storage)?;
     let config = CONFIG.load(deps.storage)?;
 
-    let from_denom = config.from_denom;
+    let from_denom = config.from_denom.clone();
     let balance = deps
         .querier
-        .query_balance(env.contract.address, from_denom.clone())?;
+        .query_balance(env.contract.address.clone(), from_denom.clone())?;
 
     if balance.amount < config.min_rewards {
         return Err(ContractError::LowBalance {
@@ -217,20 +257,48 @@ fn exec_exchange(deps: DepsMut, env: Env) -> ContractResult<Response> {
         });
     }
 
-    let belief_price: Decimal = deps
-        .querier
-        .query_wasm_smart(
-            config.price_provider_contract,
-            &drop_staking_base::msg::price_provider::QueryMsg::Price {
-                denom: from_denom.clone(),
-            },
-        )
-        .map_err(|e| ContractError::AssetPriceQueryFailed {
-            details: e.to_string(),
-        })?;
+    let price = query_price(deps.as_ref(), &env, &config)?;
+    let mut attrs: Vec<Attribute> = Vec::new();
+
+    // with TWAP the swap is bounded by the stricter of the TWAP and price provider returns
+    let min_return = match config.twap_window {
+        Some(window) => {
+            let twap = match query_twap(deps.as_ref(), &env, &config, window) {
+                Ok(twap) => twap,
+                // nothing is swapped, but the observation is kept so the TWAP gets ready
+                Err(ContractError::TwapNotReady { window }) => {
+                    let recorded = record_twap_observation(deps, &env, &config, window)?;
+                    return Ok(response(
+                        "exchange",
+                        CONTRACT_NAME,
+                        [
+                            attr("twap_not_ready", window.to_string()),
+                            attr("recorded", recorded.to_string()),
+                        ],
+                    ));
+                }
+                Err(err) => return Err(err),
+            };
+            record_twap_observation(deps, &env, &config, window)?;
+            let min_return = std::cmp::max(
+                balance.amount.checked_mul_floor(price)?,
+                balance.amount.checked_mul_floor(twap)?,
+            )
+            .checked_mul_floor(Decimal::one() - config.max_spread)?;
+            ensure!(
+                !min_return.is_zero(),
+                ContractError::ZeroMinReturn {
+                    denom: from_denom.clone()
+                }
+            );
+            attrs.push(attr("twap", twap.to_string()));
+            attrs.push(attr("min_return", min_return));
+            Some(min_return)
+        }
+        None => None,
+    };
 
     let mut msgs: Vec<CosmosMsg> = Vec::new();
-    let mut attrs: Vec<Attribute> = Vec::new();
 
     if let Some(swap_operations) = swap_operations {
         let router_contract_address = config.router_contract;
@@ -239,7 +307,7 @@ fn exec_exchange(deps: DepsMut, env: Env) -> ContractResult<Response> {
             contract_addr: router_contract_address.clone(),
             msg: to_json_binary(&RouterExecuteMsg::ExecuteSwapOperations {
                 operations: swap_operations,
-                minimum_receive: None,
+                minimum_receive: min_return,
                 to: Some(config.core_contract),
                 max_spread: Some(config.max_spread),
             })?,
@@ -250,6 +318,14 @@ fn exec_exchange(deps: DepsMut, env: Env) -> ContractResult<Response> {
         attrs.push(attr("router_contract", router_contract_address))
     } else {
         let pair_contract = config.pair_contract;
+        // pair has no minimal receive, so the belief price with zero spread enforces it
+        let (belief_price, max_spread) = match min_return {
+            Some(min_return) => (
+                Decimal::from_ratio(balance.amount, min_return),
+                Decimal::zero(),
+            ),
+            None => (price, config.max_spread),
+        };
 
         let exchange_rewards_msg = CosmosMsg::Wasm(WasmMsg::Execute {
             contract_addr: pair_contract.to_string(),
@@ -262,7 +338,7 @@ fn exec_exchange(deps: DepsMut, env: Env) -> ContractResult<Response> {
                 },
                 ask_asset_info: None,
                 belief_price: Some(belief_price),
-                max_spread: Some(config.max_spread),
+                max_spread: Some(max_spread),
                 to: Some(config.core_contract),
             })?,
             funds: vec![balance.clone()],
@@ -285,6 +361,114 @@ fn exec_exchange(deps: DepsMut, env: Env) -> ContractResult<Response> {
     .add_attributes(attrs))
 }
 
+fn query_price(deps: Deps, env: &Env, config: &Config) -> ContractResult<Decimal> {
+    let price_query_failed = |e: StdError| ContractError::AssetPriceQueryFailed {
+        details: e.to_string(),
+    };
+    let denom = config.from_denom.clone();
+    match config.price_max_age {
+        Some(max_age) => {
+            let price_info: PriceInfoResponse = deps
+                .querier
+                .query_wasm_smart(
+                    &config.price_provider_contract,
+                    &PriceProviderQueryMsg::PriceInfo {
+                        denom: denom.clone(),
+                    },
+                )
+                .map_err(price_query_failed)?;
+            ensure!(
+                price_info.updated_at.saturating_add(max_age) >= env.block.time.seconds(),
+                ContractError::StalePrice {
+                    denom,
+                    updated_at: price_info.updated_at,
+                }
+            );
+            Ok(price_info.price)
+        }
+        None => deps
+            .querier
+            .query_wasm_smart(
+                &config.price_provider_contract,
+                &PriceProviderQueryMsg::Price { denom },
+            )
+            .map_err(price_query_failed),
+    }
+}
+
+fn query_cumulative_price(deps: Deps, config: &Config) -> ContractResult<Uint128> {
+    let response: CumulativePricesResponse = deps
+        .querier
+        .query_wasm_smart(&config.pair_contract, &PairQueryMsg::CumulativePrices {})?;
+    let offer_asset_info = AssetInfo::NativeToken {
+        denom: config.from_denom.clone(),
+    };
+    response
+        .cumulative_prices
+        .into_iter()
+        .find(|(offer, _, _)| *offer == offer_asset_info)
+        .map(|(_, _, cumulative_price)| cumulative_price)
+        .ok_or(ContractError::TwapPriceNotFound {
+            denom: config.from_denom.clone(),
+        })
+}
+
+/// Average price of `from_denom` since the newest observation older than `window`
+fn query_twap(deps: Deps, env: &Env, config: &Config, window: u64) -> ContractResult<Decimal> {
+    let now = env.block.time.seconds();
+    let observation = TWAP_OBSERVATIONS
+        .may_load(deps.storage)?
+        .unwrap_or_default()
+        .into_iter()
+        .rev()
+        .find(|observation| observation.timestamp + window <= now)
+        .ok_or(ContractError::TwapNotReady { window })?;
+    let cumulative_price = query_cumulative_price(deps, config)?;
+    Ok(Decimal::from_ratio(
+        cumulative_price.wrapping_sub(observation.cumulative_price),
+        Uint128::from(now - observation.timestamp)
+            .checked_mul(Uint128::from(10u128.pow(TWAP_PRECISION)))?,
+    ))
+}
+
+fn record_twap_observation(
+    deps: DepsMut,
+    env: &Env,
+    config: &Config,
+    window: u64,
+) -> ContractResult<bool> {
+    let now = env.block.time.seconds();
+    let mut observations = TWAP_OBSERVATIONS
+        .may_load(deps.storage)?
+        .unwrap_or_default();
+    if observations
+        .last()
+        .is_some_and(|last| last.timestamp + window > now)
+    {
+        return Ok(false);
+    }
+    observations.push(TwapObservation {
+        timestamp: now,
+        cumulative_price: query_cumulative_price(deps.as_ref(), config)?,
+    });
+    if observations.len() > 2 {
+        observations.remove(0);
+    }
+    TWAP_OBSERVATIONS.save(deps.storage, &observations)?;
+    Ok(true)
+}
+
+fn exec_observe_twap(deps: DepsMut, env: Env) -> ContractResult<Response> {
+    let config = CONFIG.load(deps.storage)?;
+    let window = config.twap_window.ok_or(ContractError::TwapDisabled)?;
+    let recorded = record_twap_observation(deps, &env, &config, window)?;
+    Ok(response(
+        "observe_twap",
+        CONTRACT_NAME,
+        [attr("recorded", recorded.to_string())],
+    ))
+}
+
 fn exec_update_swap_operations(
     deps: DepsMut,
     info: MessageInfo,
@@ -1,7 +1,9 @@
 use crate::contract::instantiate;
 
-use astroport::asset::AssetInfo;
-use astroport::pair::ExecuteMsg as PairExecuteMsg;
+use astroport::asset::{Asset, AssetInfo};
+use astroport::pair::{
+    CumulativePricesResponse, ExecuteMsg as PairExecuteMsg, QueryMsg as PairQueryMsg,
+};
 use astroport::router::{ExecuteMsg as RouterExecuteMsg, SwapOperation};
 use cosmwasm_std::testing::{mock_dependencies, mock_env, mock_info};
 use cosmwasm_std::{
@@ -13,6 +15,10 @@ use drop_helpers::answer::response;
 use drop_staking_base::msg::astroport_exchange_handler::{
     ConfigResponse, ExecuteMsg, InstantiateMsg, QueryMsg,
 };
+use drop_staking_base::msg::price_provider::{
+    ExecuteMsg as PriceProviderExecuteMsg, InstantiateMsg as PriceProviderInstantiateMsg,
+};
+use drop_staking_base::state::astroport_exchange_handler::TwapObservation;
 
 const CORE_CONTRACT_ADDR: &str = "core_contract";
 const PRICE_PROVIDER_CONTRACT_ADDR: &str = "price_provider_contract";
@@ -244,6 +250,8 @@ fn test_initialization() {
         from_denom: "ueth".to_string(),
         min_rewards: Uint128::one(),
         max_spread: Decimal::percent(1),
+        twap_window: None,
+        price_max_age: None,
     };
 
     let info = mock_info(OWNER_CONTRACT_ADDR, &[]);
@@ -288,6 +296,8 @@ fn test_config_query() {
             from_denom: "ueth".to_string(),
             min_rewards: Uint128::one(),
             max_spread: Decimal::percent(1),
+            twap_window: None,
+            price_max_age: None,
         },
     );
 
@@ -307,6 +317,8 @@ fn test_config_query() {
             from_denom: "ueth".to_string(),
             min_rewards: Uint128::one(),
             swap_operations: None,
+            twap_window: None,
+            price_max_age: None,
         }
     );
 }
@@ -336,6 +348,8 @@ fn test_exchange_through_pair_call() {
             from_denom: "ueth".to_string(),
             min_rewards: Uint128::one(),
             max_spread: Decimal::percent(1),
+            twap_window: None,
+            price_max_age: None,
         },
     );
 
@@ -398,6 +412,8 @@ fn test_exchange_through_router_call() {
             from_denom: "ueth".to_string(),
             min_rewards: Uint128::one(),
             max_spread: Decimal::percent(1),
+            twap_window: None,
+            price_max_age: None,
         },
     );
 
@@ -479,6 +495,8 @@ fn test_not_enough_balance_error() {
             from_denom: "ueth".to_string(),
             min_rewards: Uint128::from(200u128),
             max_spread: Decimal::percent(1),
+            twap_window: None,
+            price_max_age: None,
         },
     );
 
@@ -523,6 +541,8 @@ fn test_unauthorized_router_call() {
             from_denom: "ueth".to_string(),
             min_rewards: Uint128::one(),
             max_spread: Decimal::percent(1),
+            twap_window: None,
+            price_max_age: None,
         },
     );
 
@@ -575,6 +595,8 @@ fn test_unauthorized_config_update() {
             from_denom: "ueth".to_string(),
             min_rewards: Uint128::one(),
             max_spread: Decimal::percent(1),
+            twap_window: None,
+            price_max_age: None,
         },
     );
 
@@ -590,6 +612,8 @@ fn test_unauthorized_config_update() {
             from_denom: Some("ueth".to_string()),
             min_rewards: Some(Uint128::one()),
             max_spread: Some(Decimal::percent(1)),
+            twap_window: None,
+            price_max_age: None,
         },
         &[],
     );
@@ -623,6 +647,8 @@ fn test_config_update() {
             from_denom: "ueth".to_string(),
             min_rewards: Uint128::one(),
             max_spread: Decimal::percent(1),
+            twap_window: None,
+            price_max_age: None,
         },
     );
 
@@ -639,6 +665,8 @@ fn test_config_update() {
                 from_denom: Some("untrn".to_string()),
                 min_rewards: Some(Uint128::zero()),
                 max_spread: Some(Decimal::percent(1)),
+                twap_window: None,
+                price_max_age: None,
             },
             &[],
         )
@@ -689,6 +717,8 @@ fn test_config_update() {
             from_denom: "untrn".to_string(),
             min_rewards: Uint128::zero(),
             swap_operations: None,
+            twap_window: None,
+            price_max_age: None,
         }
     );
 }
@@ -714,6 +744,8 @@ fn test_swap_operations_update() {
             from_denom: "ueth".to_string(),
             min_rewards: Uint128::one(),
             max_spread: Decimal::percent(1),
+            twap_window: None,
+            price_max_age: None,
         },
     );
 
@@ -759,6 +791,8 @@ fn test_swap_operations_update() {
             from_denom: "ueth".to_string(),
             min_rewards: Uint128::one(),
             swap_operations: Some(operations),
+            twap_window: None,
+            price_max_age: None,
         }
     );
 
@@ -787,6 +821,240 @@ fn test_swap_operations_update() {
             from_denom: "ueth".to_string(),
             min_rewards: Uint128::one(),
             swap_operations: None,
+            twap_window: None,
+            price_max_age: None,
         }
     );
 }
+
+/// Pair keeping a constant price of 2untrn per ueth
+fn twap_pair_contract() -> Box<dyn Contract<Empty>> {
+    let contract: ContractWrapper<
+        PairExecuteMsg,
+        Empty,
+        PairQueryMsg,
+        StdError,
+        StdError,
+        StdError,
+    > = ContractWrapper::new(
+        |_, _, _, msg: PairExecuteMsg| match msg {
+            PairExecuteMsg::Swap {
+                belief_price,
+                max_spread,
+                ..
+            } => Ok(response(
+                "pair_contract_execute",
+                "pair_mock",
+                [
+                    attr("belief_price", belief_price.unwrap().to_string()),
+                    attr("max_spread", max_spread.unwrap().to_string()),
+                ],
+            )),
+            _ => Err(StdError::generic_err("Wrong execution call")),
+        },
+        |_, _, _, _: Empty| Ok(Response::new()),
+        |_, env, msg: PairQueryMsg| match msg {
+            PairQueryMsg::CumulativePrices {} => {
+                let ueth = AssetInfo::NativeToken {
+                    denom: "ueth".to_string(),
+                };
+                let untrn = AssetInfo::NativeToken {
+                    denom: "untrn".to_string(),
+                };
+                let elapsed = Uint128::from(env.block.time.seconds());
+                to_json_binary(&CumulativePricesResponse {
+                    assets: vec![
+                        Asset {
+                            info: ueth.clone(),
+                            amount: Uint128::from(1000000u128),
+                        },
+                        Asset {
+                            info: untrn.clone(),
+                            amount: Uint128::from(2000000u128),
+                        },
+                    ],
+                    total_share: Uint128::from(1000000u128),
+                    cumulative_prices: vec![
+                        (
+                            ueth.clone(),
+                            untrn.clone(),
+                            elapsed * Uint128::from(2000000u128),
+                        ),
+                        (untrn, ueth, elapsed * Uint128::from(500000u128)),
+                    ],
+                })
+            }
+            _ => Err(StdError::generic_err("Wrong query")),
+        },
+    );
+    Box::new(contract)
+}
+
+fn instantiate_real_price_provider_contract(app: &mut App, price: Decimal) -> Addr {
+    let code_id = app.store_code(Box::new(ContractWrapper::new(
+        drop_price_provider::contract::execute,
+        drop_price_provider::contract::instantiate,
+        drop_price_provider::contract::query,
+    )));
+    let price_provider = app
+        .instantiate_contract(
+            code_id,
+            Addr::unchecked(OWNER_CONTRACT_ADDR),
+            &PriceProviderInstantiateMsg {
+                owner: None,
+                feeders: vec![],
+                max_age: None,
+            },
+            &[],
+            "price provider",
+            None,
+        )
+        .unwrap();
+    app.execute_contract(
+        Addr::unchecked(OWNER_CONTRACT_ADDR),
+        price_provider.clone(),
+        &PriceProviderExecuteMsg::SetPrice {
+            denom: "ueth".to_string(),
+            price,
+        },
+        &[],
+    )
+    .unwrap();
+    price_provider
+}
+
+#[test]
+fn test_exchange_with_twap_and_price_max_age() {
+    let mut app = mock_app();
+
+    let pair_contract = instantiate_contract(
+        &mut app,
+        twap_pair_contract,
+        "astroport pair contract".to_string(),
+    );
+    let price_provider_contract =
+        instantiate_real_price_provider_contract(&mut app, Decimal::from_ratio(3u64, 2u64));
+
+    let astroport_exchange_handler_code_id = app.store_code(astroport_handler_contract());
+    let astroport_handler_contract = instantiate_astroport_handler_contract(
+        &mut app,
+        astroport_exchange_handler_code_id,
+        InstantiateMsg {
+            owner: OWNER_CONTRACT_ADDR.to_string(),
+            core_contract: CORE_CONTRACT_ADDR.to_string(),
+            price_provider_contract: price_provider_contract.to_string(),
+            cron_address: CRON_ADDR.to_string(),
+            pair_contract: pair_contract.to_string(),
+            router_contract: "router_contract".to_string(),
+            from_denom: "ueth".to_string(),
+            min_rewards: Uint128::one(),
+            max_spread: Decimal::percent(1),
+            twap_window: Some(100),
+            price_max_age: Some(1000),
+        },
+    );
+    app.send_tokens(
+        Addr::unchecked(SENDER_ADDR),
+        astroport_handler_contract.clone(),
+        &coins(100, "ueth"),
+    )
+    .unwrap();
+
+    let observed_at = app.block_info().time.seconds();
+    let res = app
+        .execute_contract(
+            Addr::unchecked(CORE_CONTRACT_ADDR),
+            astroport_handler_contract.clone(),
+            &ExecuteMsg::Exchange {},
+            &[],
+        )
+        .unwrap();
+    assert_eq!(
+        res.events[1].attributes[1..],
+        vec![
+            Attribute::new("twap_not_ready", "100"),
+            Attribute::new("recorded", "true"),
+        ]
+    );
+    assert_eq!(res.events.len(), 2);
+
+    let res = app
+        .execute_contract(
+            Addr::unchecked(SENDER_ADDR),
+            astroport_handler_contract.clone(),
+            &ExecuteMsg::ObserveTwap {},
+            &[],
+        )
+        .unwrap();
+    assert_eq!(
+        res.events[1].attributes[1..],
+        vec![Attribute::new("recorded", "false")]
+    );
+
+    app.update_block(|block| block.time = block.time.plus_seconds(100));
+    let res = app
+        .execute_contract(
+            Addr::unchecked(CORE_CONTRACT_ADDR),
+            astroport_handler_contract.clone(),
+            &ExecuteMsg::Exchange {},
+            &[],
+        )
+        .unwrap();
+    assert_eq!(
+        res.events[1].attributes[1..],
+        vec![
+            Attribute::new("twap", "2"),
+            Attribute::new("min_return", "198"),
+            Attribute::new("pair_contract", pair_contract.to_string()),
+        ]
+    );
+    assert_eq!(
+        res.events[4].attributes[1..],
+        vec![
+            Attribute::new(
+                "belief_price",
+                Decimal::from_ratio(100u64, 198u64).to_string()
+            ),
+            Attribute::new("max_spread", "0"),
+        ]
+    );
+
+    let observations: Vec<TwapObservation> = app
+        .wrap()
+        .query_wasm_smart(
+            astroport_handler_contract.clone(),
+            &QueryMsg::TwapObservations {},
+        )
+        .unwrap();
+    assert_eq!(
+        observations,
+        vec![
+            TwapObservation {
+                timestamp: observed_at,
+                cumulative_price: Uint128::from(observed_at as u128 * 2000000),
+            },
+            TwapObservation {
+                timestamp: observed_at + 100,
+                cumulative_price: Uint128::from((observed_at + 100) as u128 * 2000000),
+            },
+        ]
+    );
+
+    app.update_block(|block| block.time = block.time.plus_seconds(901));
+    app.send_tokens(
+        Addr::unchecked(SENDER_ADDR),
+        astroport_handler_contract.clone(),
+        &coins(100, "ueth"),
+    )
+    .unwrap();
+    let res = app.execute_contract(
+        Addr::unchecked(CORE_CONTRACT_ADDR),
+        astroport_handler_contract.clone(),
+        &ExecuteMsg::Exchange {},
+        &[],
+    );
+    assert_eq!(
+        res.unwrap_err().root_cause().to_string(),
+        format!("Price of ueth is stale, updated at: {}", observed_at)
+    );
+}
@@ -6,6 +6,7 @@ use cosmwasm_std::{
 };
 use cw_storage_plus::Bound;
 use drop_helpers::answer::response;
+use drop_helpers::ica::IcaRecoveredHookMsg;
 use drop_helpers::pause::{is_paused, pause_guard, set_pause, unpause, PauseInfoResponse};
 use drop_puppeteer_base::msg::{IBCTransferReason, TransferReadyBatchesMsg};
 use drop_puppeteer_base::state::RedeemShareItem;
@@ -24,13 +25,16 @@ use drop_staking_base::{
     },
     state::{
         core::{
-            unbond_batches_map, Config, ConfigOptional, ContractState, UnbondBatch,
+            unbond_batches_map, Config, ConfigOptional, ContractState, StakingPath, UnbondBatch,
             UnbondBatchStatus, UnbondBatchStatusTimestamps, UnbondBatchesResponse, BONDED_AMOUNT,
             CONFIG, EXCHANGE_RATE, FAILED_BATCH_ID, FSM, LAST_ICA_CHANGE_HEIGHT, LAST_IDLE_CALL,
-            LAST_LSM_REDEEM, LAST_PUPPETEER_RESPONSE, LAST_STAKER_RESPONSE, LD_DENOM,
-            LSM_SHARES_TO_REDEEM, PENDING_LSM_SHARES, TOTAL_LSM_SHARES, UNBOND_BATCH_ID,
+            LAST_LSM_REDEEM, LAST_PUPPETEER_RESPONSE, LAST_SLASH_EVENT_ID, LAST_STAKER_RESPONSE,
+            LD_DENOM, LSM_SHARES_TO_REDEEM, PENDING_DELIVERY_BATCHES, PENDING_LSM_SHARES,
+            PENDING_STAKE_LOCAL, PENDING_STAKE_REMOTE, PUMP_DELIVERED_AMOUNT, TOTAL_LSM_SHARES,
+            UNBOND_BATCH_ID,
         },
         validatorset::ValidatorInfo,
+        validatorsstats::SlashEvent,
         withdrawal_voucher::{Metadata, Trait},
     },
 };
@@ -193,10 +197,20 @@ fn query_exchange_rate(deps: Deps<NeutronQuery>, config: &Config) -> ContractRes
         unprocessed_dasset_to_unbond += failed_batch.total_dasset_amount_to_withdraw;
     }
     exchange_rate_denominator += unprocessed_dasset_to_unbond;
-    let staker_balance: Uint128 = deps.querier.query_wasm_smart(
-        &config.staker_contract,
-        &drop_staking_base::msg::staker::QueryMsg::AllBalance {},
-    )?;
+    let staker_balance: Uint128 = match config.staking_path {
+        StakingPath::Staker => deps.querier.query_wasm_smart(
+            &config.staker_contract,
+            &drop_staking_base::msg::staker::QueryMsg::AllBalance {},
+        )?,
+        StakingPath::Puppeteer => {
+            PENDING_STAKE_LOCAL
+                .may_load(deps.storage)?
+                .unwrap_or_default()
+                + PENDING_STAKE_REMOTE
+                    .may_load(deps.storage)?
+                    .unwrap_or_default()
+        }
+    };
     let total_lsm_shares = Uint128::new(TOTAL_LSM_SHARES.load(deps.storage)?);
     // arithmetic operations order is important here as we don't want to overflow
     let exchange_rate_numerator = delegations_amount + staker_balance + total_lsm_shares;
@@ -278,6 +292,9 @@ pub fn execute(
             ))
         }
         ExecuteMsg::ResetBondedAmount {} => execute_reset_bonded_amount(deps, env, info),
+        ExecuteMsg::MarkBatchesWithdrawn { batch_ids } => {
+            execute_mark_batches_withdrawn(deps, env, info, batch_ids)
+        }
         ExecuteMsg::ProcessEmergencyBatch {
             batch_id,
             unbonded_amount,
@@ -289,6 +306,11 @@ pub fn execute(
         ExecuteMsg::Tick {} => execute_tick(deps, env, info),
         ExecuteMsg::PuppeteerHook(msg) => execute_puppeteer_hook(deps, env, info, *msg),
         ExecuteMsg::StakerHook(msg) => execute_staker_hook(deps, env, info, *msg),
+        ExecuteMsg::PumpHook(msg) => execute_pump_hook(deps, env, info, *msg),
+        ExecuteMsg::DeliveryReceived { amount } => {
+            execute_delivery_received(deps, env, info, amount)
+        }
+        ExecuteMsg::IcaRecoveredHook(msg) => execute_ica_recovered_hook(deps, info, msg),
         ExecuteMsg::Pause {} => exec_pause(deps, info),
         ExecuteMsg::Unpause {} => exec_unpause(deps, info),
     }
@@ -410,6 +432,31 @@ fn execute_update_withdrawn_amount(
     ))
 }
 
+fn execute_ica_recovered_hook(
+    deps: DepsMut<NeutronQuery>,
+    info: MessageInfo,
+    msg: IcaRecoveredHookMsg,
+) -> ContractResult<Response<NeutronMsg>> {
+    let config = CONFIG.load(deps.storage)?;
+    ensure!(
+        info.sender == config.puppeteer_contract
+            || info.sender == config.staker_contract
+            || config.pump_contract.as_ref() == Some(&info.sender),
+        ContractError::Unauthorized {}
+    );
+    Ok(response(
+        "execute-ica_recovered_hook",
+        CONTRACT_NAME,
+        vec![
+            attr("action", "ica_recovered_hook"),
+            attr("sender", info.sender),
+            attr("ica_id", msg.ica_id),
+            attr("ica_address", msg.ica_address),
+            attr("channel_id", msg.channel_id),
+        ],
+    ))
+}
+
 fn execute_staker_hook(
     deps: DepsMut<NeutronQuery>,
     _env: Env,
@@ -430,6 +477,114 @@ fn execute_staker_hook(
     ))
 }
 
+fn execute_pump_hook(
+    deps: DepsMut<NeutronQuery>,
+    _env: Env,
+    info: MessageInfo,
+    msg: drop_staking_base::msg::pump::ResponseHookMsg,
+) -> ContractResult<Response<NeutronMsg>> {
+    let config = CONFIG.load(deps.storage)?;
+    ensure!(
+        config.pump_contract.as_ref() == Some(&info.sender),
+        ContractError::Unauthorized {}
+    );
+    let mut attrs = vec![attr("action", "pump_hook")];
+    match msg {
+        // the ack only means the transfer left the remote chain, batches are withdrawn
+        // once the withdrawal manager reports the arrival of the funds
+        drop_staking_base::msg::pump::ResponseHookMsg::Success(success) => {
+            attrs.push(attr("in_flight", success.coin.to_string()));
+            attrs.push(attr("receiver", success.receiver));
+        }
+        drop_staking_base::msg::pump::ResponseHookMsg::Error(err) => {
+            // the funds stay on the pump ICA and are delivered by the next push
+            attrs.push(attr("error", err.details));
+        }
+    }
+    Ok(response("execute-pump_hook", CONTRACT_NAME, attrs))
+}
+
+fn execute_delivery_received(
+    deps: DepsMut<NeutronQuery>,
+    env: Env,
+    info: MessageInfo,
+    amount: Uint128,
+) -> ContractResult<Response<NeutronMsg>> {
+    let config = CONFIG.load(deps.storage)?;
+    ensure_eq!(
+        info.sender,
+        config.withdrawal_manager_contract,
+        ContractError::Unauthorized {}
+    );
+    let mut attrs = vec![attr("action", "delivery_received")];
+    let mut pending_batches = PENDING_DELIVERY_BATCHES
+        .may_load(deps.storage)?
+        .unwrap_or_default();
+    // nothing is awaited, so the funds can't be attributed to future batches
+    if pending_batches.is_empty() {
+        attrs.push(attr("skipped", amount));
+        return Ok(response("execute-delivery_received", CONTRACT_NAME, attrs));
+    }
+    let mut delivered = PUMP_DELIVERED_AMOUNT
+        .may_load(deps.storage)?
+        .unwrap_or_default()
+        + amount;
+    // batches are delivered in the order they were transferred to the pump ICA
+    while let Some(id) = pending_batches.first().copied() {
+        let mut batch = unbond_batches_map().load(deps.storage, id)?;
+        let amount = batch.unbonded_amount.unwrap_or_default();
+        if delivered < amount {
+            break;
+        }
+        delivered -= amount;
+        batch.status = UnbondBatchStatus::Withdrawn;
+        batch.status_timestamps.withdrawn = Some(env.block.time.seconds());
+        unbond_batches_map().save(deps.storage, id, &batch)?;
+        pending_batches.remove(0);
+        attrs.push(attr("withdrawn_batch_id", id.to_string()));
+    }
+    if pending_batches.is_empty() {
+        delivered = Uint128::zero();
+    }
+    PUMP_DELIVERED_AMOUNT.save(deps.storage, &delivered)?;
+    PENDING_DELIVERY_BATCHES.save(deps.storage, &pending_batches)?;
+    Ok(response("execute-delivery_received", CONTRACT_NAME, attrs))
+}
+
+fn execute_mark_batches_withdrawn(
+    deps: DepsMut<NeutronQuery>,
+    env: Env,
+    info: MessageInfo,
+    batch_ids: Vec<u128>,
+) -> ContractResult<Response<NeutronMsg>> {
+    cw_ownable::assert_owner(deps.storage, &info.sender)?;
+    let mut attrs = vec![attr("action", "mark_batches_withdrawn")];
+    let mut pending_batches = PENDING_DELIVERY_BATCHES
+        .may_load(deps.storage)?
+        .unwrap_or_default();
+    for batch_id in batch_ids {
+        let position = pending_batches
+            .iter()
+            .position(|id| *id == batch_id)
+            .ok_or(ContractError::BatchNotAwaitingDelivery { batch_id })?;
+        pending_batches.remove(position);
+        let mut batch = unbond_batches_map().load(deps.storage, batch_id)?;
+        batch.status = UnbondBatchStatus::Withdrawn;
+        batch.status_timestamps.withdrawn = Some(env.block.time.seconds());
+        unbond_batches_map().save(deps.storage, batch_id, &batch)?;
+        attrs.push(attr("withdrawn_batch_id", batch_id.to_string()));
+    }
+    if pending_batches.is_empty() {
+        PUMP_DELIVERED_AMOUNT.save(deps.storage, &Uint128::zero())?;
+    }
+    PENDING_DELIVERY_BATCHES.save(deps.storage, &pending_batches)?;
+    Ok(response(
+        "execute-mark_batches_withdrawn",
+        CONTRACT_NAME,
+        attrs,
+    ))
+}
+
 fn execute_puppeteer_hook(
     deps: DepsMut<NeutronQuery>,
     env: Env,
@@ -490,6 +645,21 @@ fn execute_puppeteer_hook(
                             }
                         }
                     }
+                    if *reason == IBCTransferReason::Stake {
+                        let sent_amount = Uint128::from(*amount);
+                        PENDING_STAKE_LOCAL.update(deps.storage, |one| {
+                            StdResult::Ok(one.unwrap_or_default().saturating_sub(sent_amount))
+                        })?;
+                        PENDING_STAKE_REMOTE.update(deps.storage, |one| {
+                            StdResult::Ok(one.unwrap_or_default() + sent_amount)
+                        })?;
+                    }
+                }
+                drop_puppeteer_base::msg::Transaction::Delegate { items, .. } => {
+                    let delegated: Uint128 = items.iter().map(|(_, amount)| *amount).sum();
+                    PENDING_STAKE_REMOTE.update(deps.storage, |one| {
+                        StdResult::Ok(one.unwrap_or_default().saturating_sub(delegated))
+                    })?;
                 }
                 drop_puppeteer_base::msg::Transaction::RedeemShares { items, .. } => {
                     let mut sum = 0u128;
@@ -509,14 +679,25 @@ fn execute_puppeteer_hook(
             match err_msg.transaction {
                 drop_puppeteer_base::msg::Transaction::Transfer { .. } // this one is for transfering non-native rewards
                 | drop_puppeteer_base::msg::Transaction::RedeemShares { .. }
+                | drop_puppeteer_base::msg::Transaction::Redelegate { .. }
+                | drop_puppeteer_base::msg::Transaction::Delegate { .. }
                 | drop_puppeteer_base::msg::Transaction::ClaimRewardsAndOptionalyTransfer { .. } => { // this goes to idle and then ruled in tick_idle
                 // IBC transfer for LSM shares and pending stake
                 FSM.go_to(deps.storage, ContractState::Idle)?
             }
-            drop_puppeteer_base::msg::Transaction::IBCTransfer { reason, .. } => {
-                if reason == IBCTransferReason::LSMShare {
-                    FSM.go_to(deps.storage, ContractState::Idle)?;
+            drop_puppeteer_base::msg::Transaction::IBCTransfer { amount, reason, .. } => {
+                if reason == IBCTransferReason::Stake {
+                    // the puppeteer returns the refunded transfer along with the hook
+                    let refunded = cw_utils::may_pay(&info, &config.base_denom).unwrap_or_default();
+                    PENDING_STAKE_LOCAL.update(deps.storage, |one| {
+                        StdResult::Ok(
+                            one.unwrap_or_default()
+                                .saturating_sub(Uint128::from(amount))
+                                + refunded,
+                        )
+                    })?;
                 }
+                FSM.go_to(deps.storage, ContractState::Idle)?;
             }
                 _ => {}
             }
@@ -553,6 +734,10 @@ fn execute_tick(
         ContractState::Claiming => execute_tick_claiming(deps.branch(), env, info, &config),
         ContractState::StakingBond => execute_tick_staking_bond(deps.branch(), env, info, &config),
         ContractState::Unbonding => execute_tick_unbonding(deps.branch(), env, info, &config),
+        ContractState::Rebalancing => execute_tick_peripheral(deps.branch(), env, info, &config),
+        ContractState::StakingTransfer => {
+            execute_tick_peripheral(deps.branch(), env, info, &config)
+        }
     }
 }
 
@@ -587,13 +772,24 @@ fn execute_tick_idle(
                 FSM.go_to(deps.storage, ContractState::LSMTransfer)?;
                 attrs.push(attr("knot", "043"));
             } else {
-                //return error if none
-                return Err(ContractError::IdleMinIntervalIsNotReached {});
+                attrs.push(attr("knot", "051"));
+                if let Some(rebalance_msg) =
+                    get_rebalance_msg(deps.as_ref(), config, &env, info.funds.clone())?
+                {
+                    messages.push(rebalance_msg);
+                    attrs.push(attr("knot", "052"));
+                    FSM.go_to(deps.storage, ContractState::Rebalancing)?;
+                    attrs.push(attr("knot", "053"));
+                } else {
+                    //return error if none
+                    return Err(ContractError::IdleMinIntervalIsNotReached {});
+                }
             }
         }
     } else {
         LAST_IDLE_CALL.save(deps.storage, &env.block.time.seconds())?;
         attrs.push(attr("knot", "004"));
+        apply_slash_events(deps.branch(), config, &mut attrs)?;
         let unbonding_batches = unbond_batches_map()
             .idx
             .status
@@ -620,6 +816,12 @@ fn execute_tick_idle(
             &config.remote_denom,
             true,
         )?;
+        // the ICA also holds the bonded funds waiting to be delegated
+        let ica_balance = ica_balance.saturating_sub(
+            PENDING_STAKE_REMOTE
+                .may_load(deps.storage)?
+                .unwrap_or_default(),
+        );
 
         let unbonded_batches = if !unbonding_batches.is_empty() {
             unbonding_batches
@@ -657,7 +859,9 @@ fn execute_tick_idle(
                         (unbonding_batch.expected_native_asset_amount, Decimal::one())
                     };
                 unbonding_batch.unbonded_amount = Some(unbonded_amount);
-                unbonding_batch.slashing_effect = Some(slashing_effect);
+                unbonding_batch.slashing_effect = Some(
+                    unbonding_batch.slashing_effect.unwrap_or(Decimal::one()) * slashing_effect,
+                );
                 unbonding_batch.status = UnbondBatchStatus::Withdrawing;
                 unbonding_batch.status_timestamps.withdrawing = Some(env.block.time.seconds());
                 unbond_batches_map().save(deps.storage, id, &unbonding_batch)?;
@@ -698,7 +902,8 @@ fn execute_tick_idle(
                             Some(env.block.time.seconds());
                     } else {
                         batch.unbonded_amount = Some(batch.expected_native_asset_amount);
-                        batch.slashing_effect = Some(Decimal::one());
+                        batch.slashing_effect =
+                            Some(batch.slashing_effect.unwrap_or(Decimal::one()));
                         batch.status = UnbondBatchStatus::Withdrawing;
                         batch.status_timestamps.withdrawing = Some(env.block.time.seconds());
                     }
@@ -759,6 +964,13 @@ fn execute_tick_idle(
                 FSM.go_to(deps.storage, ContractState::StakingBond)?;
                 attrs.push(attr("knot", "017"));
                 attrs.push(attr("state", "staking_bond"));
+            } else if let Some(stake_transfer_msg) =
+                get_stake_transfer_msg(deps.as_ref(), &env, config, &info)?
+            {
+                messages.push(stake_transfer_msg);
+                attrs.push(attr("knot", "054"));
+                FSM.go_to(deps.storage, ContractState::StakingTransfer)?;
+                attrs.push(attr("state", "staking_transfer"));
             } else {
                 attrs.push(attr("knot", "017"));
                 if let Some(unbond_message) =
@@ -796,6 +1008,106 @@ fn execute_tick_idle(
     Ok(response("execute-tick_idle", CONTRACT_NAME, attrs).add_messages(messages))
 }
 
+/// Reduces expected amounts of unbonding batches by slash events reported by the validators
+/// stats contract since the last check. Only batches which were already unbonding when the event
+/// was registered are affected: the chain slashes their unbonding entries, while later batches
+/// are computed from the already slashed delegations.
+/// As undelegations are not tracked per validator, the effect of every event is weighted by
+/// the validator share in current delegations. Failed queries postpone the adjustment to the
+/// next idle tick instead of failing it
+pub(crate) fn apply_slash_events(
+    deps: DepsMut<NeutronQuery>,
+    config: &Config,
+    attrs: &mut Vec<Attribute>,
+) -> ContractResult<()> {
+    let validators_stats_contract = match &config.validators_stats_contract {
+        Some(validators_stats_contract) => validators_stats_contract,
+        None => return Ok(()),
+    };
+    let slash_events: Vec<(u64, SlashEvent)> = match deps.querier.query_wasm_smart(
+        validators_stats_contract,
+        &drop_staking_base::msg::validatorsstats::QueryMsg::SlashEvents {
+            valoper: None,
+            start_after: LAST_SLASH_EVENT_ID.may_load(deps.storage)?,
+            limit: None,
+        },
+    ) {
+        Ok(slash_events) => slash_events,
+        Err(_) => {
+            attrs.push(attr("slash_events", "query_failed"));
+            return Ok(());
+        }
+    };
+    let last_slash_event_id = match slash_events.last() {
+        Some((id, _)) => *id,
+        None => return Ok(()),
+    };
+
+    let delegations = match deps
+        .querier
+        .query_wasm_smart::<drop_staking_base::msg::puppeteer::DelegationsResponse>(
+            config.puppeteer_contract.to_string(),
+            &drop_puppeteer_base::msg::QueryMsg::Extension {
+                msg: drop_staking_base::msg::puppeteer::QueryExtMsg::Delegations {},
+            },
+        ) {
+        Ok(response) => response.delegations.delegations,
+        Err(_) => {
+            attrs.push(attr("slash_events", "delegations_query_failed"));
+            return Ok(());
+        }
+    };
+    let total_delegated: Uint128 = delegations.iter().map(|d| d.amount.amount).sum();
+
+    // (event timestamp, slashed share of the delegations)
+    let mut slashed_shares: Vec<(u64, Decimal)> = vec![];
+    if !total_delegated.is_zero() {
+        for (_id, event) in slash_events.iter() {
+            let delegated: Uint128 = delegations
+                .iter()
+                .filter(|d| d.validator == event.valoper_address)
+                .map(|d| d.amount.amount)
+                .sum();
+            if !delegated.is_zero() {
+                slashed_shares.push((
+                    event.timestamp,
+                    event.slash_fraction * Decimal::from_ratio(delegated, total_delegated),
+                ));
+            }
+        }
+    }
+
+    let unbonding_batches = unbond_batches_map()
+        .idx
+        .status
+        .prefix(UnbondBatchStatus::Unbonding as u8)
+        .range(deps.storage, None, None, Order::Ascending)
+        .collect::<StdResult<Vec<_>>>()?;
+    for (id, mut batch) in unbonding_batches {
+        let unbonding_started = batch.status_timestamps.unbonding.unwrap_or_default();
+        let slashed_share: Decimal = slashed_shares
+            .iter()
+            .filter(|(timestamp, _)| unbonding_started <= *timestamp)
+            .map(|(_, share)| *share)
+            .sum();
+        if slashed_share.is_zero() {
+            continue;
+        }
+        let slashing_effect = Decimal::one() - slashed_share.min(Decimal::one());
+        batch.expected_native_asset_amount = batch.expected_native_asset_amount * slashing_effect;
+        batch.slashing_effect =
+            Some(batch.slashing_effect.unwrap_or(Decimal::one()) * slashing_effect);
+        unbond_batches_map().save(deps.storage, id, &batch)?;
+        attrs.push(attr(
+            format!("slashing_effect_{}", id),
+            slashing_effect.to_string(),
+        ));
+    }
+    LAST_SLASH_EVENT_ID.save(deps.storage, &last_slash_event_id)?;
+
+    Ok(())
+}
+
 fn execute_tick_peripheral(
     deps: DepsMut<NeutronQuery>,
     _env: Env,
@@ -810,14 +1122,16 @@ fn execute_tick_peripheral(
             drop_puppeteer_base::msg::Transaction::RedeemShares { .. } => {
                 attrs.push(attr("knot", "038"))
             }
-            drop_puppeteer_base::msg::Transaction::IBCTransfer { reason, .. } => {
-                if reason == IBCTransferReason::LSMShare {
-                    attrs.push(attr("knot", "043"));
-                }
-            }
+            drop_puppeteer_base::msg::Transaction::IBCTransfer { reason, .. } => match reason {
+                IBCTransferReason::LSMShare => attrs.push(attr("knot", "043")),
+                IBCTransferReason::Stake => attrs.push(attr("knot", "055")),
+            },
             drop_puppeteer_base::msg::Transaction::Transfer { .. } => {
                 attrs.push(attr("knot", "035"));
             }
+            drop_puppeteer_base::msg::Transaction::Redelegate { .. } => {
+                attrs.push(attr("knot", "056"));
+            }
             _ => {}
         }
     }
@@ -856,6 +1170,14 @@ fn execute_tick_claiming(
                                 batch.status_timestamps.withdrawing_emergency =
                                     Some(env.block.time.seconds());
                                 attrs.push(attr("unbond_batch_status", "withdrawn_emergency"));
+                            } else if config.pump_contract.is_some() {
+                                // stays withdrawing until the pump delivers the funds
+                                let mut pending_batches = PENDING_DELIVERY_BATCHES
+                                    .may_load(deps.storage)?
+                                    .unwrap_or_default();
+                                pending_batches.push(id);
+                                PENDING_DELIVERY_BATCHES.save(deps.storage, &pending_batches)?;
+                                attrs.push(attr("unbond_batch_status", "awaiting_delivery"));
                             } else {
                                 batch.status = UnbondBatchStatus::Withdrawn;
                                 batch.status_timestamps.withdrawn = Some(env.block.time.seconds());
@@ -900,6 +1222,13 @@ fn execute_tick_claiming(
         FSM.go_to(deps.storage, ContractState::StakingBond)?;
         attrs.push(attr("knot", "017"));
         attrs.push(attr("state", "staking_bond"));
+    } else if let Some(stake_transfer_msg) =
+        get_stake_transfer_msg(deps.as_ref(), &env, config, &info)?
+    {
+        messages.push(stake_transfer_msg);
+        attrs.push(attr("knot", "054"));
+        FSM.go_to(deps.storage, ContractState::StakingTransfer)?;
+        attrs.push(attr("state", "staking_transfer"));
     } else if let Some(unbond_message) =
         get_unbonding_msg(deps.branch(), &env, config, &info, &mut attrs)?
     {
@@ -924,23 +1253,32 @@ fn execute_tick_staking_bond(
     config: &Config,
 ) -> ContractResult<Response<NeutronMsg>> {
     let mut attrs = vec![attr("action", "tick_staking_bond")];
-    let response_msg = get_received_staker_response(deps.as_ref())?;
-    if let drop_staking_base::msg::staker::ResponseHookMsg::Success(response) = response_msg {
-        let balances_response: drop_staking_base::msg::puppeteer::BalancesResponse =
-            deps.querier.query_wasm_smart(
-                config.puppeteer_contract.to_string(),
-                &drop_puppeteer_base::msg::QueryMsg::Extension {
-                    msg: drop_staking_base::msg::puppeteer::QueryExtMsg::Balances {},
-                },
-            )?;
-        if response.remote_height > balances_response.remote_height {
-            return Err(ContractError::PuppeteerBalanceOutdated {
-                ica_height: response.remote_height,
-                control_height: balances_response.remote_height,
-            });
+    match config.staking_path {
+        StakingPath::Staker => {
+            let response_msg = get_received_staker_response(deps.as_ref())?;
+            if let drop_staking_base::msg::staker::ResponseHookMsg::Success(response) = response_msg
+            {
+                let balances_response: drop_staking_base::msg::puppeteer::BalancesResponse =
+                    deps.querier.query_wasm_smart(
+                        config.puppeteer_contract.to_string(),
+                        &drop_puppeteer_base::msg::QueryMsg::Extension {
+                            msg: drop_staking_base::msg::puppeteer::QueryExtMsg::Balances {},
+                        },
+                    )?;
+                if response.remote_height > balances_response.remote_height {
+                    return Err(ContractError::PuppeteerBalanceOutdated {
+                        ica_height: response.remote_height,
+                        control_height: balances_response.remote_height,
+                    });
+                }
+            }
+            LAST_STAKER_RESPONSE.remove(deps.storage);
+        }
+        StakingPath::Puppeteer => {
+            get_received_puppeteer_response(deps.as_ref())?;
+            LAST_PUPPETEER_RESPONSE.remove(deps.storage);
         }
     }
-    LAST_STAKER_RESPONSE.remove(deps.storage);
     let mut messages = vec![];
     attrs.push(attr("knot", "017"));
     if let Some(unbond_message) = get_unbonding_msg(deps.branch(), &env, config, &info, &mut attrs)?
@@ -1050,11 +1388,19 @@ fn execute_bond(
         })?;
         amount = real_amount;
     } else {
-        // if it's not LSM share, we send this amount to the staker
-        msgs.push(CosmosMsg::Bank(BankMsg::Send {
-            to_address: config.staker_contract.to_string(),
-            amount: vec![Coin::new(amount.u128(), denom)],
-        }));
+        match config.staking_path {
+            // if it's not LSM share, we send this amount to the staker
+            StakingPath::Staker => msgs.push(CosmosMsg::Bank(BankMsg::Send {
+                to_address: config.staker_contract.to_string(),
+                amount: vec![Coin::new(amount.u128(), denom)],
+            })),
+            // or keep it until it's transferred to the puppeteer ICA on tick
+            StakingPath::Puppeteer => {
+                PENDING_STAKE_LOCAL.update(deps.storage, |pending| {
+                    StdResult::Ok(pending.unwrap_or_default() + amount)
+                })?;
+            }
+        }
     }
     BONDED_AMOUNT.update(deps.storage, |total| StdResult::Ok(total + amount))?;
     let issue_amount = amount * (Decimal::one() / exchange_rate);
@@ -1137,6 +1483,11 @@ fn execute_update_config(
         config.validators_set_contract = deps.api.addr_validate(&validators_set_contract)?;
         attrs.push(attr("validators_set_contract", validators_set_contract));
     }
+    if let Some(validators_stats_contract) = new_config.validators_stats_contract {
+        config.validators_stats_contract =
+            Some(deps.api.addr_validate(&validators_stats_contract)?);
+        attrs.push(attr("validators_stats_contract", validators_stats_contract));
+    }
     if let Some(base_denom) = new_config.base_denom {
         attrs.push(attr("base_denom", &base_denom));
         config.base_denom = base_denom;
@@ -1199,6 +1550,24 @@ fn execute_update_config(
         attrs.push(attr("min_stake_amount", min_stake_amount));
         config.min_stake_amount = min_stake_amount;
     }
+    if let Some(rebalance_min_amount) = new_config.rebalance_min_amount {
+        attrs.push(attr("rebalance_min_amount", rebalance_min_amount));
+        config.rebalance_min_amount = {
+            if rebalance_min_amount.is_zero() {
+                None
+            } else {
+                Some(rebalance_min_amount)
+            }
+        };
+    }
+    if let Some(staking_path) = new_config.staking_path {
+        attrs.push(attr("staking_path", format!("{:?}", staking_path)));
+        config.staking_path = staking_path;
+    }
+    if let Some(pump_contract) = new_config.pump_contract {
+        config.pump_contract = Some(deps.api.addr_validate(&pump_contract)?);
+        attrs.push(attr("pump_contract", pump_contract));
+    }
 
     CONFIG.save(deps.storage, &config)?;
 
@@ -1309,10 +1678,36 @@ fn check_latest_icq_responses(
 
 pub fn get_stake_bond_msg<T>(
     deps: Deps<NeutronQuery>,
-    _env: &Env,
+    env: &Env,
     config: &Config,
     info: &MessageInfo,
 ) -> ContractResult<Option<CosmosMsg<T>>> {
+    if config.staking_path == StakingPath::Puppeteer {
+        let pending_stake = PENDING_STAKE_REMOTE
+            .may_load(deps.storage)?
+            .unwrap_or_default();
+        if pending_stake.is_zero() {
+            return Ok(None);
+        }
+        let to_delegate: Vec<(String, Uint128)> = deps.querier.query_wasm_smart(
+            &config.strategy_contract,
+            &drop_staking_base::msg::strategy::QueryMsg::CalcDeposit {
+                deposit: pending_stake,
+            },
+        )?;
+        // every validator may be at its cap already
+        if to_delegate.is_empty() {
+            return Ok(None);
+        }
+        return Ok(Some(CosmosMsg::<T>::Wasm(WasmMsg::Execute {
+            contract_addr: config.puppeteer_contract.to_string(),
+            msg: to_json_binary(&drop_staking_base::msg::puppeteer::ExecuteMsg::Delegate {
+                items: to_delegate,
+                reply_to: env.contract.address.to_string(),
+            })?,
+            funds: info.funds.clone(),
+        })));
+    }
     let staker_pending_stake: Result<Uint128, _> = deps.querier.query_wasm_smart(
         config.staker_contract.to_string(),
         &drop_staking_base::msg::staker::QueryMsg::NonStakedBalance {},
@@ -1321,12 +1716,24 @@ pub fn get_stake_bond_msg<T>(
         if staker_pending_stake.is_zero() {
             return Ok(None);
         }
+        // a tick may have a transfer or stake in flight, Stake would fail until it's acked
+        let staker_tx_state: drop_staking_base::state::staker::TxState =
+            deps.querier.query_wasm_smart(
+                config.staker_contract.to_string(),
+                &drop_staking_base::msg::staker::QueryMsg::TxState {},
+            )?;
+        if staker_tx_state.status != drop_staking_base::state::staker::TxStateStatus::Idle {
+            return Ok(None);
+        }
         let to_delegate: Vec<(String, Uint128)> = deps.querier.query_wasm_smart(
             &config.strategy_contract,
             &drop_staking_base::msg::strategy::QueryMsg::CalcDeposit {
                 deposit: staker_pending_stake,
             },
         )?;
+        if to_delegate.is_empty() {
+            return Ok(None);
+        }
         return Ok(Some(CosmosMsg::<T>::Wasm(WasmMsg::Execute {
             contract_addr: config.staker_contract.to_string(),
             msg: to_json_binary(&drop_staking_base::msg::staker::ExecuteMsg::Stake {
@@ -1338,6 +1745,44 @@ pub fn get_stake_bond_msg<T>(
     Ok(None)
 }
 
+fn get_stake_transfer_msg<T>(
+    deps: Deps<NeutronQuery>,
+    env: &Env,
+    config: &Config,
+    info: &MessageInfo,
+) -> ContractResult<Option<CosmosMsg<T>>> {
+    if config.staking_path != StakingPath::Puppeteer {
+        return Ok(None);
+    }
+    let pending_stake = PENDING_STAKE_LOCAL
+        .may_load(deps.storage)?
+        .unwrap_or_default();
+    // a transfer in flight is still counted as pending, so never send more than we hold
+    let balance = deps
+        .querier
+        .query_balance(&env.contract.address, &config.base_denom)?
+        .amount;
+    let amount = pending_stake.min(balance);
+    if amount.is_zero() {
+        return Ok(None);
+    }
+    Ok(Some(CosmosMsg::Wasm(WasmMsg::Execute {
+        contract_addr: config.puppeteer_contract.to_string(),
+        msg: to_json_binary(
+            &drop_staking_base::msg::puppeteer::ExecuteMsg::IBCTransfer {
+                reason: IBCTransferReason::Stake,
+                memo: None,
+                reply_to: env.contract.address.to_string(),
+            },
+        )?,
+        funds: {
+            let mut all_funds = vec![Coin::new(amount.u128(), config.base_denom.to_string())];
+            all_funds.extend(info.funds.clone());
+            all_funds
+        },
+    })))
+}
+
 fn get_unbonding_msg<T>(
     deps: DepsMut<NeutronQuery>,
     env: &Env,
@@ -1550,6 +1995,42 @@ pub fn get_pending_redeem_msg<T>(
     })))
 }
 
+/// Takes the first move of the rebalancing plan, the rest of the plan is
+/// recalculated on the next ticks against updated delegations
+fn get_rebalance_msg<T>(
+    deps: Deps<NeutronQuery>,
+    config: &Config,
+    env: &Env,
+    funds: Vec<cosmwasm_std::Coin>,
+) -> ContractResult<Option<CosmosMsg<T>>> {
+    let min_amount = match config.rebalance_min_amount {
+        Some(min_amount) => min_amount,
+        None => return Ok(None),
+    };
+    let moves: Vec<(String, String, Uint128)> = deps.querier.query_wasm_smart(
+        &config.strategy_contract,
+        &drop_staking_base::msg::strategy::QueryMsg::CalcRebalance {
+            max_moves: 1,
+            min_amount,
+        },
+    )?;
+    match moves.into_iter().next() {
+        Some((validator_from, validator_to, amount)) => {
+            Ok(Some(CosmosMsg::Wasm(WasmMsg::Execute {
+                contract_addr: config.puppeteer_contract.to_string(),
+                msg: to_json_binary(&drop_staking_base::msg::puppeteer::ExecuteMsg::Redelegate {
+                    validator_from,
+                    validator_to,
+                    amount,
+                    reply_to: env.contract.address.to_string(),
+                })?,
+                funds,
+            })))
+        }
+        None => Ok(None),
+    }
+}
+
 fn get_pending_lsm_share_msg<T, X: CustomQuery>(
     deps: Deps<X>,
     config: &Config,
@@ -1565,6 +2046,7 @@ fn get_pending_lsm_share_msg<T, X: CustomQuery>(
                 msg: to_json_binary(
                     &drop_staking_base::msg::puppeteer::ExecuteMsg::IBCTransfer {
                         reason: IBCTransferReason::LSMShare,
+                        memo: None,
                         reply_to: env.contract.address.to_string(),
                     },
                 )?,
@@ -3,17 +3,22 @@ use crate::contract::{
     execute, query,
 };
 use cosmwasm_std::{
-    from_json,
+    attr, from_json,
     testing::{mock_env, mock_info, MockApi, MockStorage},
     to_json_binary, Addr, BankMsg, Coin, CosmosMsg, Decimal, Decimal256, Event, OwnedDeps,
     Response, SubMsg, Timestamp, Uint128, WasmMsg,
 };
-use drop_helpers::testing::{mock_dependencies, WasmMockQuerier};
+use drop_helpers::{
+    ica::IcaRecoveredHookMsg,
+    testing::{mock_dependencies, WasmMockQuerier},
+};
 use drop_puppeteer_base::{
     msg::TransferReadyBatchesMsg,
     state::{Delegations, DropDelegation, RedeemShareItem},
 };
-use drop_staking_base::state::core::{FAILED_BATCH_ID, LAST_STAKER_RESPONSE};
+use drop_staking_base::state::core::{
+    FAILED_BATCH_ID, LAST_STAKER_RESPONSE, PENDING_DELIVERY_BATCHES, PUMP_DELIVERED_AMOUNT,
+};
 use drop_staking_base::{
     error::core::ContractError,
     msg::{
@@ -23,10 +28,11 @@ use drop_staking_base::{
         strategy::QueryMsg as StrategyQueryMsg,
     },
     state::core::{
-        unbond_batches_map, Config, ConfigOptional, ContractState, UnbondBatch, UnbondBatchStatus,
-        UnbondBatchStatusTimestamps, BONDED_AMOUNT, CONFIG, FSM, LAST_ICA_CHANGE_HEIGHT,
-        LAST_IDLE_CALL, LAST_LSM_REDEEM, LAST_PUPPETEER_RESPONSE, LD_DENOM, LSM_SHARES_TO_REDEEM,
-        PENDING_LSM_SHARES, TOTAL_LSM_SHARES, UNBOND_BATCH_ID,
+        unbond_batches_map, Config, ConfigOptional, ContractState, StakingPath, UnbondBatch,
+        UnbondBatchStatus, UnbondBatchStatusTimestamps, BONDED_AMOUNT, CONFIG, FSM,
+        LAST_ICA_CHANGE_HEIGHT, LAST_IDLE_CALL, LAST_LSM_REDEEM, LAST_PUPPETEER_RESPONSE,
+        LAST_SLASH_EVENT_ID, LD_DENOM, LSM_SHARES_TO_REDEEM, PENDING_LSM_SHARES,
+        PENDING_STAKE_LOCAL, PENDING_STAKE_REMOTE, TOTAL_LSM_SHARES, UNBOND_BATCH_ID,
     },
 };
 use neutron_sdk::{
@@ -54,6 +60,7 @@ fn get_default_config(
         withdrawal_voucher_contract: Addr::unchecked("withdrawal_voucher_contract"),
         withdrawal_manager_contract: Addr::unchecked("withdrawal_manager_contract"),
         validators_set_contract: Addr::unchecked("validators_set_contract"),
+        validators_stats_contract: None,
         staker_contract: Addr::unchecked("staker_contract"),
         base_denom: "base_denom".to_string(),
         remote_denom: "remote_denom".to_string(),
@@ -70,6 +77,9 @@ fn get_default_config(
         emergency_address: None,
         min_stake_amount: Uint128::new(100),
         icq_update_delay: 5,
+        rebalance_min_amount: None,
+        staking_path: StakingPath::Staker,
+        pump_contract: None,
     }
 }
 
@@ -112,6 +122,7 @@ fn test_update_config() {
             withdrawal_voucher_contract: "old_withdrawal_voucher_contract".to_string(),
             withdrawal_manager_contract: "old_withdrawal_manager_contract".to_string(),
             validators_set_contract: "old_validators_set_contract".to_string(),
+            validators_stats_contract: None,
             base_denom: "old_base_denom".to_string(),
             remote_denom: "old_remote_denom".to_string(),
             idle_min_interval: 12,
@@ -128,6 +139,8 @@ fn test_update_config() {
             min_stake_amount: Uint128::new(1200),
             owner: "admin".to_string(),
             icq_update_delay: 5,
+            rebalance_min_amount: None,
+            staking_path: None,
         },
     )
     .unwrap();
@@ -144,6 +157,7 @@ fn test_update_config() {
         withdrawal_voucher_contract: Some("new_withdrawal_voucher_contract".to_string()),
         withdrawal_manager_contract: Some("new_withdrawal_manager_contract".to_string()),
         validators_set_contract: Some("new_validators_set_contract".to_string()),
+        validators_stats_contract: Some("new_validators_stats_contract".to_string()),
         base_denom: Some("new_base_denom".to_string()),
         remote_denom: Some("new_remote_denom".to_string()),
         idle_min_interval: Some(2),
@@ -159,6 +173,9 @@ fn test_update_config() {
         bond_limit: Some(Uint128::new(2)),
         emergency_address: Some("new_emergency_address".to_string()),
         min_stake_amount: Some(Uint128::new(200)),
+        rebalance_min_amount: Some(Uint128::new(10)),
+        staking_path: Some(StakingPath::Puppeteer),
+        pump_contract: Some("new_pump_contract".to_string()),
     };
     let expected_config = Config {
         token_contract: Addr::unchecked("new_token_contract"),
@@ -168,6 +185,7 @@ fn test_update_config() {
         withdrawal_voucher_contract: Addr::unchecked("new_withdrawal_voucher_contract"),
         withdrawal_manager_contract: Addr::unchecked("new_withdrawal_manager_contract"),
         validators_set_contract: Addr::unchecked("new_validators_set_contract"),
+        validators_stats_contract: Some(Addr::unchecked("new_validators_stats_contract")),
         base_denom: "new_base_denom".to_string(),
         remote_denom: "new_remote_denom".to_string(),
         idle_min_interval: 2,
@@ -183,6 +201,9 @@ fn test_update_config() {
         emergency_address: Some("new_emergency_address".to_string()),
         min_stake_amount: Uint128::new(200),
         icq_update_delay: 5,
+        rebalance_min_amount: Some(Uint128::new(10)),
+        staking_path: StakingPath::Puppeteer,
+        pump_contract: Some(Addr::unchecked("new_pump_contract")),
     };
 
     let res = execute(
@@ -198,6 +219,280 @@ fn test_update_config() {
     assert_eq!(config, expected_config);
 }
 
+fn pump_success_hook(amount: u128, receiver: &str) -> ExecuteMsg {
+    ExecuteMsg::PumpHook(Box::new(
+        drop_staking_base::msg::pump::ResponseHookMsg::Success(
+            drop_staking_base::msg::pump::ResponseHookSuccessMsg {
+                request_id: 1,
+                request: RequestPacket {
+                    sequence: Some(1),
+                    source_port: None,
+                    source_channel: None,
+                    destination_port: None,
+                    destination_channel: None,
+                    data: None,
+                    timeout_height: None,
+                    timeout_timestamp: None,
+                },
+                coin: Coin::new(amount, "remote_denom"),
+                receiver: receiver.to_string(),
+                local_height: 100,
+            },
+        ),
+    ))
+}
+
+#[test]
+fn test_pump_hook() {
+    let mut deps = mock_dependencies(&[]);
+    CONFIG
+        .save(
+            deps.as_mut().storage,
+            &Config {
+                pump_contract: Some(Addr::unchecked("pump_contract")),
+                ..get_default_config(1000, 10, 10_000_000_000, 10, 6000, Uint128::one())
+            },
+        )
+        .unwrap();
+    for (id, unbonded_amount) in [(0u128, 100u128), (1, 200)] {
+        unbond_batches_map()
+            .save(
+                deps.as_mut().storage,
+                id,
+                &UnbondBatch {
+                    total_dasset_amount_to_withdraw: Uint128::from(unbonded_amount),
+                    expected_native_asset_amount: Uint128::from(unbonded_amount),
+                    total_unbond_items: 1,
+                    status: UnbondBatchStatus::Withdrawing,
+                    expected_release_time: 9000,
+                    slashing_effect: Some(Decimal::one()),
+                    unbonded_amount: Some(Uint128::from(unbonded_amount)),
+                    withdrawn_amount: None,
+                    status_timestamps: get_default_unbond_batch_status_timestamps(),
+                },
+            )
+            .unwrap();
+    }
+    PENDING_DELIVERY_BATCHES
+        .save(deps.as_mut().storage, &vec![0, 1])
+        .unwrap();
+
+    let res = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("stranger", &[]),
+        pump_success_hook(150, "withdrawal_manager_contract"),
+    );
+    assert_eq!(res, Err(ContractError::Unauthorized {}));
+
+    // the ack doesn't withdraw anything while the funds are in flight
+    let res = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("pump_contract", &[]),
+        pump_success_hook(150, "withdrawal_manager_contract"),
+    )
+    .unwrap();
+    assert_eq!(
+        res,
+        Response::new().add_event(
+            Event::new("crates.io:drop-staking__drop-core-execute-pump_hook").add_attributes(vec![
+                attr("action", "pump_hook"),
+                attr("in_flight", "150remote_denom"),
+                attr("receiver", "withdrawal_manager_contract"),
+            ])
+        )
+    );
+    assert_eq!(
+        unbond_batches_map()
+            .load(deps.as_ref().storage, 0)
+            .unwrap()
+            .status,
+        UnbondBatchStatus::Withdrawing
+    );
+
+    let delivery_received = |amount: u128| ExecuteMsg::DeliveryReceived {
+        amount: Uint128::from(amount),
+    };
+    let res = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("pump_contract", &[]),
+        delivery_received(150),
+    );
+    assert_eq!(res, Err(ContractError::Unauthorized {}));
+
+    let env = mock_env();
+    let res = execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info("withdrawal_manager_contract", &[]),
+        delivery_received(150),
+    )
+    .unwrap();
+    assert_eq!(
+        res,
+        Response::new().add_event(
+            Event::new("crates.io:drop-staking__drop-core-execute-delivery_received")
+                .add_attributes(vec![
+                    attr("action", "delivery_received"),
+                    attr("withdrawn_batch_id", "0"),
+                ])
+        )
+    );
+    let batch = unbond_batches_map().load(deps.as_ref().storage, 0).unwrap();
+    assert_eq!(batch.status, UnbondBatchStatus::Withdrawn);
+    assert_eq!(
+        batch.status_timestamps.withdrawn,
+        Some(env.block.time.seconds())
+    );
+    assert_eq!(
+        unbond_batches_map()
+            .load(deps.as_ref().storage, 1)
+            .unwrap()
+            .status,
+        UnbondBatchStatus::Withdrawing
+    );
+    assert_eq!(
+        PUMP_DELIVERED_AMOUNT.load(deps.as_ref().storage).unwrap(),
+        Uint128::from(50u128)
+    );
+
+    execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info("withdrawal_manager_contract", &[]),
+        delivery_received(160),
+    )
+    .unwrap();
+    assert_eq!(
+        unbond_batches_map()
+            .load(deps.as_ref().storage, 1)
+            .unwrap()
+            .status,
+        UnbondBatchStatus::Withdrawn
+    );
+    // the excess is not credited to future batches
+    assert_eq!(
+        PUMP_DELIVERED_AMOUNT.load(deps.as_ref().storage).unwrap(),
+        Uint128::zero()
+    );
+    assert!(PENDING_DELIVERY_BATCHES
+        .load(deps.as_ref().storage)
+        .unwrap()
+        .is_empty());
+
+    // nothing is awaited
+    let res = execute(
+        deps.as_mut(),
+        env,
+        mock_info("withdrawal_manager_contract", &[]),
+        delivery_received(100),
+    )
+    .unwrap();
+    assert_eq!(res.events[0].attributes[1], attr("skipped", "100"));
+    assert_eq!(
+        PUMP_DELIVERED_AMOUNT.load(deps.as_ref().storage).unwrap(),
+        Uint128::zero()
+    );
+}
+
+#[test]
+fn test_mark_batches_withdrawn() {
+    let mut deps = mock_dependencies(&[]);
+    let deps_mut = deps.as_mut();
+    cw_ownable::initialize_owner(deps_mut.storage, deps_mut.api, Some("admin")).unwrap();
+    for id in [0u128, 1] {
+        unbond_batches_map()
+            .save(
+                deps.as_mut().storage,
+                id,
+                &UnbondBatch {
+                    total_dasset_amount_to_withdraw: Uint128::from(100u128),
+                    expected_native_asset_amount: Uint128::from(100u128),
+                    total_unbond_items: 1,
+                    status: UnbondBatchStatus::Withdrawing,
+                    expected_release_time: 9000,
+                    slashing_effect: Some(Decimal::one()),
+                    unbonded_amount: Some(Uint128::from(100u128)),
+                    withdrawn_amount: None,
+                    status_timestamps: get_default_unbond_batch_status_timestamps(),
+                },
+            )
+            .unwrap();
+    }
+    PENDING_DELIVERY_BATCHES
+        .save(deps.as_mut().storage, &vec![0, 1])
+        .unwrap();
+    PUMP_DELIVERED_AMOUNT
+        .save(deps.as_mut().storage, &Uint128::from(30u128))
+        .unwrap();
+
+    let res = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("stranger", &[]),
+        ExecuteMsg::MarkBatchesWithdrawn { batch_ids: vec![1] },
+    );
+    assert_eq!(
+        res,
+        Err(ContractError::OwnershipError(
+            cw_ownable::OwnershipError::NotOwner
+        ))
+    );
+    let res = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("admin", &[]),
+        ExecuteMsg::MarkBatchesWithdrawn { batch_ids: vec![2] },
+    );
+    assert_eq!(
+        res,
+        Err(ContractError::BatchNotAwaitingDelivery { batch_id: 2 })
+    );
+
+    execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("admin", &[]),
+        ExecuteMsg::MarkBatchesWithdrawn { batch_ids: vec![1] },
+    )
+    .unwrap();
+    assert_eq!(
+        unbond_batches_map()
+            .load(deps.as_ref().storage, 1)
+            .unwrap()
+            .status,
+        UnbondBatchStatus::Withdrawn
+    );
+    assert_eq!(
+        PENDING_DELIVERY_BATCHES
+            .load(deps.as_ref().storage)
+            .unwrap(),
+        vec![0]
+    );
+    assert_eq!(
+        PUMP_DELIVERED_AMOUNT.load(deps.as_ref().storage).unwrap(),
+        Uint128::from(30u128)
+    );
+
+    execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("admin", &[]),
+        ExecuteMsg::MarkBatchesWithdrawn { batch_ids: vec![0] },
+    )
+    .unwrap();
+    assert!(PENDING_DELIVERY_BATCHES
+        .load(deps.as_ref().storage)
+        .unwrap()
+        .is_empty());
+    assert_eq!(
+        PUMP_DELIVERED_AMOUNT.load(deps.as_ref().storage).unwrap(),
+        Uint128::zero()
+    );
+}
+
 #[test]
 fn test_update_withdrawn_amount() {
     let mut deps = mock_dependencies(&[]);
@@ -271,6 +566,137 @@ fn test_update_withdrawn_amount() {
     assert_eq!(unbonding_err, ContractError::BatchNotWithdrawn {});
 }
 
+#[test]
+fn test_apply_slash_events() {
+    let mut deps = mock_dependencies(&[]);
+    let mut config = get_default_config(1000, 10, 10_000_000_000, 10, 6000, Uint128::one());
+    config.validators_stats_contract = Some(Addr::unchecked("validators_stats_contract"));
+    deps.querier
+        .add_wasm_query_response("validators_stats_contract", |_| {
+            to_json_binary(&vec![(
+                3u64,
+                drop_staking_base::state::validatorsstats::SlashEvent {
+                    valoper_address: "valoper1".to_string(),
+                    remote_height: 10,
+                    local_height: 20,
+                    timestamp: 1000,
+                    prev_tokens_per_share: Decimal256::one(),
+                    tokens_per_share: Decimal256::percent(90),
+                    slash_fraction: Decimal::percent(10),
+                },
+            )])
+            .unwrap()
+        });
+    deps.querier
+        .add_wasm_query_response("puppeteer_contract", |_| {
+            to_json_binary(&DelegationsResponse {
+                delegations: Delegations {
+                    delegations: vec![
+                        DropDelegation {
+                            delegator: Addr::unchecked("ica_address"),
+                            validator: "valoper1".to_string(),
+                            amount: Coin {
+                                denom: "remote_denom".to_string(),
+                                amount: Uint128::new(500),
+                            },
+                            share_ratio: Decimal256::one(),
+                        },
+                        DropDelegation {
+                            delegator: Addr::unchecked("ica_address"),
+                            validator: "valoper2".to_string(),
+                            amount: Coin {
+                                denom: "remote_denom".to_string(),
+                                amount: Uint128::new(500),
+                            },
+                            share_ratio: Decimal256::one(),
+                        },
+                    ],
+                },
+                remote_height: 10u64,
+                local_height: 10u64,
+                timestamp: Timestamp::from_seconds(90001),
+            })
+            .unwrap()
+        });
+    // started unbonding after the slash event was detected and before it
+    for (id, unbonding) in [(0u128, 2000u64), (1u128, 500u64)] {
+        unbond_batches_map()
+            .save(
+                deps.as_mut().storage,
+                id,
+                &UnbondBatch {
+                    total_dasset_amount_to_withdraw: Uint128::from(1000u128),
+                    expected_native_asset_amount: Uint128::from(1000u128),
+                    total_unbond_items: 1,
+                    status: UnbondBatchStatus::Unbonding,
+                    expected_release_time: 9000,
+                    slashing_effect: None,
+                    unbonded_amount: None,
+                    withdrawn_amount: None,
+                    status_timestamps: UnbondBatchStatusTimestamps {
+                        unbonding: Some(unbonding),
+                        ..get_default_unbond_batch_status_timestamps()
+                    },
+                },
+            )
+            .unwrap();
+    }
+
+    let mut attrs = vec![];
+    crate::contract::apply_slash_events(deps.as_mut(), &config, &mut attrs).unwrap();
+
+    let batch = unbond_batches_map().load(deps.as_ref().storage, 0).unwrap();
+    assert_eq!(batch.expected_native_asset_amount, Uint128::from(1000u128));
+    assert_eq!(batch.slashing_effect, None);
+    let batch = unbond_batches_map().load(deps.as_ref().storage, 1).unwrap();
+    assert_eq!(batch.expected_native_asset_amount, Uint128::from(950u128));
+    assert_eq!(batch.slashing_effect, Some(Decimal::percent(95)));
+    assert_eq!(
+        LAST_SLASH_EVENT_ID.load(deps.as_ref().storage).unwrap(),
+        3u64
+    );
+    assert_eq!(attrs, vec![attr("slashing_effect_1", "0.95")]);
+}
+
+#[test]
+fn test_apply_slash_events_query_failed() {
+    let mut deps = mock_dependencies(&[]);
+    let mut config = get_default_config(1000, 10, 10_000_000_000, 10, 6000, Uint128::one());
+    config.validators_stats_contract = Some(Addr::unchecked("validators_stats_contract"));
+    deps.querier
+        .add_wasm_query_response("validators_stats_contract", |_| {
+            to_json_binary(&vec![(
+                3u64,
+                drop_staking_base::state::validatorsstats::SlashEvent {
+                    valoper_address: "valoper1".to_string(),
+                    remote_height: 10,
+                    local_height: 20,
+                    timestamp: 1000,
+                    prev_tokens_per_share: Decimal256::one(),
+                    tokens_per_share: Decimal256::percent(90),
+                    slash_fraction: Decimal::percent(10),
+                },
+            )])
+            .unwrap()
+        });
+    deps.querier
+        .add_wasm_query_response("puppeteer_contract", |_| {
+            to_json_binary(&cosmwasm_std::Empty {}).unwrap()
+        });
+
+    let mut attrs = vec![];
+    crate::contract::apply_slash_events(deps.as_mut(), &config, &mut attrs).unwrap();
+
+    assert_eq!(
+        LAST_SLASH_EVENT_ID.may_load(deps.as_ref().storage).unwrap(),
+        None
+    );
+    assert_eq!(
+        attrs,
+        vec![attr("slash_events", "delegations_query_failed")]
+    );
+}
+
 #[test]
 fn test_execute_reset_bonded_amount() {
     let mut deps = mock_dependencies(&[]);
@@ -383,6 +809,7 @@ fn test_execute_tick_idle_get_pending_lsm_shares_transfer() {
                 msg: to_json_binary(
                     &drop_staking_base::msg::puppeteer::ExecuteMsg::IBCTransfer {
                         reason: drop_puppeteer_base::msg::IBCTransferReason::LSMShare,
+                        memo: None,
                         reply_to: "cosmos2contract".to_string(),
                     }
                 )
@@ -392,6 +819,110 @@ fn test_execute_tick_idle_get_pending_lsm_shares_transfer() {
     );
 }
 
+#[test]
+fn test_execute_tick_idle_rebalance() {
+    let mut deps = mock_dependencies(&[]);
+    let mut config = get_default_config(1000, 10, 10_000_000_000, 10, 6000, Uint128::one());
+    config.rebalance_min_amount = Some(Uint128::new(10));
+    CONFIG.save(deps.as_mut().storage, &config).unwrap();
+    LD_DENOM
+        .save(deps.as_mut().storage, &"ld_denom".into())
+        .unwrap();
+    FSM.set_initial_state(deps.as_mut().storage, ContractState::Idle)
+        .unwrap();
+    LAST_IDLE_CALL.save(deps.as_mut().storage, &0).unwrap();
+    LAST_ICA_CHANGE_HEIGHT
+        .save(deps.as_mut().storage, &0)
+        .unwrap();
+    deps.querier
+        .add_wasm_query_response("puppeteer_contract", |_| {
+            to_json_binary(&BalancesResponse {
+                balances: Balances { coins: vec![] },
+                remote_height: 10u64,
+                local_height: 10u64,
+                timestamp: Timestamp::from_seconds(90001),
+            })
+            .unwrap()
+        });
+    deps.querier
+        .add_wasm_query_response("puppeteer_contract", |_| {
+            to_json_binary(&DelegationsResponse {
+                delegations: Delegations {
+                    delegations: vec![],
+                },
+                remote_height: 10u64,
+                local_height: 10u64,
+                timestamp: Timestamp::from_seconds(90001),
+            })
+            .unwrap()
+        });
+    deps.querier
+        .add_wasm_query_response(MOCK_STRATEGY_CONTRACT_ADDR, |msg| {
+            let q: drop_staking_base::msg::strategy::QueryMsg = from_json(msg).unwrap();
+            assert_eq!(
+                q,
+                drop_staking_base::msg::strategy::QueryMsg::CalcRebalance {
+                    max_moves: 1,
+                    min_amount: Uint128::new(10),
+                }
+            );
+            to_json_binary(&vec![(
+                "valoper1".to_string(),
+                "valoper2".to_string(),
+                Uint128::new(100),
+            )])
+            .unwrap()
+        });
+    TOTAL_LSM_SHARES.save(deps.as_mut().storage, &0).unwrap();
+    BONDED_AMOUNT
+        .save(deps.as_mut().storage, &Uint128::zero())
+        .unwrap();
+    LAST_LSM_REDEEM.save(deps.as_mut().storage, &0).unwrap();
+    let mut env = mock_env();
+    env.block.time = Timestamp::from_seconds(100);
+    let res = execute(
+        deps.as_mut(),
+        env,
+        mock_info("admin", &[]),
+        ExecuteMsg::Tick {},
+    )
+    .unwrap();
+    assert_eq!(
+        res,
+        Response::new()
+            .add_event(
+                Event::new("crates.io:drop-staking__drop-core-execute-tick_idle").add_attributes(
+                    vec![
+                        ("action", "tick_idle"),
+                        ("knot", "000"),
+                        ("knot", "002"),
+                        ("knot", "003"),
+                        ("knot", "036"),
+                        ("knot", "041"),
+                        ("knot", "051"),
+                        ("knot", "052"),
+                        ("knot", "053")
+                    ]
+                )
+            )
+            .add_submessages(vec![SubMsg::new(CosmosMsg::Wasm(WasmMsg::Execute {
+                contract_addr: "puppeteer_contract".to_string(),
+                msg: to_json_binary(&drop_staking_base::msg::puppeteer::ExecuteMsg::Redelegate {
+                    validator_from: "valoper1".to_string(),
+                    validator_to: "valoper2".to_string(),
+                    amount: Uint128::new(100),
+                    reply_to: "cosmos2contract".to_string(),
+                })
+                .unwrap(),
+                funds: vec![],
+            }))])
+    );
+    assert_eq!(
+        FSM.get_current_state(deps.as_ref().storage).unwrap(),
+        ContractState::Rebalancing
+    );
+}
+
 #[test]
 fn test_idle_tick_pending_lsm_redeem() {
     let mut deps = mock_dependencies(&[]);
@@ -1050,6 +1581,10 @@ fn test_tick_idle_staking_bond() {
         .add_wasm_query_response("staker_contract", |_| {
             to_json_binary(&Uint128::from(100000u128)).unwrap()
         });
+    deps.querier
+        .add_wasm_query_response("staker_contract", |_| {
+            to_json_binary(&drop_staking_base::state::staker::TxState::default()).unwrap()
+        });
     deps.querier
         .add_wasm_query_response("strategy_contract", |msg| {
             let q: drop_staking_base::msg::strategy::QueryMsg = from_json(msg).unwrap();
@@ -2831,6 +3366,175 @@ fn test_bond_wo_receiver() {
     );
 }
 
+#[test]
+fn test_bond_puppeteer_staking_path() {
+    let mut deps = mock_dependencies(&[]);
+    let mut env = mock_env();
+    env.block.time = Timestamp::from_seconds(1000);
+    FSM.set_initial_state(deps.as_mut().storage, ContractState::Idle)
+        .unwrap();
+    BONDED_AMOUNT
+        .save(deps.as_mut().storage, &Uint128::zero())
+        .unwrap();
+    let mut config = get_default_config(1000, 3, 100, 100, 600, Uint128::one());
+    config.staking_path = StakingPath::Puppeteer;
+    CONFIG.save(deps.as_mut().storage, &config).unwrap();
+    LD_DENOM
+        .save(deps.as_mut().storage, &"ld_denom".into())
+        .unwrap();
+    let res = execute(
+        deps.as_mut(),
+        env,
+        mock_info("some", &[Coin::new(1000, "base_denom")]),
+        ExecuteMsg::Bond {
+            receiver: None,
+            r#ref: None,
+        },
+    )
+    .unwrap();
+    assert_eq!(
+        PENDING_STAKE_LOCAL.load(deps.as_ref().storage).unwrap(),
+        Uint128::from(1000u128)
+    );
+    assert_eq!(
+        res.messages,
+        vec![SubMsg::new(CosmosMsg::Wasm(WasmMsg::Execute {
+            contract_addr: "token_contract".to_string(),
+            msg: to_json_binary(&drop_staking_base::msg::token::ExecuteMsg::Mint {
+                amount: Uint128::from(1000u128),
+                receiver: "some".to_string()
+            })
+            .unwrap(),
+            funds: vec![],
+        }))]
+    );
+}
+
+#[test]
+fn test_puppeteer_hook_pending_stake_accounting() {
+    let mut deps = mock_dependencies(&[]);
+    let mut config = get_default_config(1000, 3, 100, 100, 600, Uint128::one());
+    config.staking_path = StakingPath::Puppeteer;
+    CONFIG.save(deps.as_mut().storage, &config).unwrap();
+    PENDING_STAKE_LOCAL
+        .save(deps.as_mut().storage, &Uint128::from(1000u128))
+        .unwrap();
+    let hook = |transaction| {
+        ExecuteMsg::PuppeteerHook(Box::new(
+            drop_puppeteer_base::msg::ResponseHookMsg::Success(
+                drop_puppeteer_base::msg::ResponseHookSuccessMsg {
+                    request_id: 0u64,
+                    request: null_request_packet(),
+                    local_height: 9u64,
+                    remote_height: 9u64,
+                    transaction,
+                    answers: vec![],
+                },
+            ),
+        ))
+    };
+    execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info(MOCK_PUPPETEER_CONTRACT_ADDR, &[]),
+        hook(drop_puppeteer_base::msg::Transaction::IBCTransfer {
+            denom: "base_denom".to_string(),
+            amount: 600u128,
+            recipient: "ICA".to_string(),
+            reason: drop_puppeteer_base::msg::IBCTransferReason::Stake,
+        }),
+    )
+    .unwrap();
+    assert_eq!(
+        PENDING_STAKE_LOCAL.load(deps.as_ref().storage).unwrap(),
+        Uint128::from(400u128)
+    );
+    assert_eq!(
+        PENDING_STAKE_REMOTE.load(deps.as_ref().storage).unwrap(),
+        Uint128::from(600u128)
+    );
+    execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info(MOCK_PUPPETEER_CONTRACT_ADDR, &[]),
+        hook(drop_puppeteer_base::msg::Transaction::Delegate {
+            interchain_account_id: "DROP".to_string(),
+            items: vec![
+                ("valoper1".to_string(), Uint128::from(200u128)),
+                ("valoper2".to_string(), Uint128::from(400u128)),
+            ],
+            denom: "remote_denom".to_string(),
+        }),
+    )
+    .unwrap();
+    assert_eq!(
+        PENDING_STAKE_REMOTE.load(deps.as_ref().storage).unwrap(),
+        Uint128::zero()
+    );
+}
+
+#[test]
+fn test_puppeteer_hook_stake_transfer_refund() {
+    let mut deps = mock_dependencies(&[]);
+    let mut config = get_default_config(1000, 3, 100, 100, 600, Uint128::one());
+    config.staking_path = StakingPath::Puppeteer;
+    CONFIG.save(deps.as_mut().storage, &config).unwrap();
+    PENDING_STAKE_LOCAL
+        .save(deps.as_mut().storage, &Uint128::from(1000u128))
+        .unwrap();
+    FSM.set_initial_state(deps.as_mut().storage, ContractState::Idle)
+        .unwrap();
+    let hook =
+        ExecuteMsg::PuppeteerHook(Box::new(drop_puppeteer_base::msg::ResponseHookMsg::Error(
+            drop_puppeteer_base::msg::ResponseHookErrorMsg {
+                request_id: 0u64,
+                request: null_request_packet(),
+                transaction: drop_puppeteer_base::msg::Transaction::IBCTransfer {
+                    denom: "base_denom".to_string(),
+                    amount: 600u128,
+                    recipient: "ICA".to_string(),
+                    reason: drop_puppeteer_base::msg::IBCTransferReason::Stake,
+                },
+                details: "Timeout".to_string(),
+            },
+        )));
+    // the refund is returned along with the hook, so it's still pending
+    FSM.go_to(deps.as_mut().storage, ContractState::StakingTransfer)
+        .unwrap();
+    execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info(
+            MOCK_PUPPETEER_CONTRACT_ADDR,
+            &[Coin::new(600, "base_denom")],
+        ),
+        hook.clone(),
+    )
+    .unwrap();
+    assert_eq!(
+        PENDING_STAKE_LOCAL.load(deps.as_ref().storage).unwrap(),
+        Uint128::from(1000u128)
+    );
+    // without the refund the transferred amount is no longer pending
+    FSM.go_to(deps.as_mut().storage, ContractState::StakingTransfer)
+        .unwrap();
+    execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info(MOCK_PUPPETEER_CONTRACT_ADDR, &[]),
+        hook,
+    )
+    .unwrap();
+    assert_eq!(
+        PENDING_STAKE_LOCAL.load(deps.as_ref().storage).unwrap(),
+        Uint128::from(400u128)
+    );
+    assert_eq!(
+        FSM.get_current_state(deps.as_ref().storage).unwrap(),
+        ContractState::Idle
+    );
+}
+
 #[test]
 fn test_bond_with_receiver() {
     let mut deps = mock_dependencies(&[]);
@@ -4252,3 +4956,140 @@ mod pending_redeem_shares {
         );
     }
 }
+
+#[test]
+fn test_execute_ica_recovered_hook() {
+    let mut deps = mock_dependencies(&[]);
+    let mut config = get_default_config(1000, 10, 10_000_000_000, 10, 6000, Uint128::one());
+    config.pump_contract = Some(Addr::unchecked("pump_contract"));
+    CONFIG.save(deps.as_mut().storage, &config).unwrap();
+    let hook_msg = |ica_address: &str| {
+        ExecuteMsg::IcaRecoveredHook(IcaRecoveredHookMsg {
+            ica_id: "drop_PUMP".to_string(),
+            ica_address: ica_address.to_string(),
+            port_id: "port".to_string(),
+            channel_id: "channel-1".to_string(),
+        })
+    };
+    let err = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("somebody", &[]),
+        hook_msg("ica_address"),
+    )
+    .unwrap_err();
+    assert_eq!(err, ContractError::Unauthorized {});
+    // the pump ICA address alone does not authorize the sender
+    let err = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("somebody", &[]),
+        hook_msg("pump_address"),
+    )
+    .unwrap_err();
+    assert_eq!(err, ContractError::Unauthorized {});
+    let res = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("pump_contract", &[]),
+        hook_msg("pump_address"),
+    )
+    .unwrap();
+    assert_eq!(
+        res,
+        Response::new().add_event(
+            Event::new("crates.io:drop-staking__drop-core-execute-ica_recovered_hook")
+                .add_attributes(vec![
+                    ("action", "ica_recovered_hook"),
+                    ("sender", "pump_contract"),
+                    ("ica_id", "drop_PUMP"),
+                    ("ica_address", "pump_address"),
+                    ("channel_id", "channel-1"),
+                ])
+        )
+    );
+}
+
+#[test]
+fn test_tick_peripheral_redelegate() {
+    let mut deps = mock_dependencies(&[]);
+    deps.querier
+        .add_wasm_query_response("puppeteer_contract", |_| {
+            to_json_binary(&BalancesResponse {
+                balances: Balances { coins: vec![] },
+                remote_height: 10u64,
+                local_height: 10u64,
+                timestamp: Timestamp::from_seconds(90001),
+            })
+            .unwrap()
+        });
+    deps.querier
+        .add_wasm_query_response("puppeteer_contract", |_| {
+            to_json_binary(&DelegationsResponse {
+                delegations: Delegations {
+                    delegations: vec![],
+                },
+                remote_height: 10u64,
+                local_height: 10u64,
+                timestamp: Timestamp::from_seconds(90001),
+            })
+            .unwrap()
+        });
+    CONFIG
+        .save(
+            deps.as_mut().storage,
+            &get_default_config(1000, 3, 100, 100, 600, Uint128::one()),
+        )
+        .unwrap();
+    FSM.set_initial_state(deps.as_mut().storage, ContractState::Idle)
+        .unwrap();
+    FSM.go_to(deps.as_mut().storage, ContractState::Rebalancing)
+        .unwrap();
+    LAST_ICA_CHANGE_HEIGHT
+        .save(deps.as_mut().storage, &9u64)
+        .unwrap();
+    LAST_PUPPETEER_RESPONSE
+        .save(
+            deps.as_mut().storage,
+            &drop_puppeteer_base::msg::ResponseHookMsg::Success(
+                drop_puppeteer_base::msg::ResponseHookSuccessMsg {
+                    request_id: 0u64,
+                    request: null_request_packet(),
+                    local_height: 9u64,
+                    remote_height: 9u64,
+                    transaction: drop_puppeteer_base::msg::Transaction::Redelegate {
+                        interchain_account_id: "ica".to_string(),
+                        validator_from: "valoper_from".to_string(),
+                        validator_to: "valoper_to".to_string(),
+                        denom: "remote_denom".to_string(),
+                        amount: 100u128,
+                    },
+                    answers: vec![],
+                },
+            ),
+        )
+        .unwrap();
+    let res = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("admin", &[]),
+        ExecuteMsg::Tick {},
+    )
+    .unwrap();
+    assert_eq!(
+        res,
+        Response::new().add_event(
+            Event::new("crates.io:drop-staking__drop-core-execute-tick_peripheral").add_attributes(
+                vec![
+                    ("action", "tick_peripheral"),
+                    ("knot", "056"),
+                    ("knot", "000"),
+                ]
+            )
+        )
+    );
+    assert_eq!(
+        FSM.get_current_state(deps.as_ref().storage).unwrap(),
+        ContractState::Idle
+    );
+}
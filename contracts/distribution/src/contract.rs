@@ -33,9 +33,22 @@ pub fn query(_deps: Deps, _env: Env, msg: QueryMsg) -> ContractResult<Binary> {
             withdraw,
             delegations,
         } => Ok(to_json_binary(&calc_withdraw(withdraw, delegations)?)?),
+        QueryMsg::CalcIdeal { delegations } => Ok(to_json_binary(&calc_ideal(delegations)?)?),
     }
 }
 
+/// Calculates the ideal stake of every delegation for the current total stake.
+pub fn calc_ideal(delegations: Delegations) -> ContractResult<Vec<(String, Uint128)>> {
+    let ideal_stakes = calc_ideal_stakes(delegations.total, &delegations)?;
+
+    Ok(delegations
+        .delegations
+        .into_iter()
+        .zip(ideal_stakes)
+        .map(|(d, ideal_stake)| (d.valoper_address, ideal_stake))
+        .collect())
+}
+
 /// Calculates the ideal withdrawal of stake among the given withdraw amount.
 pub fn calc_withdraw(
     withdraw: Uint128,
@@ -58,6 +71,7 @@ pub fn calc_withdraw(
 }
 
 /// Calculates the ideal distribution of stake among the given delegations.
+/// The part of the deposit exceeding the caps of every validator is left undelegated.
 pub fn calc_deposit(
     deposit: Uint128,
     delegations: Delegations,
@@ -75,7 +89,7 @@ pub fn calc_deposit(
 }
 
 pub fn calc_deposit_distribution<C, D>(
-    mut total_stake: Uint128,
+    total_stake: Uint128,
     mut deposit: Uint128,
     delegations: Delegations,
     check_stake: C,
@@ -85,19 +99,10 @@ where
     C: Fn(Uint128, Uint128) -> bool,
     D: Fn(Uint128, Uint128) -> Uint128,
 {
-    let stake_per_weight = Decimal::from_ratio(total_stake, delegations.total_weight);
+    let ideal_stakes = calc_ideal_stakes(total_stake, &delegations)?;
 
     let mut deposit_changes: Vec<(String, Uint128)> = Vec::new();
-    for d in delegations.delegations {
-        let weight = Decimal::from_atomics(d.weight, 0)?;
-        let mut ideal_stake = stake_per_weight.checked_mul(weight)?.to_uint_ceil(); // ceil used to consume all available stake
-
-        if total_stake < ideal_stake {
-            ideal_stake = total_stake;
-        }
-
-        total_stake -= ideal_stake;
-
+    for (d, ideal_stake) in delegations.delegations.into_iter().zip(ideal_stakes) {
         if check_stake(ideal_stake, d.stake) || deposit.is_zero() {
             continue;
         }
@@ -116,6 +121,121 @@ where
     Ok(deposit_changes)
 }
 
+/// Calculates the ideal stake of every delegation for the given total stake.
+/// Stake is allocated by weight, validators exceeding their cap are fixed at the cap
+/// and the excess is allocated among the others. If every validator is capped,
+/// the excess is left out, so deposits may be only partially distributed.
+pub fn calc_ideal_stakes(
+    total_stake: Uint128,
+    delegations: &Delegations,
+) -> ContractResult<Vec<Uint128>> {
+    let share_cap = delegations
+        .max_stake_share
+        .map(|max_stake_share| total_stake * max_stake_share);
+    let caps: Vec<Option<Uint128>> = delegations
+        .delegations
+        .iter()
+        .map(|d| match (d.cap, share_cap) {
+            (Some(cap), Some(share_cap)) => Some(cap.min(share_cap)),
+            (cap, share_cap) => cap.or(share_cap),
+        })
+        .collect();
+    let weights: Vec<u64> = delegations.delegations.iter().map(|d| d.weight).collect();
+
+    let mut capped = vec![false; caps.len()];
+    loop {
+        let capped_stake: Uint128 = caps
+            .iter()
+            .zip(capped.iter())
+            .filter(|(_, is_capped)| **is_capped)
+            .map(|(cap, _)| cap.unwrap_or_default())
+            .sum();
+        let free_weights: Vec<u64> = weights
+            .iter()
+            .zip(capped.iter())
+            .map(|(weight, is_capped)| if *is_capped { 0 } else { *weight })
+            .collect();
+        let total_weight = if capped.contains(&true) {
+            free_weights.iter().sum()
+        } else {
+            delegations.total_weight
+        };
+        if total_weight == 0 {
+            break;
+        }
+
+        let stakes = distribute_by_weight(
+            total_stake.saturating_sub(capped_stake),
+            &free_weights,
+            total_weight,
+        )?;
+
+        let mut changed = false;
+        for (i, stake) in stakes.iter().enumerate() {
+            if let Some(cap) = caps[i] {
+                if !capped[i] && *stake > cap {
+                    capped[i] = true;
+                    changed = true;
+                }
+            }
+        }
+
+        if !changed {
+            return Ok(stakes
+                .into_iter()
+                .zip(caps.iter().zip(capped.iter()))
+                .map(|(stake, (cap, is_capped))| {
+                    if *is_capped {
+                        cap.unwrap_or_default()
+                    } else {
+                        stake
+                    }
+                })
+                .collect());
+        }
+    }
+
+    Ok(caps
+        .into_iter()
+        .zip(capped)
+        .map(|(cap, is_capped)| {
+            if is_capped {
+                cap.unwrap_or_default()
+            } else {
+                Uint128::zero()
+            }
+        })
+        .collect())
+}
+
+fn distribute_by_weight(
+    mut amount: Uint128,
+    weights: &[u64],
+    total_weight: u64,
+) -> ContractResult<Vec<Uint128>> {
+    if total_weight == 0 {
+        return Ok(vec![Uint128::zero(); weights.len()]);
+    }
+
+    let amount_per_weight = Decimal::from_ratio(amount, total_weight);
+
+    weights
+        .iter()
+        .map(|weight| {
+            let weight = Decimal::from_atomics(*weight, 0)?;
+            let mut share = amount_per_weight.checked_mul(weight)?.to_uint_ceil(); // ceil used to consume all available stake
+
+            if amount < share {
+                share = amount;
+            }
+
+            amount -= share;
+
+            Ok(share)
+        })
+        .collect()
+}
+
 #[cfg_attr(not(feature = "library"), cosmwasm_std::entry_point)]
 pub fn migrate(
     deps: DepsMut<NeutronQuery>,
@@ -146,10 +266,12 @@ mod tests {
         let delegations = Delegations {
             total: Uint128::zero(),
             total_weight: 10,
+            max_stake_share: None,
             delegations: vec![Delegation {
                 valoper_address: "valoper1".to_string(),
                 stake: Uint128::zero(),
                 weight: 10,
+                cap: None,
             }],
         };
 
@@ -168,10 +290,12 @@ mod tests {
         let delegations = Delegations {
             total: Uint128::from(100u128),
             total_weight: 10,
+            max_stake_share: None,
             delegations: vec![Delegation {
                 valoper_address: "valoper1".to_string(),
                 stake: Uint128::from(100u128),
                 weight: 10,
+                cap: None,
             }],
         };
 
@@ -190,21 +314,25 @@ mod tests {
         let delegations = Delegations {
             total: Uint128::zero(),
             total_weight: 70,
+            max_stake_share: None,
             delegations: vec![
                 Delegation {
                     valoper_address: "valoper1".to_string(),
                     stake: Uint128::zero(),
                     weight: 10,
+                    cap: None,
                 },
                 Delegation {
                     valoper_address: "valoper2".to_string(),
                     stake: Uint128::zero(),
                     weight: 20,
+                    cap: None,
                 },
                 Delegation {
                     valoper_address: "valoper3".to_string(),
                     stake: Uint128::zero(),
                     weight: 40,
+                    cap: None,
                 },
             ],
         };
@@ -228,21 +356,25 @@ mod tests {
         let delegations = Delegations {
             total: Uint128::from(100u128),
             total_weight: 70,
+            max_stake_share: None,
             delegations: vec![
                 Delegation {
                     valoper_address: "valoper1".to_string(),
                     stake: Uint128::from(15u128),
                     weight: 10,
+                    cap: None,
                 },
                 Delegation {
                     valoper_address: "valoper2".to_string(),
                     stake: Uint128::from(29u128),
                     weight: 20,
+                    cap: None,
                 },
                 Delegation {
                     valoper_address: "valoper3".to_string(),
                     stake: Uint128::from(56u128),
                     weight: 40,
+                    cap: None,
                 },
             ],
         };
@@ -266,21 +398,25 @@ mod tests {
         let delegations = Delegations {
             total: Uint128::from(141u128),
             total_weight: 70,
+            max_stake_share: None,
             delegations: vec![
                 Delegation {
                     valoper_address: "valoper1".to_string(),
                     stake: Uint128::from(15u128),
                     weight: 10,
+                    cap: None,
                 },
                 Delegation {
                     valoper_address: "valoper2".to_string(),
                     stake: Uint128::from(70u128),
                     weight: 20,
+                    cap: None,
                 },
                 Delegation {
                     valoper_address: "valoper3".to_string(),
                     stake: Uint128::from(56u128),
                     weight: 40,
+                    cap: None,
                 },
             ],
         };
@@ -303,21 +439,25 @@ mod tests {
         let delegations = Delegations {
             total: Uint128::from(200u128),
             total_weight: 70,
+            max_stake_share: None,
             delegations: vec![
                 Delegation {
                     valoper_address: "valoper1".to_string(),
                     stake: Uint128::from(110u128),
                     weight: 10,
+                    cap: None,
                 },
                 Delegation {
                     valoper_address: "valoper2".to_string(),
                     stake: Uint128::zero(),
                     weight: 40,
+                    cap: None,
                 },
                 Delegation {
                     valoper_address: "valoper3".to_string(),
                     stake: Uint128::from(90u128),
                     weight: 20,
+                    cap: None,
                 },
             ],
         };
@@ -337,21 +477,25 @@ mod tests {
         let delegations = Delegations {
             total: Uint128::from(350u128),
             total_weight: 90,
+            max_stake_share: None,
             delegations: vec![
                 Delegation {
                     valoper_address: "valoper1".to_string(),
                     stake: Uint128::from(150u128),
                     weight: 10,
+                    cap: None,
                 },
                 Delegation {
                     valoper_address: "valoper2".to_string(),
                     stake: Uint128::from(200u128),
                     weight: 40,
+                    cap: None,
                 },
                 Delegation {
                     valoper_address: "valoper3".to_string(),
                     stake: Uint128::zero(),
                     weight: 40,
+                    cap: None,
                 },
             ],
         };
@@ -371,10 +515,12 @@ mod tests {
         let delegations = Delegations {
             total: Uint128::zero(),
             total_weight: 10,
+            max_stake_share: None,
             delegations: vec![Delegation {
                 valoper_address: "valoper1".to_string(),
                 stake: Uint128::zero(),
                 weight: 10,
+                cap: None,
             }],
         };
 
@@ -390,10 +536,12 @@ mod tests {
         let delegations = Delegations {
             total: Uint128::from(50u128),
             total_weight: 10,
+            max_stake_share: None,
             delegations: vec![Delegation {
                 valoper_address: "valoper1".to_string(),
                 stake: Uint128::from(50u128),
                 weight: 10,
+                cap: None,
             }],
         };
 
@@ -409,10 +557,12 @@ mod tests {
         let delegations = Delegations {
             total: Uint128::from(100u128),
             total_weight: 10,
+            max_stake_share: None,
             delegations: vec![Delegation {
                 valoper_address: "valoper1".to_string(),
                 stake: Uint128::from(100u128),
                 weight: 10,
+                cap: None,
             }],
         };
 
@@ -431,21 +581,25 @@ mod tests {
         let delegations = Delegations {
             total: Uint128::from(310u128),
             total_weight: 70,
+            max_stake_share: None,
             delegations: vec![
                 Delegation {
                     valoper_address: "valoper1".to_string(),
                     stake: Uint128::from(70u128),
                     weight: 10,
+                    cap: None,
                 },
                 Delegation {
                     valoper_address: "valoper2".to_string(),
                     stake: Uint128::from(90u128),
                     weight: 20,
+                    cap: None,
                 },
                 Delegation {
                     valoper_address: "valoper3".to_string(),
                     stake: Uint128::from(150u128),
                     weight: 40,
+                    cap: None,
                 },
             ],
         };
@@ -469,21 +623,25 @@ mod tests {
         let delegations = Delegations {
             total: Uint128::from(750u128),
             total_weight: 70,
+            max_stake_share: None,
             delegations: vec![
                 Delegation {
                     valoper_address: "valoper1".to_string(),
                     stake: Uint128::from(100u128),
                     weight: 10,
+                    cap: None,
                 },
                 Delegation {
                     valoper_address: "valoper2".to_string(),
                     stake: Uint128::from(250u128),
                     weight: 20,
+                    cap: None,
                 },
                 Delegation {
                     valoper_address: "valoper3".to_string(),
                     stake: Uint128::from(400u128),
                     weight: 40,
+                    cap: None,
                 },
             ],
         };
@@ -503,16 +661,19 @@ mod tests {
         let delegations = Delegations {
             total: Uint128::from(1000u128),
             total_weight: 2,
+            max_stake_share: None,
             delegations: vec![
                 Delegation {
                     valoper_address: "valoper1".to_string(),
                     stake: Uint128::from(500u128),
                     weight: 1,
+                    cap: None,
                 },
                 Delegation {
                     valoper_address: "valoper2".to_string(),
                     stake: Uint128::from(500u128),
                     weight: 1,
+                    cap: None,
                 },
             ],
         };
@@ -529,16 +690,19 @@ mod tests {
         let delegations = Delegations {
             total: Uint128::from(1000u128),
             total_weight: 2,
+            max_stake_share: None,
             delegations: vec![
                 Delegation {
                     valoper_address: "valoper1".to_string(),
                     stake: Uint128::from(500u128),
                     weight: 1,
+                    cap: None,
                 },
                 Delegation {
                     valoper_address: "valoper2".to_string(),
                     stake: Uint128::from(500u128),
                     weight: 1,
+                    cap: None,
                 },
             ],
         };
@@ -553,4 +717,113 @@ mod tests {
             ]
         );
     }
+
+    #[test]
+    fn calc_ideal_deposit_with_cap() {
+        let stake = Uint128::from(100u128);
+
+        let delegations = Delegations {
+            total: Uint128::zero(),
+            total_weight: 70,
+            max_stake_share: None,
+            delegations: vec![
+                Delegation {
+                    valoper_address: "valoper1".to_string(),
+                    stake: Uint128::zero(),
+                    weight: 10,
+                    cap: None,
+                },
+                Delegation {
+                    valoper_address: "valoper2".to_string(),
+                    stake: Uint128::zero(),
+                    weight: 20,
+                    cap: None,
+                },
+                Delegation {
+                    valoper_address: "valoper3".to_string(),
+                    stake: Uint128::zero(),
+                    weight: 40,
+                    cap: Some(Uint128::from(40u128)),
+                },
+            ],
+        };
+
+        let distribution = calc_deposit(stake, delegations).unwrap();
+
+        assert_eq!(
+            distribution,
+            vec![
+                ("valoper1".to_string(), Uint128::from(20u128)),
+                ("valoper2".to_string(), Uint128::from(40u128)),
+                ("valoper3".to_string(), Uint128::from(40u128))
+            ]
+        );
+    }
+
+    #[test]
+    fn calc_ideal_deposit_all_capped() {
+        let stake = Uint128::from(100u128);
+
+        let delegations = Delegations {
+            total: Uint128::zero(),
+            total_weight: 2,
+            max_stake_share: None,
+            delegations: vec![
+                Delegation {
+                    valoper_address: "valoper1".to_string(),
+                    stake: Uint128::zero(),
+                    weight: 1,
+                    cap: Some(Uint128::from(30u128)),
+                },
+                Delegation {
+                    valoper_address: "valoper2".to_string(),
+                    stake: Uint128::zero(),
+                    weight: 1,
+                    cap: Some(Uint128::from(30u128)),
+                },
+            ],
+        };
+
+        let distribution = calc_deposit(stake, delegations).unwrap();
+
+        assert_eq!(
+            distribution,
+            vec![
+                ("valoper1".to_string(), Uint128::from(30u128)),
+                ("valoper2".to_string(), Uint128::from(30u128))
+            ]
+        );
+    }
+
+    #[test]
+    fn calc_ideal_withdraw_with_max_stake_share() {
+        let withdraw = Uint128::from(100u128);
+
+        let delegations = Delegations {
+            total: Uint128::from(400u128),
+            total_weight: 4,
+            max_stake_share: Some(Decimal::percent(50)),
+            delegations: vec![
+                Delegation {
+                    valoper_address: "valoper1".to_string(),
+                    stake: Uint128::from(100u128),
+                    weight: 1,
+                    cap: None,
+                },
+                Delegation {
+                    valoper_address: "valoper2".to_string(),
+                    stake: Uint128::from(300u128),
+                    weight: 3,
+                    cap: None,
+                },
+            ],
+        };
+
+        let distribution = calc_withdraw(withdraw, delegations).unwrap();
+
+        assert_eq!(
+            distribution,
+            vec![("valoper2".to_string(), Uint128::from(100u128))]
+        );
+    }
 }
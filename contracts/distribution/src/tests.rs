@@ -26,10 +26,12 @@ fn query_deposit_calculation() {
             delegations: Delegations {
                 total: Uint128::zero(),
                 total_weight: 10,
+                max_stake_share: None,
                 delegations: vec![Delegation {
                     valoper_address: "valoper1".to_string(),
                     stake: Uint128::zero(),
                     weight: 10u64,
+                    cap: None,
                 }],
             },
         },
@@ -54,10 +56,12 @@ fn query_withdraw_calculation() {
             delegations: Delegations {
                 total: Uint128::from(100u128),
                 total_weight: 10,
+                max_stake_share: None,
                 delegations: vec![Delegation {
                     valoper_address: "valoper1".to_string(),
                     stake: Uint128::from(100u128),
                     weight: 10u64,
+                    cap: None,
                 }],
             },
         },
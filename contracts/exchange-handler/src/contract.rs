@@ -0,0 +1,213 @@
+use cosmwasm_std::{
+    attr, ensure, entry_point, to_json_binary, Attribute, CosmosMsg, Decimal, Deps, Order,
+};
+use cosmwasm_std::{Binary, DepsMut, Env, MessageInfo, Response, StdResult};
+use cw_ownable::{get_ownership, update_ownership};
+use drop_helpers::answer::{attr_coin, response};
+use drop_staking_base::error::exchange_handler::{ContractError, ContractResult};
+use drop_staking_base::msg::exchange_handler::{ExecuteMsg, InstantiateMsg, MigrateMsg, QueryMsg};
+use drop_staking_base::state::exchange_handler::{
+    Config, ConfigOptional, Dex, DexRoute, CONFIG, DEX_ROUTES,
+};
+
+use crate::dex::exchange_msg;
+
+const CONTRACT_NAME: &str = concat!("crates.io:drop-staking__", env!("CARGO_PKG_NAME"));
+const CONTRACT_VERSION: &str = env!("CARGO_PKG_VERSION");
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn instantiate(
+    deps: DepsMut,
+    _env: Env,
+    _info: MessageInfo,
+    msg: InstantiateMsg,
+) -> ContractResult<Response> {
+    cw2::set_contract_version(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
+    cw_ownable::initialize_owner(deps.storage, deps.api, Some(msg.owner.as_ref()))?;
+
+    let core_contract = deps.api.addr_validate(&msg.core_contract)?;
+    let price_provider_contract = deps.api.addr_validate(&msg.price_provider_contract)?;
+    validate_max_spread(msg.max_spread)?;
+
+    let config = Config {
+        core_contract: core_contract.to_string(),
+        price_provider_contract: price_provider_contract.to_string(),
+        to_denom: msg.to_denom.clone(),
+        max_spread: msg.max_spread,
+    };
+    CONFIG.save(deps.storage, &config)?;
+
+    Ok(response(
+        "instantiate",
+        CONTRACT_NAME,
+        [
+            attr("core_contract", msg.core_contract),
+            attr("price_provider_contract", msg.price_provider_contract),
+            attr("to_denom", msg.to_denom),
+            attr("max_spread", msg.max_spread.to_string()),
+        ],
+    ))
+}
+
+fn validate_max_spread(max_spread: Decimal) -> ContractResult<()> {
+    ensure!(!max_spread.is_zero(), ContractError::ZeroMaxSpread);
+    ensure!(
+        max_spread <= Decimal::percent(10),
+        ContractError::MaxSpreadTooBig
+    );
+    Ok(())
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn query(deps: Deps, _env: Env, msg: QueryMsg) -> StdResult<Binary> {
+    match msg {
+        QueryMsg::Ownership {} => to_json_binary(&get_ownership(deps.storage)?),
+        QueryMsg::Config {} => to_json_binary(&CONFIG.load(deps.storage)?),
+        QueryMsg::Routes {} => {
+            let routes: Vec<DexRoute> = DEX_ROUTES
+                .range(deps.storage, None, None, Order::Ascending)
+                .map(|item| item.map(|(_key, value)| value))
+                .collect::<StdResult<_>>()?;
+            to_json_binary(&routes)
+        }
+    }
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn execute(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    msg: ExecuteMsg,
+) -> ContractResult<Response> {
+    match msg {
+        ExecuteMsg::UpdateOwnership(action) => {
+            update_ownership(deps.into_empty(), &env.block, &info.sender, action)?;
+            Ok(Response::new())
+        }
+        ExecuteMsg::UpdateConfig { new_config } => exec_update_config(deps, info, new_config),
+        ExecuteMsg::AddRoute { route } => exec_add_route(deps, info, route),
+        ExecuteMsg::RemoveRoute { denom } => exec_remove_route(deps, info, denom),
+        ExecuteMsg::Exchange {} => exec_exchange(deps, env),
+    }
+}
+
+fn exec_update_config(
+    deps: DepsMut,
+    info: MessageInfo,
+    new_config: ConfigOptional,
+) -> ContractResult<Response> {
+    cw_ownable::assert_owner(deps.storage, &info.sender)?;
+
+    let mut config = CONFIG.load(deps.storage)?;
+    let mut attrs: Vec<Attribute> = Vec::new();
+
+    if let Some(core_contract) = new_config.core_contract {
+        let core_contract = deps.api.addr_validate(&core_contract)?;
+        config.core_contract = core_contract.to_string();
+        attrs.push(attr("core_contract", core_contract))
+    }
+
+    if let Some(price_provider_contract) = new_config.price_provider_contract {
+        let price_provider_contract = deps.api.addr_validate(&price_provider_contract)?;
+        config.price_provider_contract = price_provider_contract.to_string();
+        attrs.push(attr("price_provider_contract", price_provider_contract))
+    }
+
+    if let Some(to_denom) = new_config.to_denom {
+        config.to_denom = to_denom.clone();
+        attrs.push(attr("to_denom", to_denom))
+    }
+
+    if let Some(max_spread) = new_config.max_spread {
+        validate_max_spread(max_spread)?;
+        config.max_spread = max_spread;
+        attrs.push(attr("max_spread", max_spread.to_string()))
+    }
+
+    CONFIG.save(deps.storage, &config)?;
+
+    Ok(response("config_update", CONTRACT_NAME, attrs))
+}
+
+fn exec_add_route(deps: DepsMut, info: MessageInfo, route: DexRoute) -> ContractResult<Response> {
+    cw_ownable::assert_owner(deps.storage, &info.sender)?;
+
+    ensure!(
+        !DEX_ROUTES.has(deps.storage, &route.denom),
+        ContractError::RouteAlreadyExists {
+            denom: route.denom.clone()
+        }
+    );
+    let (dex, contract) = match &route.dex {
+        Dex::AstroportPair { pair_contract } => ("astroport_pair", pair_contract),
+        Dex::AstroportRouter {
+            router_contract, ..
+        } => ("astroport_router", router_contract),
+        Dex::Router { router_contract } => ("router", router_contract),
+    };
+    deps.api.addr_validate(contract)?;
+    DEX_ROUTES.save(deps.storage, &route.denom, &route)?;
+
+    Ok(response(
+        "add_route",
+        CONTRACT_NAME,
+        [
+            attr("denom", route.denom),
+            attr("dex", dex),
+            attr("contract", contract),
+            attr("min_rewards", route.min_rewards),
+        ],
+    ))
+}
+
+fn exec_remove_route(deps: DepsMut, info: MessageInfo, denom: String) -> ContractResult<Response> {
+    cw_ownable::assert_owner(deps.storage, &info.sender)?;
+
+    DEX_ROUTES.remove(deps.storage, &denom);
+
+    Ok(response(
+        "remove_route",
+        CONTRACT_NAME,
+        [attr("denom", denom)],
+    ))
+}
+
+fn exec_exchange(deps: DepsMut, env: Env) -> ContractResult<Response> {
+    let config = CONFIG.load(deps.storage)?;
+    let routes: Vec<DexRoute> = DEX_ROUTES
+        .range(deps.storage, None, None, Order::Ascending)
+        .map(|item| item.map(|(_key, value)| value))
+        .collect::<StdResult<_>>()?;
+
+    let mut msgs: Vec<CosmosMsg> = Vec::new();
+    let mut attrs: Vec<Attribute> = Vec::new();
+    for route in routes {
+        let balance = deps
+            .querier
+            .query_balance(&env.contract.address, &route.denom)?;
+        if balance.amount.is_zero() || balance.amount < route.min_rewards {
+            continue;
+        }
+        let (msg, min_output) = exchange_msg(deps.as_ref(), &config, &route.dex, balance.clone())?;
+        msgs.push(msg);
+        attrs.push(attr_coin("swap_amount", balance.amount, balance.denom));
+        attrs.push(attr_coin("min_output", min_output, &config.to_denom));
+    }
+    ensure!(!msgs.is_empty(), ContractError::NothingToExchange);
+
+    Ok(response("exchange", CONTRACT_NAME, attrs).add_messages(msgs))
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn migrate(deps: DepsMut, _env: Env, _msg: MigrateMsg) -> ContractResult<Response> {
+    let version: semver::Version = CONTRACT_VERSION.parse()?;
+    let storage_version: semver::Version =
+        cw2::get_contract_version(deps.storage)?.version.parse()?;
+
+    if storage_version < version {
+        cw2::set_contract_version(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
+    }
+
+    Ok(Response::new())
+}
@@ -0,0 +1,272 @@
+use astroport::asset::{Asset, AssetInfo, PairInfo};
+use astroport::pair::{ExecuteMsg as PairExecuteMsg, QueryMsg as PairQueryMsg};
+use astroport::router::{ExecuteMsg as RouterExecuteMsg, SwapOperation};
+use cosmwasm_std::{ensure, to_json_binary, Coin, CosmosMsg, Decimal, Deps, Uint128, WasmMsg};
+use drop_staking_base::error::exchange_handler::{ContractError, ContractResult};
+use drop_staking_base::msg::dex_router::{
+    ExecuteMsg as DexRouterExecuteMsg, GetRouteResponse, QueryMsg as DexRouterQueryMsg,
+    SwapAmountInRoute,
+};
+use drop_staking_base::state::exchange_handler::{Config, Dex};
+
+/// Common interface of the DEXes rewards can be exchanged on
+pub trait DexAdapter {
+    type Route;
+
+    /// Discovers the route swapping `offer_denom` into `ask_denom`
+    fn route(&self, deps: Deps, offer_denom: &str, ask_denom: &str) -> ContractResult<Self::Route>;
+
+    /// Builds the message swapping `offer` and sending at least `min_output` to `receiver`
+    fn swap_msg(
+        &self,
+        route: Self::Route,
+        offer: Coin,
+        ask_denom: &str,
+        min_output: Uint128,
+        receiver: &str,
+    ) -> ContractResult<CosmosMsg>;
+
+    /// Returns the swap message of `offer` into `config.to_denom` along with its minimal output
+    fn exchange_msg(
+        &self,
+        deps: Deps,
+        config: &Config,
+        offer: Coin,
+    ) -> ContractResult<(CosmosMsg, Uint128)> {
+        let route = self.route(deps, &offer.denom, &config.to_denom)?;
+        let min_output = min_output(deps, config, &offer)?;
+        let msg = self.swap_msg(
+            route,
+            offer,
+            &config.to_denom,
+            min_output,
+            &config.core_contract,
+        )?;
+        Ok((msg, min_output))
+    }
+}
+
+/// Computes the minimal output from the price provider quote of `offer.denom` in `to_denom`.
+/// The quote is the amount of `to_denom` per unit of `offer.denom`
+pub fn min_output(deps: Deps, config: &Config, offer: &Coin) -> ContractResult<Uint128> {
+    let price: Decimal = deps
+        .querier
+        .query_wasm_smart(
+            &config.price_provider_contract,
+            &drop_staking_base::msg::price_provider::QueryMsg::Price {
+                denom: offer.denom.clone(),
+            },
+        )
+        .map_err(|e| ContractError::AssetPriceQueryFailed {
+            details: e.to_string(),
+        })?;
+    let min_output = offer
+        .amount
+        .checked_mul_floor(price * (Decimal::one() - config.max_spread))?;
+    ensure!(
+        !min_output.is_zero(),
+        ContractError::ZeroMinOutput {
+            denom: offer.denom.clone()
+        }
+    );
+    Ok(min_output)
+}
+
+pub fn exchange_msg(
+    deps: Deps,
+    config: &Config,
+    dex: &Dex,
+    offer: Coin,
+) -> ContractResult<(CosmosMsg, Uint128)> {
+    match dex {
+        Dex::AstroportPair { pair_contract } => {
+            AstroportPair { pair_contract }.exchange_msg(deps, config, offer)
+        }
+        Dex::AstroportRouter {
+            router_contract,
+            operations,
+        } => AstroportRouter {
+            router_contract,
+            operations,
+            max_spread: config.max_spread,
+        }
+        .exchange_msg(deps, config, offer),
+        Dex::Router { router_contract } => {
+            Router { router_contract }.exchange_msg(deps, config, offer)
+        }
+    }
+}
+
+pub struct AstroportPair<'a> {
+    pub pair_contract: &'a str,
+}
+
+impl DexAdapter for AstroportPair<'_> {
+    type Route = AssetInfo;
+
+    fn route(&self, deps: Deps, offer_denom: &str, ask_denom: &str) -> ContractResult<Self::Route> {
+        let pair_info: PairInfo = deps
+            .querier
+            .query_wasm_smart(self.pair_contract, &PairQueryMsg::Pair {})?;
+        let has_denom = |denom: &str| {
+            pair_info.asset_infos.contains(&AssetInfo::NativeToken {
+                denom: denom.to_string(),
+            })
+        };
+        ensure!(
+            has_denom(offer_denom) && has_denom(ask_denom),
+            ContractError::RouteNotFound {
+                from: offer_denom.to_string(),
+                to: ask_denom.to_string(),
+            }
+        );
+        Ok(AssetInfo::NativeToken {
+            denom: ask_denom.to_string(),
+        })
+    }
+
+    fn swap_msg(
+        &self,
+        route: Self::Route,
+        offer: Coin,
+        _ask_denom: &str,
+        min_output: Uint128,
+        receiver: &str,
+    ) -> ContractResult<CosmosMsg> {
+        // pair has no minimal receive, so the belief price with zero spread enforces it
+        Ok(CosmosMsg::Wasm(WasmMsg::Execute {
+            contract_addr: self.pair_contract.to_string(),
+            msg: to_json_binary(&PairExecuteMsg::Swap {
+                offer_asset: Asset {
+                    info: AssetInfo::NativeToken {
+                        denom: offer.denom.clone(),
+                    },
+                    amount: offer.amount,
+                },
+                ask_asset_info: Some(route),
+                belief_price: Some(Decimal::from_ratio(offer.amount, min_output)),
+                max_spread: Some(Decimal::zero()),
+                to: Some(receiver.to_string()),
+            })?,
+            funds: vec![offer],
+        }))
+    }
+}
+
+pub struct AstroportRouter<'a> {
+    pub router_contract: &'a str,
+    pub operations: &'a [SwapOperation],
+    pub max_spread: Decimal,
+}
+
+fn operation_denoms(operation: &SwapOperation) -> Option<(&str, &str)> {
+    match operation {
+        SwapOperation::NativeSwap {
+            offer_denom,
+            ask_denom,
+        } => Some((offer_denom.as_str(), ask_denom.as_str())),
+        SwapOperation::AstroSwap {
+            offer_asset_info: AssetInfo::NativeToken { denom: offer_denom },
+            ask_asset_info: AssetInfo::NativeToken { denom: ask_denom },
+        } => Some((offer_denom.as_str(), ask_denom.as_str())),
+        _ => None,
+    }
+}
+
+impl DexAdapter for AstroportRouter<'_> {
+    type Route = Vec<SwapOperation>;
+
+    fn route(
+        &self,
+        _deps: Deps,
+        offer_denom: &str,
+        ask_denom: &str,
+    ) -> ContractResult<Self::Route> {
+        let mut denom = offer_denom;
+        for operation in self.operations {
+            match operation_denoms(operation) {
+                Some((offer, ask)) if offer == denom => denom = ask,
+                _ => {
+                    return Err(ContractError::RouteNotFound {
+                        from: offer_denom.to_string(),
+                        to: ask_denom.to_string(),
+                    })
+                }
+            }
+        }
+        ensure!(
+            !self.operations.is_empty() && denom == ask_denom,
+            ContractError::RouteNotFound {
+                from: offer_denom.to_string(),
+                to: ask_denom.to_string(),
+            }
+        );
+        Ok(self.operations.to_vec())
+    }
+
+    fn swap_msg(
+        &self,
+        route: Self::Route,
+        offer: Coin,
+        _ask_denom: &str,
+        min_output: Uint128,
+        receiver: &str,
+    ) -> ContractResult<CosmosMsg> {
+        Ok(CosmosMsg::Wasm(WasmMsg::Execute {
+            contract_addr: self.router_contract.to_string(),
+            msg: to_json_binary(&RouterExecuteMsg::ExecuteSwapOperations {
+                operations: route,
+                minimum_receive: Some(min_output),
+                to: Some(receiver.to_string()),
+                max_spread: Some(self.max_spread),
+            })?,
+            funds: vec![offer],
+        }))
+    }
+}
+
+/// Osmosis-style router
+pub struct Router<'a> {
+    pub router_contract: &'a str,
+}
+
+impl DexAdapter for Router<'_> {
+    type Route = Vec<SwapAmountInRoute>;
+
+    fn route(&self, deps: Deps, offer_denom: &str, ask_denom: &str) -> ContractResult<Self::Route> {
+        let response: GetRouteResponse = deps
+            .querier
+            .query_wasm_smart(
+                self.router_contract,
+                &DexRouterQueryMsg::GetRoute {
+                    input_denom: offer_denom.to_string(),
+                    output_denom: ask_denom.to_string(),
+                },
+            )
+            .map_err(|_| ContractError::RouteNotFound {
+                from: offer_denom.to_string(),
+                to: ask_denom.to_string(),
+            })?;
+        Ok(response.pool_route)
+    }
+
+    fn swap_msg(
+        &self,
+        route: Self::Route,
+        offer: Coin,
+        ask_denom: &str,
+        min_output: Uint128,
+        receiver: &str,
+    ) -> ContractResult<CosmosMsg> {
+        Ok(CosmosMsg::Wasm(WasmMsg::Execute {
+            contract_addr: self.router_contract.to_string(),
+            msg: to_json_binary(&DexRouterExecuteMsg::Swap {
+                output_denom: ask_denom.to_string(),
+                min_output_amount: min_output,
+                route: Some(route),
+                to: Some(receiver.to_string()),
+            })?,
+            funds: vec![offer],
+        }))
+    }
+}
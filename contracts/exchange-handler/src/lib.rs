@@ -0,0 +1,5 @@
+pub mod contract;
+pub mod dex;
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,539 @@
+use crate::contract::instantiate;
+
+use astroport::asset::{AssetInfo, PairInfo};
+use astroport::factory::PairType;
+use astroport::pair::{ExecuteMsg as PairExecuteMsg, QueryMsg as PairQueryMsg};
+use astroport::router::SwapOperation;
+use cosmwasm_std::testing::{mock_dependencies, mock_env, mock_info};
+use cosmwasm_std::{
+    attr, coin, coins, to_json_binary, Addr, Attribute, Coin, Decimal, Empty, Event, Response,
+    StdError, Uint128,
+};
+use cw_multi_test::{custom_app, App, Contract, ContractWrapper, Executor};
+use drop_helpers::answer::response;
+use drop_staking_base::msg::dex_router::{
+    ExecuteMsg as DexRouterExecuteMsg, InstantiateMsg as DexRouterInstantiateMsg, SwapAmountInRoute,
+};
+use drop_staking_base::msg::exchange_handler::{ExecuteMsg, InstantiateMsg, QueryMsg};
+use drop_staking_base::msg::price_provider::{
+    ExecuteMsg as PriceProviderExecuteMsg, InstantiateMsg as PriceProviderInstantiateMsg,
+};
+use drop_staking_base::state::exchange_handler::{Config, ConfigOptional, Dex, DexRoute};
+
+const CORE_CONTRACT_ADDR: &str = "core_contract";
+const OWNER_ADDR: &str = "owner";
+const SENDER_ADDR: &str = "sender";
+
+fn price_provider_contract() -> Box<dyn Contract<Empty>> {
+    let contract = ContractWrapper::new(
+        drop_price_provider::contract::execute,
+        drop_price_provider::contract::instantiate,
+        drop_price_provider::contract::query,
+    );
+    Box::new(contract)
+}
+
+fn pair_contract() -> Box<dyn Contract<Empty>> {
+    let contract: ContractWrapper<
+        PairExecuteMsg,
+        Empty,
+        PairQueryMsg,
+        StdError,
+        StdError,
+        StdError,
+    > = ContractWrapper::new(
+        |_, _, info, msg: PairExecuteMsg| match msg {
+            PairExecuteMsg::Swap {
+                offer_asset,
+                ask_asset_info,
+                belief_price,
+                max_spread,
+                to,
+            } => Ok(response(
+                "pair_contract_execute",
+                "pair_mock",
+                [
+                    attr("to", to.unwrap()),
+                    attr("offer_asset", offer_asset.to_string()),
+                    attr("ask_asset_info", ask_asset_info.unwrap().to_string()),
+                    attr("belief_price", belief_price.unwrap().to_string()),
+                    attr("max_spread", max_spread.unwrap().to_string()),
+                    attr(
+                        "funds_received",
+                        format!("{}{}", info.funds[0].amount, info.funds[0].denom),
+                    ),
+                ],
+            )),
+            _ => Err(StdError::generic_err("Wrong execution call")),
+        },
+        |_, _, _, _: Empty| Ok(Response::new()),
+        |_, env, msg: PairQueryMsg| match msg {
+            PairQueryMsg::Pair {} => to_json_binary(&PairInfo {
+                asset_infos: vec![
+                    AssetInfo::NativeToken {
+                        denom: "ueth".to_string(),
+                    },
+                    AssetInfo::NativeToken {
+                        denom: "untrn".to_string(),
+                    },
+                ],
+                contract_addr: env.contract.address,
+                liquidity_token: Addr::unchecked("lp_token"),
+                pair_type: PairType::Xyk {},
+            }),
+            _ => Err(StdError::generic_err("Wrong query")),
+        },
+    );
+    Box::new(contract)
+}
+
+fn dex_router_contract() -> Box<dyn Contract<Empty>> {
+    let contract = ContractWrapper::new(
+        drop_mock_dex_router::contract::execute,
+        drop_mock_dex_router::contract::instantiate,
+        drop_mock_dex_router::contract::query,
+    );
+    Box::new(contract)
+}
+
+fn exchange_handler_contract() -> Box<dyn Contract<Empty>> {
+    let contract = ContractWrapper::new(
+        crate::contract::execute,
+        crate::contract::instantiate,
+        crate::contract::query,
+    );
+    Box::new(contract)
+}
+
+fn mock_app() -> App {
+    custom_app(|r, _a, s| {
+        r.bank
+            .init_balance(
+                s,
+                &Addr::unchecked(SENDER_ADDR),
+                vec![coin(1000000000, "untrn"), coin(1000000000, "ueth")],
+            )
+            .unwrap();
+    })
+}
+
+fn instantiate_handler(app: &mut App, price: Decimal) -> Addr {
+    let price_provider_code_id = app.store_code(price_provider_contract());
+    let price_provider = app
+        .instantiate_contract(
+            price_provider_code_id,
+            Addr::unchecked(OWNER_ADDR),
+            &PriceProviderInstantiateMsg {
+                owner: None,
+                feeders: vec![],
+                max_age: None,
+            },
+            &[],
+            "price provider",
+            None,
+        )
+        .unwrap();
+    app.execute_contract(
+        Addr::unchecked(OWNER_ADDR),
+        price_provider.clone(),
+        &PriceProviderExecuteMsg::SetPrice {
+            denom: "ueth".to_string(),
+            price,
+        },
+        &[],
+    )
+    .unwrap();
+    let handler_code_id = app.store_code(exchange_handler_contract());
+    app.instantiate_contract(
+        handler_code_id,
+        Addr::unchecked(OWNER_ADDR),
+        &InstantiateMsg {
+            owner: OWNER_ADDR.to_string(),
+            core_contract: CORE_CONTRACT_ADDR.to_string(),
+            price_provider_contract: price_provider.to_string(),
+            to_denom: "untrn".to_string(),
+            max_spread: Decimal::percent(1),
+        },
+        &[],
+        "exchange handler",
+        None,
+    )
+    .unwrap()
+}
+
+/// Router with a single 1_000_000ueth / 2_000_000untrn pool
+fn instantiate_dex_router(app: &mut App) -> Addr {
+    let code_id = app.store_code(dex_router_contract());
+    let router = app
+        .instantiate_contract(
+            code_id,
+            Addr::unchecked(OWNER_ADDR),
+            &DexRouterInstantiateMsg {},
+            &[],
+            "dex router",
+            None,
+        )
+        .unwrap();
+    app.execute_contract(
+        Addr::unchecked(SENDER_ADDR),
+        router.clone(),
+        &DexRouterExecuteMsg::CreatePool {},
+        &[coin(1000000, "ueth"), coin(2000000, "untrn")],
+    )
+    .unwrap();
+    app.execute_contract(
+        Addr::unchecked(OWNER_ADDR),
+        router.clone(),
+        &DexRouterExecuteMsg::SetRoute {
+            input_denom: "ueth".to_string(),
+            output_denom: "untrn".to_string(),
+            pool_route: vec![SwapAmountInRoute {
+                pool_id: 1,
+                token_out_denom: "untrn".to_string(),
+            }],
+        },
+        &[],
+    )
+    .unwrap();
+    router
+}
+
+fn add_route(app: &mut App, handler: &Addr, dex: Dex) {
+    app.execute_contract(
+        Addr::unchecked(OWNER_ADDR),
+        handler.clone(),
+        &ExecuteMsg::AddRoute {
+            route: DexRoute {
+                denom: "ueth".to_string(),
+                dex,
+                min_rewards: Uint128::from(10u128),
+            },
+        },
+        &[],
+    )
+    .unwrap();
+}
+
+#[test]
+fn test_initialization() {
+    let mut deps = mock_dependencies();
+    let msg = InstantiateMsg {
+        owner: OWNER_ADDR.to_string(),
+        core_contract: CORE_CONTRACT_ADDR.to_string(),
+        price_provider_contract: "price_provider".to_string(),
+        to_denom: "untrn".to_string(),
+        max_spread: Decimal::percent(1),
+    };
+
+    let res = instantiate(deps.as_mut(), mock_env(), mock_info(OWNER_ADDR, &[]), msg).unwrap();
+
+    assert_eq!(
+        res.events,
+        vec![
+            Event::new("crates.io:drop-staking__drop-exchange-handler-instantiate").add_attributes(
+                vec![
+                    attr("core_contract", CORE_CONTRACT_ADDR),
+                    attr("price_provider_contract", "price_provider"),
+                    attr("to_denom", "untrn"),
+                    attr("max_spread", "0.01"),
+                ]
+            )
+        ]
+    );
+
+    let res = instantiate(
+        deps.as_mut(),
+        mock_env(),
+        mock_info(OWNER_ADDR, &[]),
+        InstantiateMsg {
+            owner: OWNER_ADDR.to_string(),
+            core_contract: CORE_CONTRACT_ADDR.to_string(),
+            price_provider_contract: "price_provider".to_string(),
+            to_denom: "untrn".to_string(),
+            max_spread: Decimal::percent(11),
+        },
+    );
+    assert_eq!(
+        res.unwrap_err().to_string(),
+        "Max spread is too big. Max: 0.1"
+    );
+}
+
+#[test]
+fn test_update_config() {
+    let mut app = mock_app();
+    let handler = instantiate_handler(&mut app, Decimal::one());
+
+    let res = app.execute_contract(
+        Addr::unchecked(SENDER_ADDR),
+        handler.clone(),
+        &ExecuteMsg::UpdateConfig {
+            new_config: ConfigOptional {
+                core_contract: None,
+                price_provider_contract: None,
+                to_denom: Some("uatom".to_string()),
+                max_spread: None,
+            },
+        },
+        &[],
+    );
+    assert_eq!(
+        res.unwrap_err().root_cause().to_string(),
+        "Caller is not the contract's current owner"
+    );
+
+    app.execute_contract(
+        Addr::unchecked(OWNER_ADDR),
+        handler.clone(),
+        &ExecuteMsg::UpdateConfig {
+            new_config: ConfigOptional {
+                core_contract: None,
+                price_provider_contract: None,
+                to_denom: Some("uatom".to_string()),
+                max_spread: Some(Decimal::percent(5)),
+            },
+        },
+        &[],
+    )
+    .unwrap();
+
+    let config: Config = app
+        .wrap()
+        .query_wasm_smart(handler, &QueryMsg::Config {})
+        .unwrap();
+    assert_eq!(config.to_denom, "uatom");
+    assert_eq!(config.max_spread, Decimal::percent(5));
+}
+
+#[test]
+fn test_add_remove_route() {
+    let mut app = mock_app();
+    let handler = instantiate_handler(&mut app, Decimal::one());
+    let dex = Dex::Router {
+        router_contract: "router".to_string(),
+    };
+
+    add_route(&mut app, &handler, dex.clone());
+    let res = app.execute_contract(
+        Addr::unchecked(OWNER_ADDR),
+        handler.clone(),
+        &ExecuteMsg::AddRoute {
+            route: DexRoute {
+                denom: "ueth".to_string(),
+                dex: dex.clone(),
+                min_rewards: Uint128::zero(),
+            },
+        },
+        &[],
+    );
+    assert_eq!(
+        res.unwrap_err().root_cause().to_string(),
+        "Route for ueth already exists"
+    );
+
+    let routes: Vec<DexRoute> = app
+        .wrap()
+        .query_wasm_smart(handler.clone(), &QueryMsg::Routes {})
+        .unwrap();
+    assert_eq!(
+        routes,
+        vec![DexRoute {
+            denom: "ueth".to_string(),
+            dex,
+            min_rewards: Uint128::from(10u128),
+        }]
+    );
+
+    app.execute_contract(
+        Addr::unchecked(OWNER_ADDR),
+        handler.clone(),
+        &ExecuteMsg::RemoveRoute {
+            denom: "ueth".to_string(),
+        },
+        &[],
+    )
+    .unwrap();
+    let routes: Vec<DexRoute> = app
+        .wrap()
+        .query_wasm_smart(handler, &QueryMsg::Routes {})
+        .unwrap();
+    assert_eq!(routes, vec![]);
+}
+
+#[test]
+fn test_exchange_through_router() {
+    let mut app = mock_app();
+    let router = instantiate_dex_router(&mut app);
+    let handler = instantiate_handler(&mut app, Decimal::from_ratio(2u128, 1u128));
+    add_route(
+        &mut app,
+        &handler,
+        Dex::Router {
+            router_contract: router.to_string(),
+        },
+    );
+
+    let res = app.execute_contract(
+        Addr::unchecked(SENDER_ADDR),
+        handler.clone(),
+        &ExecuteMsg::Exchange {},
+        &[],
+    );
+    assert_eq!(
+        res.unwrap_err().root_cause().to_string(),
+        "Nothing to exchange"
+    );
+
+    app.send_tokens(
+        Addr::unchecked(SENDER_ADDR),
+        handler.clone(),
+        &coins(100, "ueth"),
+    )
+    .unwrap();
+    let res = app
+        .execute_contract(
+            Addr::unchecked(SENDER_ADDR),
+            handler.clone(),
+            &ExecuteMsg::Exchange {},
+            &[],
+        )
+        .unwrap();
+
+    assert_eq!(
+        res.events[1].attributes[1..],
+        vec![
+            Attribute::new("swap_amount", "100ueth"),
+            Attribute::new("min_output", "198untrn"),
+        ]
+    );
+    assert_eq!(
+        app.wrap()
+            .query_balance(CORE_CONTRACT_ADDR, "untrn")
+            .unwrap(),
+        Coin::new(199, "untrn")
+    );
+    assert_eq!(
+        app.wrap().query_balance(handler, "ueth").unwrap(),
+        Coin::new(0, "ueth")
+    );
+}
+
+#[test]
+fn test_exchange_through_router_min_output() {
+    let mut app = mock_app();
+    let router = instantiate_dex_router(&mut app);
+    let handler = instantiate_handler(&mut app, Decimal::from_ratio(3u128, 1u128));
+    add_route(
+        &mut app,
+        &handler,
+        Dex::Router {
+            router_contract: router.to_string(),
+        },
+    );
+
+    app.send_tokens(
+        Addr::unchecked(SENDER_ADDR),
+        handler.clone(),
+        &coins(100, "ueth"),
+    )
+    .unwrap();
+    let res = app.execute_contract(
+        Addr::unchecked(SENDER_ADDR),
+        handler,
+        &ExecuteMsg::Exchange {},
+        &[],
+    );
+    assert_eq!(
+        res.unwrap_err().root_cause().to_string(),
+        "Output 199 is less than minimum 297"
+    );
+}
+
+#[test]
+fn test_exchange_through_astroport_pair() {
+    let mut app = mock_app();
+    let pair_code_id = app.store_code(pair_contract());
+    let pair = app
+        .instantiate_contract(
+            pair_code_id,
+            Addr::unchecked(OWNER_ADDR),
+            &Empty {},
+            &[],
+            "astroport pair",
+            None,
+        )
+        .unwrap();
+    let handler = instantiate_handler(&mut app, Decimal::from_ratio(2u128, 1u128));
+    add_route(
+        &mut app,
+        &handler,
+        Dex::AstroportPair {
+            pair_contract: pair.to_string(),
+        },
+    );
+
+    app.send_tokens(
+        Addr::unchecked(SENDER_ADDR),
+        handler.clone(),
+        &coins(100, "ueth"),
+    )
+    .unwrap();
+    let res = app
+        .execute_contract(
+            Addr::unchecked(SENDER_ADDR),
+            handler,
+            &ExecuteMsg::Exchange {},
+            &[],
+        )
+        .unwrap();
+
+    assert_eq!(res.events[3].ty, "wasm-pair_mock-pair_contract_execute");
+    assert_eq!(
+        res.events[3].attributes[1..],
+        vec![
+            Attribute::new("to", CORE_CONTRACT_ADDR),
+            Attribute::new("offer_asset", "100ueth"),
+            Attribute::new("ask_asset_info", "untrn"),
+            Attribute::new(
+                "belief_price",
+                Decimal::from_ratio(100u128, 198u128).to_string()
+            ),
+            Attribute::new("max_spread", "0"),
+            Attribute::new("funds_received", "100ueth"),
+        ]
+    );
+}
+
+#[test]
+fn test_exchange_through_astroport_router_wrong_route() {
+    let mut app = mock_app();
+    let handler = instantiate_handler(&mut app, Decimal::one());
+    add_route(
+        &mut app,
+        &handler,
+        Dex::AstroportRouter {
+            router_contract: "astroport_router".to_string(),
+            operations: vec![SwapOperation::NativeSwap {
+                offer_denom: "ueth".to_string(),
+                ask_denom: "uatom".to_string(),
+            }],
+        },
+    );
+
+    app.send_tokens(
+        Addr::unchecked(SENDER_ADDR),
+        handler.clone(),
+        &coins(100, "ueth"),
+    )
+    .unwrap();
+    let res = app.execute_contract(
+        Addr::unchecked(SENDER_ADDR),
+        handler,
+        &ExecuteMsg::Exchange {},
+        &[],
+    );
+    assert_eq!(
+        res.unwrap_err().root_cause().to_string(),
+        "No swap route from ueth to untrn"
+    );
+}
@@ -291,6 +291,9 @@ pub fn instantiate(
                 validator_set_address: validators_set_contract.to_string(),
                 distribution_address: distribution_contract.to_string(),
                 denom: msg.remote_opts.denom.to_string(),
+                validators_stats_address: None,
+                max_stake_share: None,
+                max_voting_power_share: None,
             })?,
             funds: vec![],
             salt: Binary::from(salt),
@@ -310,6 +313,7 @@ pub fn instantiate(
                 remote_denom: msg.remote_opts.denom.to_string(),
                 pump_ica_address: None,
                 validators_set_contract,
+                validators_stats_contract: None,
                 unbonding_period: msg.core_params.unbonding_period,
                 unbonding_safe_period: msg.core_params.unbonding_safe_period,
                 unbond_batch_switch_time: msg.core_params.unbond_batch_switch_time,
@@ -323,6 +327,8 @@ pub fn instantiate(
                 emergency_address: None,
                 min_stake_amount: msg.core_params.min_stake_amount,
                 icq_update_delay: msg.core_params.icq_update_delay,
+                rebalance_min_amount: None,
+                staking_path: None,
             })?,
             funds: vec![],
             salt: Binary::from(salt),
@@ -370,6 +376,7 @@ pub fn instantiate(
                 config: SplitterConfig {
                     receivers: get_splitter_receivers(msg.fee_params, staker_contract.to_string())?,
                     denom: msg.base_denom.to_string(),
+                    ..Default::default()
                 },
             })?,
             funds: vec![],
@@ -0,0 +1,11 @@
+use cosmwasm_schema::write_api;
+use drop_staking_base::msg::dex_router::{ExecuteMsg, InstantiateMsg, MigrateMsg, QueryMsg};
+
+fn main() {
+    write_api! {
+        instantiate: InstantiateMsg,
+        query: QueryMsg,
+        execute: ExecuteMsg,
+        migrate: MigrateMsg
+    }
+}
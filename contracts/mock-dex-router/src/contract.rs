@@ -0,0 +1,205 @@
+use cosmwasm_std::{
+    attr, ensure, entry_point, to_json_binary, BankMsg, Binary, Coin, Deps, DepsMut, Env,
+    MessageInfo, Response, Storage, Uint128,
+};
+use drop_helpers::answer::{attr_coin, response};
+use drop_staking_base::msg::dex_router::{
+    ExecuteMsg, GetRouteResponse, InstantiateMsg, MigrateMsg, QueryMsg, SwapAmountInRoute,
+};
+use drop_staking_base::state::dex_router::{Pool, POOLS, POOL_COUNT, ROUTES};
+
+use crate::error::{ContractError, ContractResult};
+
+const CONTRACT_NAME: &str = concat!("crates.io:drop-staking__", env!("CARGO_PKG_NAME"));
+const CONTRACT_VERSION: &str = env!("CARGO_PKG_VERSION");
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn instantiate(
+    deps: DepsMut,
+    _env: Env,
+    _info: MessageInfo,
+    _msg: InstantiateMsg,
+) -> ContractResult<Response> {
+    cw2::set_contract_version(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
+    POOL_COUNT.save(deps.storage, &0)?;
+    Ok(response::<(&str, &str), _>(
+        "instantiate",
+        CONTRACT_NAME,
+        [],
+    ))
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn query(deps: Deps, _env: Env, msg: QueryMsg) -> ContractResult<Binary> {
+    match msg {
+        QueryMsg::GetRoute {
+            input_denom,
+            output_denom,
+        } => {
+            let pool_route = ROUTES
+                .may_load(deps.storage, (&input_denom, &output_denom))?
+                .ok_or(ContractError::RouteNotFound {
+                    input_denom,
+                    output_denom,
+                })?;
+            Ok(to_json_binary(&GetRouteResponse { pool_route })?)
+        }
+        QueryMsg::SimulateSwap { input_coin, route } => {
+            let (output, _) = simulate_swap(deps.storage, input_coin, &route)?;
+            Ok(to_json_binary(&output.amount)?)
+        }
+        QueryMsg::Pool { pool_id } => Ok(to_json_binary(&POOLS.load(deps.storage, pool_id)?)?),
+    }
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn execute(
+    deps: DepsMut,
+    _env: Env,
+    info: MessageInfo,
+    msg: ExecuteMsg,
+) -> ContractResult<Response> {
+    match msg {
+        ExecuteMsg::CreatePool {} => execute_create_pool(deps, info),
+        ExecuteMsg::SetRoute {
+            input_denom,
+            output_denom,
+            pool_route,
+        } => {
+            ROUTES.save(deps.storage, (&input_denom, &output_denom), &pool_route)?;
+            Ok(response(
+                "execute-set-route",
+                CONTRACT_NAME,
+                [
+                    attr("input_denom", input_denom),
+                    attr("output_denom", output_denom),
+                    attr("hops", pool_route.len().to_string()),
+                ],
+            ))
+        }
+        ExecuteMsg::Swap {
+            output_denom,
+            min_output_amount,
+            route,
+            to,
+        } => execute_swap(deps, info, output_denom, min_output_amount, route, to),
+    }
+}
+
+fn execute_create_pool(deps: DepsMut, info: MessageInfo) -> ContractResult<Response> {
+    let assets: [Coin; 2] = info
+        .funds
+        .try_into()
+        .map_err(|_| ContractError::InvalidFunds {
+            reason: "pool requires exactly two coins".to_string(),
+        })?;
+    let pool_id = POOL_COUNT.load(deps.storage)? + 1;
+    POOL_COUNT.save(deps.storage, &pool_id)?;
+    POOLS.save(deps.storage, pool_id, &Pool { assets })?;
+    Ok(response(
+        "execute-create-pool",
+        CONTRACT_NAME,
+        [attr("pool_id", pool_id.to_string())],
+    ))
+}
+
+fn execute_swap(
+    deps: DepsMut,
+    info: MessageInfo,
+    output_denom: String,
+    min_output_amount: Uint128,
+    route: Option<Vec<SwapAmountInRoute>>,
+    to: Option<String>,
+) -> ContractResult<Response> {
+    let input_coin = cw_utils::one_coin(&info)?;
+    let route = match route {
+        Some(route) => route,
+        None => ROUTES
+            .may_load(deps.storage, (&input_coin.denom, &output_denom))?
+            .ok_or(ContractError::RouteNotFound {
+                input_denom: input_coin.denom.clone(),
+                output_denom: output_denom.clone(),
+            })?,
+    };
+    let (output, pools) = simulate_swap(deps.storage, input_coin.clone(), &route)?;
+    ensure!(
+        output.denom == output_denom,
+        ContractError::RouteNotFound {
+            input_denom: input_coin.denom,
+            output_denom,
+        }
+    );
+    ensure!(
+        output.amount >= min_output_amount,
+        ContractError::MinOutputNotReached {
+            amount: output.amount,
+            min_amount: min_output_amount,
+        }
+    );
+    for (pool_id, pool) in pools {
+        POOLS.save(deps.storage, pool_id, &pool)?;
+    }
+    let receiver = match to {
+        Some(to) => deps.api.addr_validate(&to)?,
+        None => info.sender,
+    };
+    Ok(response(
+        "execute-swap",
+        CONTRACT_NAME,
+        [
+            attr_coin("input", input_coin.amount, input_coin.denom),
+            attr_coin("output", output.amount, output.denom.clone()),
+            attr("receiver", receiver.to_string()),
+        ],
+    )
+    .add_message(BankMsg::Send {
+        to_address: receiver.to_string(),
+        amount: vec![output],
+    }))
+}
+
+/// Swaps through every pool of the route using `x * y = k` without fees
+fn simulate_swap(
+    storage: &dyn Storage,
+    input_coin: Coin,
+    route: &[SwapAmountInRoute],
+) -> ContractResult<(Coin, Vec<(u64, Pool)>)> {
+    let mut coin = input_coin;
+    let mut pools = vec![];
+    for hop in route {
+        let mut pool = POOLS.load(storage, hop.pool_id)?;
+        let (input_index, output_index) =
+            match (pool.assets[0].denom.as_str(), pool.assets[1].denom.as_str()) {
+                (a, b) if a == coin.denom && b == hop.token_out_denom => (0, 1),
+                (a, b) if b == coin.denom && a == hop.token_out_denom => (1, 0),
+                _ => {
+                    return Err(ContractError::InvalidPoolDenom {
+                        pool_id: hop.pool_id,
+                        denom: coin.denom,
+                    })
+                }
+            };
+        let input_reserve = pool.assets[input_index].amount;
+        let output_reserve = pool.assets[output_index].amount;
+        let output_amount =
+            output_reserve.multiply_ratio(coin.amount, input_reserve.checked_add(coin.amount)?);
+        pool.assets[input_index].amount = input_reserve.checked_add(coin.amount)?;
+        pool.assets[output_index].amount = output_reserve.checked_sub(output_amount)?;
+        pools.push((hop.pool_id, pool));
+        coin = Coin::new(output_amount.u128(), hop.token_out_denom.clone());
+    }
+    Ok((coin, pools))
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn migrate(deps: DepsMut, _env: Env, _msg: MigrateMsg) -> ContractResult<Response> {
+    let version: semver::Version = CONTRACT_VERSION.parse()?;
+    let storage_version: semver::Version =
+        cw2::get_contract_version(deps.storage)?.version.parse()?;
+
+    if storage_version < version {
+        cw2::set_contract_version(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
+    }
+
+    Ok(Response::new())
+}
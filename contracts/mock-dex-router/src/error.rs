@@ -0,0 +1,44 @@
+use cosmwasm_std::{OverflowError, StdError, Uint128};
+use cw_utils::PaymentError;
+use thiserror::Error;
+
+#[derive(Error, Debug, PartialEq)]
+pub enum ContractError {
+    #[error("{0}")]
+    Std(#[from] StdError),
+
+    #[error("{0}")]
+    OverflowError(#[from] OverflowError),
+
+    #[error("{0}")]
+    PaymentError(#[from] PaymentError),
+
+    #[error("Invalid Funds: {reason}")]
+    InvalidFunds { reason: String },
+
+    #[error("Pool {pool_id} has no {denom}")]
+    InvalidPoolDenom { pool_id: u64, denom: String },
+
+    #[error("No route from {input_denom} to {output_denom}")]
+    RouteNotFound {
+        input_denom: String,
+        output_denom: String,
+    },
+
+    #[error("Output {amount} is less than minimum {min_amount}")]
+    MinOutputNotReached {
+        amount: Uint128,
+        min_amount: Uint128,
+    },
+
+    #[error("Semver parsing error: {0}")]
+    SemVer(String),
+}
+
+impl From<semver::Error> for ContractError {
+    fn from(err: semver::Error) -> Self {
+        Self::SemVer(err.to_string())
+    }
+}
+
+pub type ContractResult<T> = Result<T, ContractError>;
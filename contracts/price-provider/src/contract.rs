@@ -1,9 +1,13 @@
-use cosmwasm_std::{to_json_binary, Decimal, Deps};
+use cosmwasm_std::{attr, ensure, to_json_binary, Addr, Decimal, Deps, Order, StdResult};
 use cosmwasm_std::{Binary, DepsMut, Env, MessageInfo, Response};
 use drop_helpers::answer::response;
 use drop_staking_base::error::price_provider::{ContractError, ContractResult};
-use drop_staking_base::msg::price_provider::{ExecuteMsg, InstantiateMsg, MigrateMsg, QueryMsg};
-use drop_staking_base::state::price_provider::PRICES;
+use drop_staking_base::msg::price_provider::{
+    ExecuteMsg, InstantiateMsg, MigrateMsg, PriceInfoResponse, PriceSource, QueryMsg,
+};
+use drop_staking_base::state::price_provider::{
+    Config, PriceFeed, CONFIG, FEEDERS, FEEDS, PRICES, PRICE_UPDATES,
+};
 
 const CONTRACT_NAME: &str = concat!("crates.io:drop-staking__", env!("CARGO_PKG_NAME"));
 const CONTRACT_VERSION: &str = env!("CARGO_PKG_VERSION");
@@ -23,25 +27,118 @@ pub fn instantiate(
 
     cw_ownable::initialize_owner(deps.storage, deps.api, Some(owner.as_str()))?;
 
+    let feeders = if msg.feeders.is_empty() {
+        vec![owner]
+    } else {
+        msg.feeders
+            .iter()
+            .map(|feeder| deps.api.addr_validate(feeder))
+            .collect::<StdResult<_>>()?
+    };
+    FEEDERS.save(deps.storage, &feeders)?;
+    CONFIG.save(
+        deps.storage,
+        &Config {
+            max_age: msg.max_age,
+        },
+    )?;
+
     Ok(Response::default())
 }
 
 #[cfg_attr(not(feature = "library"), cosmwasm_std::entry_point)]
-pub fn query(deps: Deps, _env: Env, msg: QueryMsg) -> ContractResult<Binary> {
+pub fn query(deps: Deps, env: Env, msg: QueryMsg) -> ContractResult<Binary> {
     match msg {
-        QueryMsg::Price { denom } => query_price(deps, denom),
+        QueryMsg::Price { denom } => Ok(to_json_binary(
+            &query_fresh_price(deps, &env, denom)?.price,
+        )?),
+        QueryMsg::PriceInfo { denom } => {
+            Ok(to_json_binary(&query_fresh_price(deps, &env, denom)?)?)
+        }
+        QueryMsg::Prices { denoms } => {
+            let mut prices = vec![];
+            for denom in denoms {
+                if let Some(price) = aggregate_price(deps, &env, &denom)? {
+                    prices.push(price);
+                }
+            }
+            Ok(to_json_binary(&prices)?)
+        }
+        QueryMsg::Feeders {} => Ok(to_json_binary(&FEEDERS.load(deps.storage)?)?),
+        QueryMsg::Config {} => Ok(to_json_binary(
+            &CONFIG.may_load(deps.storage)?.unwrap_or_default(),
+        )?),
         QueryMsg::Ownership {} => Ok(to_json_binary(&cw_ownable::get_ownership(deps.storage)?)?),
     }
 }
 
-fn query_price(deps: Deps, denom: String) -> ContractResult<Binary> {
-    let price = PRICES
-        .load(deps.storage, &denom)
-        .map_err(|e| ContractError::DenomNotFound {
-            details: e.to_string(),
-        })?;
+fn query_fresh_price(deps: Deps, env: &Env, denom: String) -> ContractResult<PriceInfoResponse> {
+    let price = aggregate_price(deps, env, &denom)?.ok_or(ContractError::DenomNotFound {
+        details: denom.clone(),
+    })?;
+    ensure!(
+        !price.stale,
+        ContractError::StalePrice {
+            denom,
+            updated_at: price.updated_at,
+        }
+    );
+    Ok(price)
+}
+
+/// Median of the feeds younger than `max_age`, or of all feeds marked as stale if there is none
+fn aggregate_price(
+    deps: Deps,
+    env: &Env,
+    denom: &str,
+) -> ContractResult<Option<PriceInfoResponse>> {
+    let config = CONFIG.may_load(deps.storage)?.unwrap_or_default();
+    let feeds: Vec<(Addr, PriceFeed)> = FEEDS
+        .prefix(denom)
+        .range(deps.storage, None, None, Order::Ascending)
+        .collect::<StdResult<_>>()?;
+    if feeds.is_empty() {
+        return Ok(None);
+    }
+    let now = env.block.time.seconds();
+    let (mut fresh, stale): (Vec<_>, Vec<_>) = feeds.into_iter().partition(|(_, feed)| {
+        config.max_age.map_or(true, |max_age| {
+            feed.updated_at.saturating_add(max_age) >= now
+        })
+    });
+    let is_stale = fresh.is_empty();
+    if is_stale {
+        fresh = stale;
+    }
+
+    fresh.sort_by(|(_, a), (_, b)| a.price.cmp(&b.price));
+    let middle = fresh.len() / 2;
+    let price = if fresh.len() % 2 == 0 {
+        Decimal::percent(50) * (fresh[middle - 1].1.price + fresh[middle].1.price)
+    } else {
+        fresh[middle].1.price
+    };
+    let updated_at = fresh
+        .iter()
+        .map(|(_, feed)| feed.updated_at)
+        .min()
+        .unwrap_or_default();
+    let mut feeders: Vec<Addr> = fresh.into_iter().map(|(feeder, _)| feeder).collect();
+    let source = if feeders.len() == 1 {
+        PriceSource::Feeder {
+            address: feeders.remove(0),
+        }
+    } else {
+        PriceSource::Median { feeders }
+    };
 
-    to_json_binary(&price).map_err(ContractError::Std)
+    Ok(Some(PriceInfoResponse {
+        denom: denom.to_string(),
+        price,
+        updated_at,
+        source,
+        stale: is_stale,
+    }))
 }
 
 #[cfg_attr(not(feature = "library"), cosmwasm_std::entry_point)]
@@ -54,6 +151,22 @@ pub fn execute(
     match msg {
         ExecuteMsg::RemoveDenom { denom } => execute_remove_denom(deps, env, info, denom),
         ExecuteMsg::SetPrice { denom, price } => execute_set_price(deps, env, info, denom, price),
+        ExecuteMsg::AddFeeder { address } => execute_add_feeder(deps, info, address),
+        ExecuteMsg::RemoveFeeder { address } => execute_remove_feeder(deps, info, address),
+        ExecuteMsg::UpdateConfig { new_config } => {
+            cw_ownable::assert_owner(deps.storage, &info.sender)?;
+            CONFIG.save(deps.storage, &new_config)?;
+            Ok(response(
+                "execute-update-config",
+                CONTRACT_NAME,
+                [attr(
+                    "max_age",
+                    new_config
+                        .max_age
+                        .map_or("none".to_string(), |max_age| max_age.to_string()),
+                )],
+            ))
+        }
         ExecuteMsg::UpdateOwnership(action) => {
             cw_ownable::update_ownership(deps.into_empty(), &env.block, &info.sender, action)?;
             Ok(response::<(&str, &str), _>(
@@ -67,17 +180,31 @@ pub fn execute(
 
 fn execute_set_price(
     deps: DepsMut,
-    _env: Env,
+    env: Env,
     info: MessageInfo,
     denom: String,
     price: Decimal,
 ) -> ContractResult<Response> {
-    cw_ownable::assert_owner(deps.storage, &info.sender)?;
-    PRICES.save(deps.storage, &denom, &price)?;
-    Ok(response::<(&str, &str), _>(
+    ensure!(
+        FEEDERS.load(deps.storage)?.contains(&info.sender),
+        ContractError::Unauthorized
+    );
+    FEEDS.save(
+        deps.storage,
+        (&denom, &info.sender),
+        &PriceFeed {
+            price,
+            updated_at: env.block.time.seconds(),
+        },
+    )?;
+    Ok(response(
         "execute-set-price",
         CONTRACT_NAME,
-        [],
+        [
+            attr("denom", denom),
+            attr("price", price.to_string()),
+            attr("feeder", info.sender),
+        ],
     ))
 }
 
@@ -88,7 +215,13 @@ fn execute_remove_denom(
     denom: String,
 ) -> ContractResult<Response> {
     cw_ownable::assert_owner(deps.storage, &info.sender)?;
-    PRICES.remove(deps.storage, &denom);
+    let feeders: Vec<Addr> = FEEDS
+        .prefix(&denom)
+        .keys(deps.storage, None, None, Order::Ascending)
+        .collect::<StdResult<_>>()?;
+    for feeder in feeders {
+        FEEDS.remove(deps.storage, (&denom, &feeder));
+    }
     Ok(response::<(&str, &str), _>(
         "execute-remove-denom",
         CONTRACT_NAME,
@@ -96,6 +229,52 @@ fn execute_remove_denom(
     ))
 }
 
+fn execute_add_feeder(
+    deps: DepsMut,
+    info: MessageInfo,
+    address: String,
+) -> ContractResult<Response> {
+    cw_ownable::assert_owner(deps.storage, &info.sender)?;
+    let feeder = deps.api.addr_validate(&address)?;
+    let mut feeders = FEEDERS.load(deps.storage)?;
+    ensure!(
+        !feeders.contains(&feeder),
+        ContractError::FeederAlreadyExists { address }
+    );
+    feeders.push(feeder);
+    FEEDERS.save(deps.storage, &feeders)?;
+    Ok(response(
+        "execute-add-feeder",
+        CONTRACT_NAME,
+        [attr("feeder", address)],
+    ))
+}
+
+fn execute_remove_feeder(
+    deps: DepsMut,
+    info: MessageInfo,
+    address: String,
+) -> ContractResult<Response> {
+    cw_ownable::assert_owner(deps.storage, &info.sender)?;
+    let feeder = deps.api.addr_validate(&address)?;
+    let mut feeders = FEEDERS.load(deps.storage)?;
+    feeders.retain(|f| *f != feeder);
+    FEEDERS.save(deps.storage, &feeders)?;
+    // prices of the removed feeder must not take part in aggregation anymore
+    let feeds: Vec<(String, Addr)> = FEEDS
+        .keys(deps.storage, None, None, Order::Ascending)
+        .filter(|key| key.as_ref().map_or(true, |(_, f)| *f == feeder))
+        .collect::<StdResult<_>>()?;
+    for (denom, feeder) in feeds {
+        FEEDS.remove(deps.storage, (&denom, &feeder));
+    }
+    Ok(response(
+        "execute-remove-feeder",
+        CONTRACT_NAME,
+        [attr("feeder", address)],
+    ))
+}
+
 #[cfg_attr(not(feature = "library"), cosmwasm_std::entry_point)]
 pub fn migrate(deps: DepsMut, _env: Env, _msg: MigrateMsg) -> ContractResult<Response> {
     let version: semver::Version = CONTRACT_VERSION.parse()?;
@@ -106,5 +285,30 @@ pub fn migrate(deps: DepsMut, _env: Env, _msg: MigrateMsg) -> ContractResult<Res
         cw2::set_contract_version(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
     }
 
+    // owner set prices become the feeds of the owner
+    if FEEDERS.may_load(deps.storage)?.is_none() {
+        let owner = cw_ownable::get_ownership(deps.storage)?
+            .owner
+            .ok_or(cw_ownable::OwnershipError::NoOwner)?;
+        let prices: Vec<(String, Decimal)> = PRICES
+            .range(deps.storage, None, None, Order::Ascending)
+            .collect::<StdResult<_>>()?;
+        for (denom, price) in prices {
+            // prices of unknown age are stale until they are set again
+            let updated_at = PRICE_UPDATES
+                .may_load(deps.storage, &denom)?
+                .unwrap_or_default();
+            FEEDS.save(
+                deps.storage,
+                (&denom, &owner),
+                &PriceFeed { price, updated_at },
+            )?;
+            PRICES.remove(deps.storage, &denom);
+            PRICE_UPDATES.remove(deps.storage, &denom);
+        }
+        FEEDERS.save(deps.storage, &vec![owner])?;
+        CONFIG.save(deps.storage, &Config::default())?;
+    }
+
     Ok(Response::new())
 }
@@ -0,0 +1,284 @@
+use crate::contract::{execute, instantiate, migrate, query};
+use cosmwasm_std::testing::{mock_dependencies, mock_env, mock_info};
+use cosmwasm_std::{from_json, Addr, Decimal, DepsMut, Env};
+use drop_staking_base::error::price_provider::ContractError;
+use drop_staking_base::msg::price_provider::{
+    ExecuteMsg, InstantiateMsg, MigrateMsg, PriceInfoResponse, PriceSource, QueryMsg,
+};
+use drop_staking_base::state::price_provider::{Config, PRICES, PRICE_UPDATES};
+
+fn set_price(deps: DepsMut, env: Env, feeder: &str, price: u64) {
+    execute(
+        deps,
+        env,
+        mock_info(feeder, &[]),
+        ExecuteMsg::SetPrice {
+            denom: "ueth".to_string(),
+            price: Decimal::percent(price),
+        },
+    )
+    .unwrap();
+}
+
+#[test]
+fn test_median_of_feeders() {
+    let mut deps = mock_dependencies();
+    instantiate(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("owner", &[]),
+        InstantiateMsg {
+            owner: None,
+            feeders: vec!["feeder1".to_string(), "feeder2".to_string()],
+            max_age: Some(100),
+        },
+    )
+    .unwrap();
+
+    let res = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("owner", &[]),
+        ExecuteMsg::SetPrice {
+            denom: "ueth".to_string(),
+            price: Decimal::one(),
+        },
+    )
+    .unwrap_err();
+    assert_eq!(res, ContractError::Unauthorized);
+
+    execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("owner", &[]),
+        ExecuteMsg::AddFeeder {
+            address: "feeder3".to_string(),
+        },
+    )
+    .unwrap();
+
+    let mut env = mock_env();
+    set_price(deps.as_mut(), env.clone(), "feeder1", 100);
+    env.block.time = env.block.time.plus_seconds(50);
+    set_price(deps.as_mut(), env.clone(), "feeder2", 300);
+    set_price(deps.as_mut(), env.clone(), "feeder3", 150);
+
+    let price: Decimal = from_json(
+        query(
+            deps.as_ref(),
+            env.clone(),
+            QueryMsg::Price {
+                denom: "ueth".to_string(),
+            },
+        )
+        .unwrap(),
+    )
+    .unwrap();
+    assert_eq!(price, Decimal::percent(150));
+
+    // the feeder1 price gets outdated, so the median of the two others is taken
+    env.block.time = env.block.time.plus_seconds(51);
+    let prices: Vec<PriceInfoResponse> = from_json(
+        query(
+            deps.as_ref(),
+            env.clone(),
+            QueryMsg::Prices {
+                denoms: vec!["ueth".to_string(), "uatom".to_string()],
+            },
+        )
+        .unwrap(),
+    )
+    .unwrap();
+    assert_eq!(
+        prices,
+        vec![PriceInfoResponse {
+            denom: "ueth".to_string(),
+            price: Decimal::percent(225),
+            updated_at: mock_env().block.time.seconds() + 50,
+            source: PriceSource::Median {
+                feeders: vec![Addr::unchecked("feeder3"), Addr::unchecked("feeder2")],
+            },
+            stale: false,
+        }]
+    );
+
+    execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info("owner", &[]),
+        ExecuteMsg::RemoveFeeder {
+            address: "feeder2".to_string(),
+        },
+    )
+    .unwrap();
+    let price: PriceInfoResponse = from_json(
+        query(
+            deps.as_ref(),
+            env.clone(),
+            QueryMsg::PriceInfo {
+                denom: "ueth".to_string(),
+            },
+        )
+        .unwrap(),
+    )
+    .unwrap();
+    assert_eq!(
+        price.source,
+        PriceSource::Feeder {
+            address: Addr::unchecked("feeder3")
+        }
+    );
+    assert_eq!(price.price, Decimal::percent(150));
+}
+
+#[test]
+fn test_stale_price() {
+    let mut deps = mock_dependencies();
+    instantiate(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("owner", &[]),
+        InstantiateMsg {
+            owner: None,
+            feeders: vec![],
+            max_age: Some(100),
+        },
+    )
+    .unwrap();
+    set_price(deps.as_mut(), mock_env(), "owner", 100);
+
+    let mut env = mock_env();
+    env.block.time = env.block.time.plus_seconds(101);
+    let res = query(
+        deps.as_ref(),
+        env.clone(),
+        QueryMsg::Price {
+            denom: "ueth".to_string(),
+        },
+    )
+    .unwrap_err();
+    assert_eq!(
+        res,
+        ContractError::StalePrice {
+            denom: "ueth".to_string(),
+            updated_at: mock_env().block.time.seconds(),
+        }
+    );
+
+    let prices: Vec<PriceInfoResponse> = from_json(
+        query(
+            deps.as_ref(),
+            env.clone(),
+            QueryMsg::Prices {
+                denoms: vec!["ueth".to_string()],
+            },
+        )
+        .unwrap(),
+    )
+    .unwrap();
+    assert!(prices[0].stale);
+
+    execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info("owner", &[]),
+        ExecuteMsg::UpdateConfig {
+            new_config: Config { max_age: None },
+        },
+    )
+    .unwrap();
+    let price: Decimal = from_json(
+        query(
+            deps.as_ref(),
+            env,
+            QueryMsg::Price {
+                denom: "ueth".to_string(),
+            },
+        )
+        .unwrap(),
+    )
+    .unwrap();
+    assert_eq!(price, Decimal::one());
+}
+
+#[test]
+fn test_migrate_legacy_prices() {
+    let mut deps = mock_dependencies();
+    cw2::set_contract_version(
+        deps.as_mut().storage,
+        "crates.io:drop-staking__drop-price-provider",
+        "1.0.0",
+    )
+    .unwrap();
+    let deps_mut = deps.as_mut();
+    cw_ownable::initialize_owner(deps_mut.storage, deps_mut.api, Some("owner")).unwrap();
+    let env = mock_env();
+    for denom in ["uatom", "ueth"] {
+        PRICES
+            .save(deps.as_mut().storage, &denom.to_string(), &Decimal::one())
+            .unwrap();
+    }
+    PRICE_UPDATES
+        .save(
+            deps.as_mut().storage,
+            &"ueth".to_string(),
+            &env.block.time.seconds(),
+        )
+        .unwrap();
+    migrate(deps.as_mut(), env.clone(), MigrateMsg {}).unwrap();
+
+    execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info("owner", &[]),
+        ExecuteMsg::UpdateConfig {
+            new_config: Config {
+                max_age: Some(u64::MAX),
+            },
+        },
+    )
+    .unwrap();
+    let info: PriceInfoResponse = from_json(
+        query(
+            deps.as_ref(),
+            env.clone(),
+            QueryMsg::PriceInfo {
+                denom: "ueth".to_string(),
+            },
+        )
+        .unwrap(),
+    )
+    .unwrap();
+    assert_eq!(info.updated_at, env.block.time.seconds());
+    assert!(!info.stale);
+
+    execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info("owner", &[]),
+        ExecuteMsg::UpdateConfig {
+            new_config: Config { max_age: Some(100) },
+        },
+    )
+    .unwrap();
+    // the age of a legacy price without an update time is unknown
+    let info: PriceInfoResponse = from_json(
+        query(
+            deps.as_ref(),
+            env,
+            QueryMsg::PriceInfo {
+                denom: "uatom".to_string(),
+            },
+        )
+        .unwrap(),
+    )
+    .unwrap();
+    assert_eq!(info.updated_at, 0);
+    assert!(info.stale);
+    assert_eq!(
+        info.source,
+        PriceSource::Feeder {
+            address: Addr::unchecked("owner")
+        }
+    );
+}
@@ -1,21 +1,26 @@
 use cosmos_sdk_proto::cosmos::gov::v1beta1::ProposalStatus;
 use cosmwasm_std::{
     attr, ensure_eq, to_json_binary, Attribute, Binary, CosmosMsg, Decimal, Deps, DepsMut, Env,
-    MessageInfo, Order, Reply, Response, StdResult, SubMsg, Uint128, WasmMsg,
+    MessageInfo, Order, Reply, Response, StdResult, SubMsg, SubMsgResult, Uint128, WasmMsg,
 };
 use std::collections::HashMap;
 
 use crate::error::{ContractError, ContractResult};
 use drop_helpers::answer::response;
 use drop_helpers::query_id::get_query_id;
+use drop_puppeteer_base::msg::{
+    ResponseHookMsg as PuppeteerResponseHookMsg, Transaction, VoteOption, WeightedVoteOption,
+};
 use drop_staking_base::msg::proposal_votes::ExecuteMsg as ProposalVotesExecuteMsg;
 use drop_staking_base::msg::provider_proposals::{
     ExecuteMsg, InstantiateMsg, MigrateMsg, QueryMsg,
 };
+use drop_staking_base::msg::puppeteer::ExecuteMsg as PuppeteerExecuteMsg;
 use drop_staking_base::msg::validatorset::ExecuteMsg as ValidatorSetExecuteMsg;
 use drop_staking_base::state::provider_proposals::{
-    Config, ConfigOptional, Metrics, ProposalInfo, CONFIG, PROPOSALS, PROPOSALS_REPLY_ID,
-    PROPOSALS_VOTES, QUERY_ID,
+    Config, ConfigOptional, Metrics, ProposalInfo, ProtocolVote, ProtocolVoteStatus, VotingPolicy,
+    ABSTAIN_VOTE_REPLY_ID_OFFSET, CONFIG, PROPOSALS, PROPOSALS_REPLY_ID, PROPOSALS_VOTES,
+    PROTOCOL_VOTES, QUERY_ID,
 };
 use neutron_sdk::bindings::msg::NeutronMsg;
 use neutron_sdk::bindings::query::{NeutronQuery, QueryRegisteredQueryResultResponse};
@@ -55,6 +60,8 @@ pub fn instantiate(
         init_proposal: msg.init_proposal,
         proposals_prefetch: msg.proposals_prefetch,
         veto_spam_threshold: msg.veto_spam_threshold,
+        puppeteer_address: None,
+        voting_policy: VotingPolicy::Disabled,
     };
 
     CONFIG.save(deps.storage, config)?;
@@ -94,6 +101,9 @@ pub fn query(deps: Deps<NeutronQuery>, _env: Env, msg: QueryMsg) -> StdResult<Bi
         QueryMsg::GetProposal { proposal_id } => query_proposal(deps, proposal_id),
         QueryMsg::GetProposals {} => query_proposals(deps),
         QueryMsg::Metrics {} => query_metrics(deps),
+        QueryMsg::ProtocolVote { proposal_id } => {
+            to_json_binary(&PROTOCOL_VOTES.may_load(deps.storage, proposal_id)?)
+        }
     }
 }
 
@@ -158,13 +168,18 @@ fn query_proposals(deps: Deps<NeutronQuery>) -> StdResult<Binary> {
 #[cfg_attr(not(feature = "library"), cosmwasm_std::entry_point)]
 pub fn execute(
     deps: DepsMut<NeutronQuery>,
-    _env: Env,
+    env: Env,
     info: MessageInfo,
     msg: ExecuteMsg,
 ) -> ContractResult<Response<NeutronMsg>> {
     match msg {
         ExecuteMsg::UpdateConfig { new_config } => execute_update_config(deps, info, new_config),
         ExecuteMsg::UpdateProposalVotes { votes } => execute_update_votes(deps, info, votes),
+        ExecuteMsg::CastVote {
+            proposal_id,
+            options,
+        } => execute_cast_vote(deps, env, info, proposal_id, options),
+        ExecuteMsg::PuppeteerHook(msg) => execute_puppeteer_hook(deps, info, *msg),
     }
 }
 
@@ -235,6 +250,20 @@ fn execute_update_config(
         attrs.push(attr("veto_spam_threshold", veto_spam_threshold.to_string()))
     }
 
+    if let Some(puppeteer_address) = new_config.puppeteer_address {
+        let puppeteer_address = deps.api.addr_validate(&puppeteer_address)?;
+        config.puppeteer_address = Some(puppeteer_address.to_string());
+        attrs.push(attr("puppeteer_address", puppeteer_address))
+    }
+
+    if let Some(voting_policy) = new_config.voting_policy {
+        if let VotingPolicy::Operator { address } = &voting_policy {
+            deps.api.addr_validate(address)?;
+        }
+        attrs.push(attr("voting_policy", format!("{:?}", voting_policy)));
+        config.voting_policy = voting_policy;
+    }
+
     CONFIG.save(deps.storage, &config)?;
 
     Ok(response("config_update", CONTRACT_NAME, attrs).add_messages(msgs))
@@ -270,6 +299,162 @@ pub fn execute_update_votes(
     ))
 }
 
+pub fn execute_cast_vote(
+    deps: DepsMut<NeutronQuery>,
+    env: Env,
+    info: MessageInfo,
+    proposal_id: u64,
+    options: Vec<WeightedVoteOption>,
+) -> ContractResult<Response<NeutronMsg>> {
+    let config = CONFIG.load(deps.storage)?;
+
+    match &config.voting_policy {
+        VotingPolicy::Operator { address } if address.as_str() == info.sender.as_str() => {}
+        _ => return Err(ContractError::Unauthorized),
+    }
+
+    let proposal = PROPOSALS
+        .may_load(deps.storage, proposal_id)?
+        .ok_or(ContractError::ProposalNotInVotingPeriod { proposal_id })?;
+    ensure_eq!(
+        proposal.status,
+        ProposalStatus::VotingPeriod as i32,
+        ContractError::ProposalNotInVotingPeriod { proposal_id }
+    );
+
+    let msg = protocol_vote_msg(deps, &env, &config, proposal_id, options)?;
+
+    Ok(response(
+        "execute-cast_vote",
+        CONTRACT_NAME,
+        [
+            attr("action", "cast_vote"),
+            attr("proposal_id", proposal_id.to_string()),
+        ],
+    )
+    .add_message(msg))
+}
+
+pub fn execute_puppeteer_hook(
+    deps: DepsMut<NeutronQuery>,
+    info: MessageInfo,
+    msg: PuppeteerResponseHookMsg,
+) -> ContractResult<Response<NeutronMsg>> {
+    let config = CONFIG.load(deps.storage)?;
+
+    ensure_eq!(
+        config.puppeteer_address,
+        Some(info.sender.to_string()),
+        ContractError::Unauthorized
+    );
+
+    let mut attrs = vec![attr("action", "puppeteer_hook")];
+    let (transaction, succeeded) = match msg {
+        PuppeteerResponseHookMsg::Success(msg) => (msg.transaction, true),
+        PuppeteerResponseHookMsg::Error(msg) => (msg.transaction, false),
+    };
+    if let Transaction::Vote { proposal_id, .. } | Transaction::WeightedVote { proposal_id, .. } =
+        transaction
+    {
+        if succeeded {
+            PROTOCOL_VOTES.update(deps.storage, proposal_id, |vote| {
+                vote.map(|vote| ProtocolVote {
+                    status: ProtocolVoteStatus::Voted,
+                    ..vote
+                })
+                .ok_or(ContractError::ProtocolVoteNotFound { proposal_id })
+            })?;
+        } else {
+            // failed votes are cast again on the next proposals update or by the operator
+            PROTOCOL_VOTES.remove(deps.storage, proposal_id);
+        }
+        attrs.push(attr("proposal_id", proposal_id.to_string()));
+        attrs.push(attr("succeeded", succeeded.to_string()));
+    }
+
+    Ok(response("execute-puppeteer_hook", CONTRACT_NAME, attrs))
+}
+
+/// Records the protocol vote as pending and casts it via the puppeteer
+fn protocol_vote_msg(
+    deps: DepsMut<NeutronQuery>,
+    env: &Env,
+    config: &Config,
+    proposal_id: u64,
+    options: Vec<WeightedVoteOption>,
+) -> ContractResult<CosmosMsg<NeutronMsg>> {
+    let puppeteer_address = config
+        .puppeteer_address
+        .clone()
+        .ok_or(ContractError::PuppeteerNotSet)?;
+
+    let reply_to = env.contract.address.to_string();
+    let msg = match options.as_slice() {
+        [single] if single.weight == Decimal::one() => PuppeteerExecuteMsg::Vote {
+            proposal_id,
+            option: single.option,
+            reply_to,
+        },
+        _ => PuppeteerExecuteMsg::WeightedVote {
+            proposal_id,
+            options: options.clone(),
+            reply_to,
+        },
+    };
+
+    PROTOCOL_VOTES.save(
+        deps.storage,
+        proposal_id,
+        &ProtocolVote {
+            options,
+            status: ProtocolVoteStatus::Pending,
+        },
+    )?;
+
+    Ok(CosmosMsg::Wasm(WasmMsg::Execute {
+        contract_addr: puppeteer_address,
+        msg: to_json_binary(&msg)?,
+        funds: vec![],
+    }))
+}
+
+/// Abstains on the proposals in the voting period which weren't voted by the protocol yet.
+/// Votes are sent as reply-on-error submessages so a failing one doesn't revert the update.
+pub(crate) fn abstain_votes_msgs(
+    mut deps: DepsMut<NeutronQuery>,
+    env: &Env,
+    config: &Config,
+    proposals: &[Proposal],
+) -> ContractResult<Vec<SubMsg<NeutronMsg>>> {
+    if config.voting_policy != VotingPolicy::Abstain || config.puppeteer_address.is_none() {
+        return Ok(vec![]);
+    }
+
+    let mut msgs = vec![];
+    for proposal in proposals {
+        if proposal.status == ProposalStatus::VotingPeriod as i32
+            && !PROTOCOL_VOTES.has(deps.storage, proposal.proposal_id)
+        {
+            let msg = protocol_vote_msg(
+                deps.branch(),
+                env,
+                config,
+                proposal.proposal_id,
+                vec![WeightedVoteOption {
+                    option: VoteOption::Abstain,
+                    weight: Decimal::one(),
+                }],
+            )?;
+            msgs.push(SubMsg::reply_on_error(
+                msg,
+                ABSTAIN_VOTE_REPLY_ID_OFFSET + proposal.proposal_id,
+            ));
+        }
+    }
+
+    Ok(msgs)
+}
+
 #[cfg_attr(not(feature = "library"), cosmwasm_std::entry_point)]
 pub fn sudo(
     deps: DepsMut<NeutronQuery>,
@@ -288,7 +473,7 @@ pub fn sudo(
 
 pub fn sudo_kv_query_result(
     deps: DepsMut<NeutronQuery>,
-    _env: Env,
+    env: Env,
     query_id: u64,
 ) -> ContractResult<Response<NeutronMsg>> {
     deps.api.debug(&format!(
@@ -300,14 +485,15 @@ pub fn sudo_kv_query_result(
     let interchain_query_result = get_raw_interchain_query_result(deps.as_ref(), query_id)?;
 
     if Some(query_id) == proposals_query_id {
-        return sudo_proposals_query(deps, interchain_query_result);
+        return sudo_proposals_query(deps, env, interchain_query_result);
     }
 
     Ok(Response::default())
 }
 
 fn sudo_proposals_query(
-    deps: DepsMut<NeutronQuery>,
+    mut deps: DepsMut<NeutronQuery>,
+    env: Env,
     interchain_query_result: QueryRegisteredQueryResultResponse,
 ) -> ContractResult<Response<NeutronMsg>> {
     let data: GovernmentProposal =
@@ -366,13 +552,18 @@ fn sudo_proposals_query(
         None => deps.api.debug("WASMDEBUG: first_proposal is None"),
     }
 
-    for proposal in data.proposals {
+    for proposal in data.proposals.iter() {
         if proposal.status != ProposalStatus::Unspecified as i32 {
-            PROPOSALS.save(deps.storage, proposal.proposal_id, &proposal)?;
+            PROPOSALS.save(deps.storage, proposal.proposal_id, proposal)?;
         }
     }
 
-    Ok(Response::new().add_messages(msgs))
+    let config = CONFIG.load(deps.storage)?;
+    let abstain_msgs = abstain_votes_msgs(deps.branch(), &env, &config, &data.proposals)?;
+
+    Ok(Response::new()
+        .add_messages(msgs)
+        .add_submessages(abstain_msgs))
 }
 
 fn is_proposal_finished(proposal: &Proposal) -> bool {
@@ -417,10 +608,33 @@ pub fn reply(deps: DepsMut, env: Env, msg: Reply) -> ContractResult<Response> {
 
     match msg.id {
         PROPOSALS_REPLY_ID => proposals_votes_reply(deps, env, msg),
+        id if id >= ABSTAIN_VOTE_REPLY_ID_OFFSET => {
+            abstain_vote_reply(deps, id - ABSTAIN_VOTE_REPLY_ID_OFFSET, msg)
+        }
         id => Err(ContractError::UnknownReplyId { id }),
     }
 }
 
+fn abstain_vote_reply(deps: DepsMut, proposal_id: u64, msg: Reply) -> ContractResult<Response> {
+    let error = match msg.result {
+        SubMsgResult::Err(error) => error,
+        SubMsgResult::Ok(_) => String::new(),
+    };
+
+    // the vote is cast again on the next proposals update
+    PROTOCOL_VOTES.remove(deps.storage, proposal_id);
+
+    Ok(response(
+        "reply-abstain_vote",
+        CONTRACT_NAME,
+        [
+            attr("action", "abstain_vote_failed"),
+            attr("proposal_id", proposal_id.to_string()),
+            attr("error", error),
+        ],
+    ))
+}
+
 fn proposals_votes_reply(deps: DepsMut, _env: Env, msg: Reply) -> ContractResult<Response> {
     let query_id = get_query_id(msg.result)?;
 
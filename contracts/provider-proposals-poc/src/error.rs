@@ -17,6 +17,15 @@ pub enum ContractError {
     #[error("unauthorized")]
     Unauthorized,
 
+    #[error("puppeteer address is not set")]
+    PuppeteerNotSet,
+
+    #[error("proposal {proposal_id} is not in the voting period")]
+    ProposalNotInVotingPeriod { proposal_id: u64 },
+
+    #[error("protocol vote for proposal {proposal_id} not found")]
+    ProtocolVoteNotFound { proposal_id: u64 },
+
     #[error("unknown reply id: {id}")]
     UnknownReplyId { id: u64 },
 
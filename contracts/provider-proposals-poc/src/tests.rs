@@ -31,6 +31,8 @@ fn instantiate() {
             init_proposal: 1,
             proposals_prefetch: 5,
             veto_spam_threshold: Decimal::from_atomics(1u64, 2).unwrap(),
+            puppeteer_address: None,
+            voting_policy: drop_staking_base::state::provider_proposals::VotingPolicy::Disabled,
         },
     )
     .unwrap();
@@ -50,6 +52,8 @@ fn instantiate() {
             init_proposal: 1,
             proposals_prefetch: 5,
             veto_spam_threshold: Decimal::from_atomics(1u64, 2).unwrap(),
+            puppeteer_address: None,
+            voting_policy: drop_staking_base::state::provider_proposals::VotingPolicy::Disabled,
         }
     );
 
@@ -123,6 +127,8 @@ fn query_config() {
                 init_proposal: 1,
                 proposals_prefetch: 5,
                 veto_spam_threshold: Decimal::from_atomics(1u64, 2).unwrap(),
+                puppeteer_address: None,
+                voting_policy: drop_staking_base::state::provider_proposals::VotingPolicy::Disabled,
             },
         )
         .unwrap();
@@ -145,6 +151,8 @@ fn query_config() {
             init_proposal: 1,
             proposals_prefetch: 5,
             veto_spam_threshold: Decimal::from_atomics(1u64, 2).unwrap(),
+            puppeteer_address: None,
+            voting_policy: drop_staking_base::state::provider_proposals::VotingPolicy::Disabled,
         })
         .unwrap()
     );
@@ -167,6 +175,8 @@ fn update_config_wrong_owner() {
                 init_proposal: 1,
                 proposals_prefetch: 5,
                 veto_spam_threshold: Decimal::from_atomics(1u64, 2).unwrap(),
+                puppeteer_address: None,
+                voting_policy: drop_staking_base::state::provider_proposals::VotingPolicy::Disabled,
             },
         )
         .unwrap();
@@ -186,6 +196,8 @@ fn update_config_wrong_owner() {
                 init_proposal: None,
                 proposals_prefetch: Some(5),
                 veto_spam_threshold: Some(Decimal::from_atomics(1u64, 2).unwrap()),
+                puppeteer_address: None,
+                voting_policy: None,
             },
         },
     )
@@ -225,6 +237,8 @@ fn update_config_ok() {
                 init_proposal: 1,
                 proposals_prefetch: 5,
                 veto_spam_threshold: Decimal::from_atomics(1u64, 2).unwrap(),
+                puppeteer_address: None,
+                voting_policy: drop_staking_base::state::provider_proposals::VotingPolicy::Disabled,
             },
         )
         .unwrap();
@@ -244,6 +258,8 @@ fn update_config_ok() {
                 proposals_prefetch: Some(7),
                 init_proposal: None,
                 veto_spam_threshold: Some(Decimal::from_atomics(3u64, 2).unwrap()),
+                puppeteer_address: None,
+                voting_policy: None,
             },
         },
     )
@@ -268,6 +284,8 @@ fn update_config_ok() {
             init_proposal: 1,
             proposals_prefetch: 7,
             veto_spam_threshold: Decimal::from_atomics(3u64, 2).unwrap(),
+            puppeteer_address: None,
+            voting_policy: drop_staking_base::state::provider_proposals::VotingPolicy::Disabled,
         })
         .unwrap()
     );
@@ -290,6 +308,8 @@ fn update_votes_wrong_sender_address() {
                 init_proposal: 1,
                 proposals_prefetch: 5,
                 veto_spam_threshold: Decimal::from_atomics(1u64, 2).unwrap(),
+                puppeteer_address: None,
+                voting_policy: drop_staking_base::state::provider_proposals::VotingPolicy::Disabled,
             },
         )
         .unwrap();
@@ -328,6 +348,8 @@ fn update_votes_ok() {
                 init_proposal: 1,
                 proposals_prefetch: 5,
                 veto_spam_threshold: Decimal::from_atomics(1u64, 2).unwrap(),
+                puppeteer_address: None,
+                voting_policy: drop_staking_base::state::provider_proposals::VotingPolicy::Disabled,
             },
         )
         .unwrap();
@@ -380,6 +402,8 @@ fn update_votes_with_data() {
                 init_proposal: 1,
                 proposals_prefetch: 5,
                 veto_spam_threshold: Decimal::from_atomics(1u64, 2).unwrap(),
+                puppeteer_address: None,
+                voting_policy: drop_staking_base::state::provider_proposals::VotingPolicy::Disabled,
             },
         )
         .unwrap();
@@ -463,4 +487,348 @@ fn update_votes_with_data() {
     );
 }
 
+fn voting_config(
+    voting_policy: drop_staking_base::state::provider_proposals::VotingPolicy,
+) -> drop_staking_base::state::provider_proposals::Config {
+    drop_staking_base::state::provider_proposals::Config {
+        connection_id: "connection-0".to_string(),
+        port_id: "transfer".to_string(),
+        update_period: 100,
+        core_address: "core".to_string(),
+        proposal_votes_address: Some("proposal_votes".to_string()),
+        validators_set_address: "validators_set".to_string(),
+        init_proposal: 1,
+        proposals_prefetch: 5,
+        veto_spam_threshold: Decimal::from_atomics(1u64, 2).unwrap(),
+        puppeteer_address: Some("puppeteer".to_string()),
+        voting_policy,
+    }
+}
+
+fn proposal(
+    proposal_id: u64,
+    status: i32,
+) -> neutron_sdk::interchain_queries::v045::types::Proposal {
+    neutron_sdk::interchain_queries::v045::types::Proposal {
+        proposal_id,
+        proposal_type: None,
+        total_deposit: vec![],
+        status,
+        submit_time: None,
+        deposit_end_time: None,
+        voting_start_time: None,
+        voting_end_time: None,
+        final_tally_result: None,
+    }
+}
+
+fn weighted_options(
+    items: Vec<(drop_puppeteer_base::msg::VoteOption, Decimal)>,
+) -> Vec<drop_puppeteer_base::msg::WeightedVoteOption> {
+    items
+        .into_iter()
+        .map(|(option, weight)| drop_puppeteer_base::msg::WeightedVoteOption { option, weight })
+        .collect()
+}
+
+fn vote_hook(
+    succeeded: bool,
+    proposal_id: u64,
+) -> drop_staking_base::msg::provider_proposals::ExecuteMsg {
+    let request = neutron_sdk::sudo::msg::RequestPacket {
+        sequence: None,
+        source_port: None,
+        source_channel: None,
+        destination_port: None,
+        destination_channel: None,
+        data: None,
+        timeout_height: None,
+        timeout_timestamp: None,
+    };
+    let transaction = drop_puppeteer_base::msg::Transaction::Vote {
+        interchain_account_id: "DROP".to_string(),
+        proposal_id,
+        option: drop_puppeteer_base::msg::VoteOption::Abstain,
+    };
+    let msg = if succeeded {
+        drop_puppeteer_base::msg::ResponseHookMsg::Success(
+            drop_puppeteer_base::msg::ResponseHookSuccessMsg {
+                request_id: 1,
+                request,
+                transaction,
+                answers: vec![],
+                local_height: 1,
+                remote_height: 1,
+            },
+        )
+    } else {
+        drop_puppeteer_base::msg::ResponseHookMsg::Error(
+            drop_puppeteer_base::msg::ResponseHookErrorMsg {
+                request_id: 1,
+                transaction,
+                request,
+                details: "error".to_string(),
+            },
+        )
+    };
+    drop_staking_base::msg::provider_proposals::ExecuteMsg::PuppeteerHook(Box::new(msg))
+}
+
+#[test]
+fn cast_vote() {
+    use drop_puppeteer_base::msg::VoteOption::{Abstain, No, Yes};
+    let mut deps = mock_dependencies::<MockQuerier>();
+    drop_staking_base::state::provider_proposals::CONFIG
+        .save(
+            deps.as_mut().storage,
+            &voting_config(
+                drop_staking_base::state::provider_proposals::VotingPolicy::Operator {
+                    address: "operator".to_string(),
+                },
+            ),
+        )
+        .unwrap();
+    for (proposal_id, status) in [(1u64, 2), (2u64, 3)] {
+        drop_staking_base::state::provider_proposals::PROPOSALS
+            .save(
+                deps.as_mut().storage,
+                proposal_id,
+                &proposal(proposal_id, status),
+            )
+            .unwrap();
+    }
+
+    let error = crate::contract::execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("stranger", &[]),
+        drop_staking_base::msg::provider_proposals::ExecuteMsg::CastVote {
+            proposal_id: 1,
+            options: weighted_options(vec![(Yes, Decimal::one())]),
+        },
+    )
+    .unwrap_err();
+    assert_eq!(error, crate::error::ContractError::Unauthorized);
+
+    let error = crate::contract::execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("operator", &[]),
+        drop_staking_base::msg::provider_proposals::ExecuteMsg::CastVote {
+            proposal_id: 2,
+            options: weighted_options(vec![(Yes, Decimal::one())]),
+        },
+    )
+    .unwrap_err();
+    assert_eq!(
+        error,
+        crate::error::ContractError::ProposalNotInVotingPeriod { proposal_id: 2 }
+    );
+
+    let response = crate::contract::execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("operator", &[]),
+        drop_staking_base::msg::provider_proposals::ExecuteMsg::CastVote {
+            proposal_id: 1,
+            options: weighted_options(vec![(Yes, Decimal::one())]),
+        },
+    )
+    .unwrap();
+    assert_eq!(
+        response.messages,
+        vec![SubMsg::new(cosmwasm_std::WasmMsg::Execute {
+            contract_addr: "puppeteer".to_string(),
+            msg: to_json_binary(&drop_staking_base::msg::puppeteer::ExecuteMsg::Vote {
+                proposal_id: 1,
+                option: Yes,
+                reply_to: mock_env().contract.address.to_string(),
+            })
+            .unwrap(),
+            funds: vec![],
+        })]
+    );
+
+    let options = weighted_options(vec![
+        (No, Decimal::from_atomics(6u64, 1).unwrap()),
+        (Abstain, Decimal::from_atomics(4u64, 1).unwrap()),
+    ]);
+    let response = crate::contract::execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("operator", &[]),
+        drop_staking_base::msg::provider_proposals::ExecuteMsg::CastVote {
+            proposal_id: 1,
+            options: options.clone(),
+        },
+    )
+    .unwrap();
+    assert_eq!(
+        response.messages,
+        vec![SubMsg::new(cosmwasm_std::WasmMsg::Execute {
+            contract_addr: "puppeteer".to_string(),
+            msg: to_json_binary(
+                &drop_staking_base::msg::puppeteer::ExecuteMsg::WeightedVote {
+                    proposal_id: 1,
+                    options: options.clone(),
+                    reply_to: mock_env().contract.address.to_string(),
+                }
+            )
+            .unwrap(),
+            funds: vec![],
+        })]
+    );
+    assert_eq!(
+        drop_staking_base::state::provider_proposals::PROTOCOL_VOTES
+            .load(deps.as_ref().storage, 1)
+            .unwrap(),
+        drop_staking_base::state::provider_proposals::ProtocolVote {
+            options,
+            status: drop_staking_base::state::provider_proposals::ProtocolVoteStatus::Pending,
+        }
+    );
+}
+
+#[test]
+fn puppeteer_vote_hook() {
+    let mut deps = mock_dependencies::<MockQuerier>();
+    drop_staking_base::state::provider_proposals::CONFIG
+        .save(
+            deps.as_mut().storage,
+            &voting_config(drop_staking_base::state::provider_proposals::VotingPolicy::Abstain),
+        )
+        .unwrap();
+    for proposal_id in [1u64, 2u64] {
+        drop_staking_base::state::provider_proposals::PROTOCOL_VOTES
+            .save(
+                deps.as_mut().storage,
+                proposal_id,
+                &drop_staking_base::state::provider_proposals::ProtocolVote {
+                    options: weighted_options(vec![(
+                        drop_puppeteer_base::msg::VoteOption::Abstain,
+                        Decimal::one(),
+                    )]),
+                    status:
+                        drop_staking_base::state::provider_proposals::ProtocolVoteStatus::Pending,
+                },
+            )
+            .unwrap();
+    }
+
+    let error = crate::contract::execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("stranger", &[]),
+        vote_hook(true, 1),
+    )
+    .unwrap_err();
+    assert_eq!(error, crate::error::ContractError::Unauthorized);
+
+    crate::contract::execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("puppeteer", &[]),
+        vote_hook(true, 1),
+    )
+    .unwrap();
+    crate::contract::execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("puppeteer", &[]),
+        vote_hook(false, 2),
+    )
+    .unwrap();
+
+    assert_eq!(
+        drop_staking_base::state::provider_proposals::PROTOCOL_VOTES
+            .load(deps.as_ref().storage, 1)
+            .unwrap()
+            .status,
+        drop_staking_base::state::provider_proposals::ProtocolVoteStatus::Voted
+    );
+    assert!(
+        !drop_staking_base::state::provider_proposals::PROTOCOL_VOTES.has(deps.as_ref().storage, 2)
+    );
+}
+
+#[test]
+fn abstain_votes() {
+    let mut deps = mock_dependencies::<MockQuerier>();
+    let proposals = vec![proposal(1, 2), proposal(2, 2), proposal(3, 1)];
+    drop_staking_base::state::provider_proposals::PROTOCOL_VOTES
+        .save(
+            deps.as_mut().storage,
+            2,
+            &drop_staking_base::state::provider_proposals::ProtocolVote {
+                options: vec![],
+                status: drop_staking_base::state::provider_proposals::ProtocolVoteStatus::Voted,
+            },
+        )
+        .unwrap();
+
+    let msgs = crate::contract::abstain_votes_msgs(
+        deps.as_mut(),
+        &mock_env(),
+        &voting_config(drop_staking_base::state::provider_proposals::VotingPolicy::Disabled),
+        &proposals,
+    )
+    .unwrap();
+    assert!(msgs.is_empty());
+
+    let msgs = crate::contract::abstain_votes_msgs(
+        deps.as_mut(),
+        &mock_env(),
+        &voting_config(drop_staking_base::state::provider_proposals::VotingPolicy::Abstain),
+        &proposals,
+    )
+    .unwrap();
+    assert_eq!(
+        msgs,
+        vec![SubMsg::reply_on_error(
+            cosmwasm_std::WasmMsg::Execute {
+                contract_addr: "puppeteer".to_string(),
+                msg: to_json_binary(&drop_staking_base::msg::puppeteer::ExecuteMsg::Vote {
+                    proposal_id: 1,
+                    option: drop_puppeteer_base::msg::VoteOption::Abstain,
+                    reply_to: mock_env().contract.address.to_string(),
+                })
+                .unwrap(),
+                funds: vec![],
+            },
+            drop_staking_base::state::provider_proposals::ABSTAIN_VOTE_REPLY_ID_OFFSET + 1,
+        )]
+    );
+    assert_eq!(
+        drop_staking_base::state::provider_proposals::PROTOCOL_VOTES
+            .load(deps.as_ref().storage, 1)
+            .unwrap()
+            .status,
+        drop_staking_base::state::provider_proposals::ProtocolVoteStatus::Pending
+    );
+
+    let response = crate::contract::reply(
+        deps.as_mut().into_empty(),
+        mock_env(),
+        cosmwasm_std::Reply {
+            id: drop_staking_base::state::provider_proposals::ABSTAIN_VOTE_REPLY_ID_OFFSET + 1,
+            result: cosmwasm_std::SubMsgResult::Err("tx in progress".to_string()),
+        },
+    )
+    .unwrap();
+    assert_eq!(
+        response.events,
+        vec![
+            Event::new("crates.io:drop-staking__drop-provider-proposals-poc-reply-abstain_vote")
+                .add_attributes(vec![
+                    attr("action", "abstain_vote_failed"),
+                    attr("proposal_id", "1"),
+                    attr("error", "tx in progress"),
+                ])
+        ]
+    );
+    assert!(
+        !drop_staking_base::state::provider_proposals::PROTOCOL_VOTES.has(deps.as_ref().storage, 1)
+    );
+}
+
 // TODO: Add more tests
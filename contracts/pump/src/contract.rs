@@ -1,16 +1,24 @@
 use crate::error::{ContractError, ContractResult};
 use cosmos_sdk_proto::cosmos::base::abci::v1beta1::TxMsgData;
 use cosmos_sdk_proto::cosmos::base::v1beta1::Coin as ProtoCoin;
-use cosmos_sdk_proto::ibc::applications::transfer::v1::{MsgTransfer, MsgTransferResponse};
-use cosmwasm_std::{attr, to_json_binary, Addr, Coin, CosmosMsg, Deps, StdError};
+use cosmos_sdk_proto::ibc::applications::transfer::v1::MsgTransferResponse;
+use cosmwasm_std::{
+    attr, to_json_binary, Addr, Coin, CosmosMsg, Deps, Order, Reply, StdError, StdResult, SubMsg,
+    WasmMsg,
+};
 use cosmwasm_std::{Binary, DepsMut, Env, MessageInfo, Response};
 use drop_helpers::answer::response;
 use drop_helpers::ibc_fee::query_ibc_fee;
+use drop_helpers::ibc_transfer::{validate_memo, MsgTransfer, DEFAULT_MAX_MEMO_LENGTH};
+use drop_helpers::ica::IcaRecoveredHookMsg;
 use drop_staking_base::msg::pump::{
-    ExecuteMsg, InstantiateMsg, MigrateMsg, OpenAckVersion, QueryMsg, UpdateConfigMsg,
+    ExecuteMsg, InstantiateMsg, MigrateMsg, OpenAckVersion, QueryMsg, ReceiverExecuteMsg,
+    ResponseHookErrorMsg, ResponseHookMsg, ResponseHookSuccessMsg, UpdateConfigMsg,
+};
+use drop_staking_base::state::pump::{
+    Config, Delivery, CONFIG, DELIVERIES, DELIVERY_QUEUE, ICA, ICA_ID, PUSH_REPLY_ID,
 };
-use drop_staking_base::state::pump::{Config, CONFIG, ICA, ICA_ID};
-use neutron_sdk::bindings::msg::{IbcFee, NeutronMsg};
+use neutron_sdk::bindings::msg::{IbcFee, MsgSubmitTxResponse, NeutronMsg};
 use neutron_sdk::bindings::query::NeutronQuery;
 use neutron_sdk::bindings::types::ProtobufAny;
 use neutron_sdk::interchain_txs::helpers::decode_message_response;
@@ -54,6 +62,11 @@ pub fn instantiate(
                 .transpose()?,
             timeout: msg.timeout,
             local_denom: msg.local_denom,
+            ica_recovery: None,
+            dest_memo: None,
+            routes: vec![],
+            reply_to: None,
+            max_memo_length: DEFAULT_MAX_MEMO_LENGTH,
         },
     )?;
     Ok(response("instantiate", CONTRACT_NAME, attrs))
@@ -64,6 +77,7 @@ pub fn query(deps: Deps, _env: Env, msg: QueryMsg) -> ContractResult<Binary> {
     match msg {
         QueryMsg::Config {} => query_config(deps),
         QueryMsg::Ica {} => query_ica(deps),
+        QueryMsg::Routes {} => Ok(to_json_binary(&CONFIG.load(deps.storage)?.routes)?),
         QueryMsg::Ownership {} => {
             let ownership = cw_ownable::get_ownership(deps.storage)?;
             Ok(to_json_binary(&ownership)?)
@@ -160,6 +174,36 @@ fn execute_update_config(
     if let Some(local_denom) = new_config.local_denom {
         config.local_denom = local_denom;
     }
+    if let Some(ica_recovery) = new_config.ica_recovery {
+        if let Some(hook_receiver) = &ica_recovery.hook_receiver {
+            deps.api.addr_validate(hook_receiver)?;
+        }
+        config.ica_recovery = Some(ica_recovery);
+    }
+    if let Some(max_memo_length) = new_config.max_memo_length {
+        config.max_memo_length = max_memo_length;
+    }
+    if let Some(dest_memo) = new_config.dest_memo {
+        validate_memo(&dest_memo, config.max_memo_length)?;
+        config.dest_memo = Some(dest_memo).filter(|memo| !memo.is_empty());
+    }
+    if let Some(routes) = new_config.routes {
+        let mut denoms = std::collections::HashSet::new();
+        for route in &routes {
+            if !denoms.insert(route.denom.as_str()) {
+                return Err(ContractError::DuplicatedRoute {
+                    denom: route.denom.clone(),
+                });
+            }
+            if let Some(memo) = &route.memo {
+                validate_memo(memo, config.max_memo_length)?;
+            }
+        }
+        config.routes = routes;
+    }
+    if let Some(reply_to) = new_config.reply_to {
+        config.reply_to = Some(deps.api.addr_validate(&reply_to)?);
+    }
     CONFIG.save(deps.storage, &config)?;
     Ok(response("update_config", CONTRACT_NAME, attrs))
 }
@@ -192,6 +236,7 @@ fn execute_push(
 ) -> ContractResult<Response<NeutronMsg>> {
     let config = CONFIG.load(deps.storage)?;
     let mut messages = vec![];
+    let mut deliveries = vec![];
     let attrs = vec![
         attr("action", "push"),
         attr("connection_id", &config.connection_id),
@@ -205,37 +250,62 @@ fn execute_push(
         .dest_port
         .as_ref()
         .ok_or(ContractError::NoDestinationPort {})?;
-    let dst_channel = &config
-        .dest_channel
-        .as_ref()
-        .ok_or(ContractError::NoDestinationChannel {})?;
-    let dst_address = config
-        .dest_address
-        .as_ref()
-        .ok_or(ContractError::NoDestinationAddress {})?
-        .to_string();
     for coin in coins {
+        let (dst_channel, dst_address, memo) =
+            match config.routes.iter().find(|route| route.denom == coin.denom) {
+                Some(route) => (
+                    route.channel.to_string(),
+                    route.receiver.to_string(),
+                    route.memo.clone(),
+                ),
+                None => (
+                    config
+                        .dest_channel
+                        .clone()
+                        .ok_or(ContractError::NoDestinationChannel {})?,
+                    config
+                        .dest_address
+                        .as_ref()
+                        .ok_or(ContractError::NoDestinationAddress {})?
+                        .to_string(),
+                    config.dest_memo.clone(),
+                ),
+            };
         let msg = MsgTransfer {
             source_port: dst_port.to_string(),
-            source_channel: dst_channel.to_string(),
+            source_channel: dst_channel,
             token: Some(ProtoCoin {
-                denom: coin.denom,
+                denom: coin.denom.to_string(),
                 amount: coin.amount.to_string(),
             }),
             sender: ica.to_string(),
             receiver: dst_address.to_string(),
             timeout_height: None,
             timeout_timestamp,
+            memo: memo.unwrap_or_default(),
         };
-        messages.push(compose_msg(
+        let msg = compose_msg(
             &config,
             msg,
             &fee,
             "/ibc.applications.transfer.v1.MsgTransfer".to_string(),
             config.timeout.local,
-        )?);
+        )?;
+        // the outcome is tracked only when there is someone to report it to
+        if config.reply_to.is_some() {
+            deliveries.push(Delivery {
+                coin,
+                receiver: dst_address,
+            });
+            messages.push(SubMsg::reply_on_success(msg, PUSH_REPLY_ID));
+        } else {
+            messages.push(SubMsg::new(msg));
+        }
     }
-    Ok(response("push", CONTRACT_NAME, attrs).add_messages(messages))
+    if !deliveries.is_empty() {
+        DELIVERY_QUEUE.save(deps.storage, &deliveries)?;
+    }
+    Ok(response("push", CONTRACT_NAME, attrs).add_submessages(messages))
 }
 
 fn compose_msg<T: prost::Message>(
@@ -266,7 +336,11 @@ fn compose_msg<T: prost::Message>(
 }
 
 #[cfg_attr(not(feature = "library"), cosmwasm_std::entry_point)]
-pub fn sudo(deps: DepsMut<NeutronQuery>, env: Env, msg: SudoMsg) -> ContractResult<Response> {
+pub fn sudo(
+    deps: DepsMut<NeutronQuery>,
+    env: Env,
+    msg: SudoMsg,
+) -> ContractResult<Response<NeutronMsg>> {
     match msg {
         SudoMsg::Response { request, data } => sudo_response(deps, env, request, data),
         SudoMsg::Error { request, details } => sudo_error(deps, env, request, details),
@@ -297,23 +371,105 @@ pub fn sudo_open_ack(
     channel_id: String,
     _counterparty_channel_id: String,
     counterparty_version: String,
-) -> ContractResult<Response> {
+) -> ContractResult<Response<NeutronMsg>> {
     let parsed_version: Result<OpenAckVersion, _> =
         serde_json_wasm::from_str(counterparty_version.as_str());
     if let Ok(parsed_version) = parsed_version {
-        ICA.set_address(deps.storage, parsed_version.address, port_id, channel_id)?;
-        Ok(Response::default())
+        let recovered = ICA.set_address(
+            deps.storage,
+            parsed_version.address.clone(),
+            port_id.clone(),
+            channel_id.clone(),
+        )?;
+        let mut msgs = vec![];
+        if recovered {
+            if let Some(recovery) = CONFIG.load(deps.storage)?.ica_recovery {
+                msgs.extend(recovery.hook_msg(IcaRecoveredHookMsg {
+                    ica_id: ICA_ID.to_string(),
+                    ica_address: parsed_version.address,
+                    port_id,
+                    channel_id,
+                })?);
+            }
+        }
+        Ok(Response::default().add_messages(msgs))
     } else {
         Err(StdError::generic_err("can't parse version").into())
     }
 }
 
+/// Removes the delivery of the request and composes the hook to reply_to if any
+fn delivery_hook_msgs(
+    deps: DepsMut<NeutronQuery>,
+    request: &RequestPacket,
+    hook: impl FnOnce(u64, Delivery) -> ResponseHookMsg,
+) -> ContractResult<Vec<CosmosMsg<NeutronMsg>>> {
+    let (Some(seq_id), Some(channel_id)) = (request.sequence, request.source_channel.as_deref())
+    else {
+        return Ok(vec![]);
+    };
+    let Some(delivery) = DELIVERIES.may_load(deps.storage, (channel_id, seq_id))? else {
+        return Ok(vec![]);
+    };
+    DELIVERIES.remove(deps.storage, (channel_id, seq_id));
+    let Some(reply_to) = CONFIG.load(deps.storage)?.reply_to else {
+        return Ok(vec![]);
+    };
+    Ok(vec![CosmosMsg::Wasm(WasmMsg::Execute {
+        contract_addr: reply_to.to_string(),
+        msg: to_json_binary(&ReceiverExecuteMsg::PumpHook(hook(seq_id, delivery)))?,
+        funds: vec![],
+    })])
+}
+
+/// Removes every delivery left on the closed ICA channel and composes the failure hooks
+/// to reply_to if any. These packets never get their own sudo callback
+fn purge_channel_deliveries(
+    deps: DepsMut<NeutronQuery>,
+    request: &RequestPacket,
+    channel_id: &str,
+) -> ContractResult<(usize, Vec<CosmosMsg<NeutronMsg>>)> {
+    let deliveries = DELIVERIES
+        .prefix(channel_id)
+        .range(deps.storage, None, None, Order::Ascending)
+        .collect::<StdResult<Vec<_>>>()?;
+    for (seq_id, _) in &deliveries {
+        DELIVERIES.remove(deps.storage, (channel_id, *seq_id));
+    }
+    let Some(reply_to) = CONFIG.load(deps.storage)?.reply_to else {
+        return Ok((deliveries.len(), vec![]));
+    };
+    let msgs = deliveries
+        .iter()
+        .map(|(seq_id, delivery)| {
+            Ok(CosmosMsg::Wasm(WasmMsg::Execute {
+                contract_addr: reply_to.to_string(),
+                msg: to_json_binary(&ReceiverExecuteMsg::PumpHook(ResponseHookMsg::Error(
+                    ResponseHookErrorMsg {
+                        request_id: *seq_id,
+                        request: RequestPacket {
+                            sequence: Some(*seq_id),
+                            data: None,
+                            ..request.clone()
+                        },
+                        coin: delivery.coin.clone(),
+                        receiver: delivery.receiver.clone(),
+                        details: "channel closed".to_string(),
+                    },
+                )))?,
+                funds: vec![],
+            }))
+        })
+        .collect::<StdResult<Vec<_>>>()?;
+    Ok((deliveries.len(), msgs))
+}
+
 fn sudo_response(
     deps: DepsMut<NeutronQuery>,
-    _env: Env,
+    env: Env,
     request: RequestPacket,
     data: Binary,
-) -> ContractResult<Response> {
+) -> ContractResult<Response<NeutronMsg>> {
     let attrs = vec![
         attr("action", "sudo_response"),
         attr("request_id", request.sequence.unwrap_or(0).to_string()),
@@ -343,24 +499,64 @@ fn sudo_response(
             }
         };
     }
-    Ok(response("sudo-response", CONTRACT_NAME, attrs))
+    let msgs = delivery_hook_msgs(deps, &request, |request_id, delivery| {
+        ResponseHookMsg::Success(ResponseHookSuccessMsg {
+            request_id,
+            request: request.clone(),
+            coin: delivery.coin,
+            receiver: delivery.receiver,
+            local_height: env.block.height,
+        })
+    })?;
+    Ok(response("sudo-response", CONTRACT_NAME, attrs).add_messages(msgs))
 }
 
 fn sudo_timeout(
-    deps: DepsMut<NeutronQuery>,
-    _env: Env,
+    mut deps: DepsMut<NeutronQuery>,
+    env: Env,
     request: RequestPacket,
-) -> ContractResult<Response> {
-    let attrs = vec![
+) -> ContractResult<Response<NeutronMsg>> {
+    let mut attrs = vec![
         attr("action", "sudo_timeout"),
         attr("request_id", request.sequence.unwrap_or(0).to_string()),
     ];
-    ICA.set_timeout(deps.storage)?;
     deps.api.debug(&format!(
         "WASMDEBUG: sudo_timeout: request: {request:?}",
         request = request
     ));
-    Ok(response("sudo-timeout", CONTRACT_NAME, attrs))
+    let channel_id = request
+        .source_channel
+        .clone()
+        .ok_or_else(|| StdError::generic_err("source_channel not found"))?;
+    let mut msgs = delivery_hook_msgs(deps.branch(), &request, |request_id, delivery| {
+        ResponseHookMsg::Error(ResponseHookErrorMsg {
+            request_id,
+            request: request.clone(),
+            coin: delivery.coin,
+            receiver: delivery.receiver,
+            details: "timeout".to_string(),
+        })
+    })?;
+    let config = CONFIG.load(deps.storage)?;
+    if ICA.timeout(deps.storage, &channel_id)? {
+        // the ordered ICA channel is closed, the rest of its deliveries will never be acked
+        let (purged, purge_msgs) = purge_channel_deliveries(deps.branch(), &request, &channel_id)?;
+        if purged > 0 {
+            attrs.push(attr("purged_deliveries", purged.to_string()));
+        }
+        msgs.extend(purge_msgs);
+        if let Some(recovery) = &config.ica_recovery {
+            msgs.extend(ICA.recover(
+                deps.storage,
+                &deps.querier,
+                &env.contract.address,
+                config.connection_id,
+                ICA_ID,
+                recovery,
+            )?);
+        }
+    }
+    Ok(response("sudo-timeout", CONTRACT_NAME, attrs).add_messages(msgs))
 }
 
 fn sudo_error(
@@ -368,7 +564,7 @@ fn sudo_error(
     _env: Env,
     request: RequestPacket,
     details: String,
-) -> ContractResult<Response> {
+) -> ContractResult<Response<NeutronMsg>> {
     let attrs = vec![
         attr("action", "sudo_error"),
         attr("request_id", request.sequence.unwrap_or(0).to_string()),
@@ -382,7 +578,59 @@ fn sudo_error(
     let _seq_id = request
         .sequence
         .ok_or_else(|| StdError::generic_err("sequence not found"))?;
-    Ok(response("sudo-error", CONTRACT_NAME, attrs))
+    let msgs = delivery_hook_msgs(deps, &request, |request_id, delivery| {
+        ResponseHookMsg::Error(ResponseHookErrorMsg {
+            request_id,
+            request: request.clone(),
+            coin: delivery.coin,
+            receiver: delivery.receiver,
+            details,
+        })
+    })?;
+    Ok(response("sudo-error", CONTRACT_NAME, attrs).add_messages(msgs))
+}
+
+#[cfg_attr(not(feature = "library"), cosmwasm_std::entry_point)]
+pub fn reply(deps: DepsMut, _env: Env, msg: Reply) -> StdResult<Response> {
+    match msg.id {
+        PUSH_REPLY_ID => push_reply(deps, msg),
+        _ => Err(StdError::generic_err(format!(
+            "unsupported reply message id {}",
+            msg.id
+        ))),
+    }
+}
+
+fn push_reply(deps: DepsMut, msg: Reply) -> StdResult<Response> {
+    let resp: MsgSubmitTxResponse = serde_json_wasm::from_slice(
+        msg.result
+            .into_result()
+            .map_err(StdError::generic_err)?
+            .data
+            .ok_or_else(|| StdError::generic_err("no result"))?
+            .as_slice(),
+    )
+    .map_err(|e| StdError::generic_err(format!("failed to parse response: {e:?}")))?;
+    // replies come in the order of the submitted transfers
+    let mut queue = DELIVERY_QUEUE.load(deps.storage)?;
+    if queue.is_empty() {
+        return Err(StdError::generic_err("delivery queue is empty"));
+    }
+    let delivery = queue.remove(0);
+    DELIVERY_QUEUE.save(deps.storage, &queue)?;
+    DELIVERIES.save(
+        deps.storage,
+        (resp.channel.as_str(), resp.sequence_id),
+        &delivery,
+    )?;
+    Ok(response(
+        "reply-push",
+        CONTRACT_NAME,
+        [
+            attr("channel_id", resp.channel),
+            attr("seq_id", resp.sequence_id.to_string()),
+        ],
+    ))
 }
 
 #[cfg_attr(not(feature = "library"), cosmwasm_std::entry_point)]
@@ -398,6 +646,7 @@ pub fn migrate(
     if storage_version < version {
         cw2::set_contract_version(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
     }
+    ICA.init_last_address(deps.storage)?;
 
     Ok(Response::new())
 }
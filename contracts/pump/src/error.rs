@@ -44,6 +44,9 @@ pub enum ContractError {
     #[error("No destination channel is set")]
     NoDestinationChannel {},
 
+    #[error("Duplicated route for denom {denom}")]
+    DuplicatedRoute { denom: String },
+
     #[error("Refundee is not set")]
     RefundeeIsNotSet {},
 
@@ -1,20 +1,30 @@
 use crate::{
-    contract::{execute, instantiate},
+    contract::{execute, instantiate, query, reply, sudo},
     error::ContractError,
 };
 use cosmwasm_std::{
-    coins,
+    coins, from_json,
     testing::{mock_env, mock_info},
-    to_json_binary, Addr, BankMsg, Binary, Coin, CosmosMsg, Event, Response, SubMsg,
+    to_json_binary, to_json_vec, Addr, BankMsg, Binary, Coin, CosmosMsg, Event, Reply, Response,
+    SubMsg, SubMsgResponse, SubMsgResult, WasmMsg,
+};
+use drop_helpers::{
+    ica::{IcaRecoveredHookMsg, IcaRecoveryConfig, IcaRecoveryReceiverExecuteMsg, IcaState},
+    testing::mock_dependencies,
+};
+use drop_staking_base::{
+    msg::pump::OpenAckVersion,
+    state::pump::{
+        Config, Delivery, Route, CONFIG, DELIVERIES, DELIVERY_QUEUE, ICA, PUSH_REPLY_ID,
+    },
 };
-use drop_helpers::testing::mock_dependencies;
-use drop_staking_base::state::pump::{Config, CONFIG, ICA};
 use neutron_sdk::{
     bindings::{
-        msg::{IbcFee, NeutronMsg},
+        msg::{IbcFee, MsgSubmitTxResponse, NeutronMsg},
         types::ProtobufAny,
     },
     query::min_ibc_fee::MinIbcFeeResponse,
+    sudo::msg::{RequestPacket, SudoMsg},
 };
 use prost::Message;
 
@@ -30,6 +40,11 @@ fn get_default_config() -> Config {
             remote: 10u64,
         },
         local_denom: "local_denom".to_string(),
+        ica_recovery: None,
+        dest_memo: None,
+        routes: vec![],
+        reply_to: None,
+        max_memo_length: 256,
     }
 }
 
@@ -100,6 +115,16 @@ fn test_update_config() {
             remote: 1u64,
         }),
         local_denom: Some("new_local_denom".to_string()),
+        ica_recovery: None,
+        dest_memo: Some("memo".to_string()),
+        routes: Some(vec![Route {
+            denom: "reward_denom".to_string(),
+            channel: "rewards_channel".to_string(),
+            receiver: "rewards_manager".to_string(),
+            memo: None,
+        }]),
+        reply_to: Some("core".to_string()),
+        max_memo_length: Some(128),
     };
     let res = execute(
         deps.as_mut(),
@@ -116,7 +141,7 @@ fn test_update_config() {
             "crates.io:drop-neutron-contracts__drop-pump-update_config"
         ).add_attributes(vec![
             ("action","update_config"),
-            ("new_config", "UpdateConfigMsg { dest_address: Some(\"new_dest_address\"), dest_channel: Some(\"new_dest_channel\"), dest_port: Some(\"new_dest_port\"), connection_id: Some(\"new_connection\"), refundee: Some(\"new_refundee\"), timeout: Some(PumpTimeout { local: Some(1), remote: 1 }), local_denom: Some(\"new_local_denom\") }")
+            ("new_config", "UpdateConfigMsg { dest_address: Some(\"new_dest_address\"), dest_channel: Some(\"new_dest_channel\"), dest_port: Some(\"new_dest_port\"), connection_id: Some(\"new_connection\"), refundee: Some(\"new_refundee\"), timeout: Some(PumpTimeout { local: Some(1), remote: 1 }), local_denom: Some(\"new_local_denom\"), ica_recovery: None, dest_memo: Some(\"memo\"), routes: Some([Route { denom: \"reward_denom\", channel: \"rewards_channel\", receiver: \"rewards_manager\", memo: None }]), reply_to: Some(\"core\"), max_memo_length: Some(128) }")
         ]))
     );
     let config = CONFIG.load(deps.as_ref().storage).unwrap();
@@ -133,6 +158,16 @@ fn test_update_config() {
                 remote: 1u64,
             },
             local_denom: "new_local_denom".to_string(),
+            ica_recovery: None,
+            dest_memo: Some("memo".to_string()),
+            routes: vec![Route {
+                denom: "reward_denom".to_string(),
+                channel: "rewards_channel".to_string(),
+                receiver: "rewards_manager".to_string(),
+                memo: None,
+            }],
+            reply_to: Some(Addr::unchecked("core")),
+            max_memo_length: 128,
         }
     );
 }
@@ -395,3 +430,633 @@ fn test_push() {
             )))
     );
 }
+
+#[test]
+fn test_push_with_memo() {
+    let mut deps = mock_dependencies(&[]);
+    deps.querier.add_custom_query_response(|_| {
+        to_json_binary(&MinIbcFeeResponse {
+            min_fee: IbcFee {
+                recv_fee: vec![],
+                ack_fee: coins(100, "local_denom"),
+                timeout_fee: coins(200, "local_denom"),
+            },
+        })
+        .unwrap()
+    });
+    ICA.set_address(deps.as_mut().storage, "some", "port", "channel")
+        .unwrap();
+    let memo = r#"{"wasm":{"contract":"dest_address","msg":{"receive":{}}}}"#;
+    CONFIG
+        .save(
+            deps.as_mut().storage,
+            &Config {
+                dest_memo: Some(memo.to_string()),
+                ..get_default_config()
+            },
+        )
+        .unwrap();
+    let env = mock_env();
+    let res = execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info("nobody", &[]),
+        drop_staking_base::msg::pump::ExecuteMsg::Push {
+            coins: vec![Coin::new(100u128, "remote_denom")],
+        },
+    )
+    .unwrap();
+    let msg = drop_helpers::ibc_transfer::MsgTransfer {
+        source_port: "dest_port".to_string(),
+        source_channel: "dest_channel".to_string(),
+        token: Some(cosmos_sdk_proto::cosmos::base::v1beta1::Coin {
+            denom: "remote_denom".to_string(),
+            amount: "100".to_string(),
+        }),
+        sender: "some".to_string(),
+        receiver: "dest_address".to_string(),
+        timeout_height: None,
+        timeout_timestamp: env.block.time.plus_seconds(10).nanos(),
+        memo: memo.to_string(),
+    };
+    assert_eq!(
+        res.messages,
+        vec![SubMsg::new(CosmosMsg::Custom(NeutronMsg::submit_tx(
+            "connection".to_string(),
+            "drop_PUMP".to_string(),
+            vec![ProtobufAny {
+                type_url: "/ibc.applications.transfer.v1.MsgTransfer".to_string(),
+                value: Binary::from(msg.encode_to_vec()),
+            }],
+            "".to_string(),
+            10u64,
+            IbcFee {
+                recv_fee: vec![],
+                ack_fee: coins(100, "local_denom"),
+                timeout_fee: coins(200, "local_denom")
+            }
+        )))]
+    );
+}
+
+#[test]
+fn test_update_config_memo_too_long() {
+    let mut deps = mock_dependencies(&[]);
+    CONFIG
+        .save(deps.as_mut().storage, &get_default_config())
+        .unwrap();
+    let deps_mut = deps.as_mut();
+    cw_ownable::initialize_owner(deps_mut.storage, deps_mut.api, Some("owner")).unwrap();
+    let err = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("owner", &[]),
+        drop_staking_base::msg::pump::ExecuteMsg::UpdateConfig {
+            new_config: Box::new(drop_staking_base::msg::pump::UpdateConfigMsg {
+                dest_address: None,
+                dest_channel: None,
+                dest_port: None,
+                connection_id: None,
+                refundee: None,
+                timeout: None,
+                local_denom: None,
+                ica_recovery: None,
+                dest_memo: Some("a".repeat(257)),
+                routes: None,
+                reply_to: None,
+                max_memo_length: None,
+            }),
+        },
+    )
+    .unwrap_err();
+    assert_eq!(
+        err,
+        ContractError::Std(cosmwasm_std::StdError::generic_err(
+            "Memo is too long: 257 > 256"
+        ))
+    );
+}
+
+#[test]
+fn test_update_config_duplicated_route() {
+    let mut deps = mock_dependencies(&[]);
+    CONFIG
+        .save(deps.as_mut().storage, &get_default_config())
+        .unwrap();
+    let deps_mut = deps.as_mut();
+    cw_ownable::initialize_owner(deps_mut.storage, deps_mut.api, Some("owner")).unwrap();
+    let route = Route {
+        denom: "reward_denom".to_string(),
+        channel: "rewards_channel".to_string(),
+        receiver: "rewards_manager".to_string(),
+        memo: None,
+    };
+    let err = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("owner", &[]),
+        drop_staking_base::msg::pump::ExecuteMsg::UpdateConfig {
+            new_config: Box::new(drop_staking_base::msg::pump::UpdateConfigMsg {
+                dest_address: None,
+                dest_channel: None,
+                dest_port: None,
+                connection_id: None,
+                refundee: None,
+                timeout: None,
+                local_denom: None,
+                ica_recovery: None,
+                dest_memo: None,
+                routes: Some(vec![route.clone(), route]),
+                reply_to: None,
+                max_memo_length: None,
+            }),
+        },
+    )
+    .unwrap_err();
+    assert_eq!(
+        err,
+        ContractError::DuplicatedRoute {
+            denom: "reward_denom".to_string()
+        }
+    );
+}
+
+#[test]
+fn test_push_routes() {
+    let mut deps = mock_dependencies(&[]);
+    deps.querier.add_custom_query_response(|_| {
+        to_json_binary(&MinIbcFeeResponse {
+            min_fee: IbcFee {
+                recv_fee: vec![],
+                ack_fee: coins(100, "local_denom"),
+                timeout_fee: coins(200, "local_denom"),
+            },
+        })
+        .unwrap()
+    });
+    ICA.set_address(deps.as_mut().storage, "some", "port", "channel")
+        .unwrap();
+    let forward_memo =
+        r#"{"forward":{"receiver":"rewards_manager","port":"transfer","channel":"channel-1"}}"#;
+    let routes = vec![Route {
+        denom: "reward_denom".to_string(),
+        channel: "hub_channel".to_string(),
+        receiver: "hub_address".to_string(),
+        memo: Some(forward_memo.to_string()),
+    }];
+    CONFIG
+        .save(
+            deps.as_mut().storage,
+            &Config {
+                routes: routes.clone(),
+                ..get_default_config()
+            },
+        )
+        .unwrap();
+    let queried: Vec<Route> = from_json(
+        query(
+            deps.as_ref().into_empty(),
+            mock_env(),
+            drop_staking_base::msg::pump::QueryMsg::Routes {},
+        )
+        .unwrap(),
+    )
+    .unwrap();
+    assert_eq!(queried, routes);
+    let env = mock_env();
+    let res = execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info("nobody", &[]),
+        drop_staking_base::msg::pump::ExecuteMsg::Push {
+            coins: vec![
+                Coin::new(100u128, "reward_denom"),
+                Coin::new(200u128, "remote_denom"),
+            ],
+        },
+    )
+    .unwrap();
+    let transfer = |denom: &str, amount: &str, channel: &str, receiver: &str, memo: &str| {
+        SubMsg::new(CosmosMsg::Custom(NeutronMsg::submit_tx(
+            "connection".to_string(),
+            "drop_PUMP".to_string(),
+            vec![ProtobufAny {
+                type_url: "/ibc.applications.transfer.v1.MsgTransfer".to_string(),
+                value: Binary::from(
+                    drop_helpers::ibc_transfer::MsgTransfer {
+                        source_port: "dest_port".to_string(),
+                        source_channel: channel.to_string(),
+                        token: Some(cosmos_sdk_proto::cosmos::base::v1beta1::Coin {
+                            denom: denom.to_string(),
+                            amount: amount.to_string(),
+                        }),
+                        sender: "some".to_string(),
+                        receiver: receiver.to_string(),
+                        timeout_height: None,
+                        timeout_timestamp: env.block.time.plus_seconds(10).nanos(),
+                        memo: memo.to_string(),
+                    }
+                    .encode_to_vec(),
+                ),
+            }],
+            "".to_string(),
+            10u64,
+            IbcFee {
+                recv_fee: vec![],
+                ack_fee: coins(100, "local_denom"),
+                timeout_fee: coins(200, "local_denom"),
+            },
+        )))
+    };
+    assert_eq!(
+        res.messages,
+        vec![
+            transfer(
+                "reward_denom",
+                "100",
+                "hub_channel",
+                "hub_address",
+                forward_memo
+            ),
+            transfer("remote_denom", "200", "dest_channel", "dest_address", ""),
+        ]
+    );
+}
+
+#[test]
+fn test_push_delivery_hooks() {
+    let mut deps = mock_dependencies(&[]);
+    deps.querier.add_custom_query_response(|_| {
+        to_json_binary(&MinIbcFeeResponse {
+            min_fee: IbcFee {
+                recv_fee: vec![],
+                ack_fee: coins(100, "local_denom"),
+                timeout_fee: coins(200, "local_denom"),
+            },
+        })
+        .unwrap()
+    });
+    ICA.set_address(deps.as_mut().storage, "some", "port", "channel")
+        .unwrap();
+    CONFIG
+        .save(
+            deps.as_mut().storage,
+            &Config {
+                reply_to: Some(Addr::unchecked("core")),
+                ..get_default_config()
+            },
+        )
+        .unwrap();
+    let res = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("nobody", &[]),
+        drop_staking_base::msg::pump::ExecuteMsg::Push {
+            coins: vec![
+                Coin::new(100u128, "remote_denom"),
+                Coin::new(200u128, "remote_denom"),
+            ],
+        },
+    )
+    .unwrap();
+    assert_eq!(res.messages.len(), 2);
+    assert!(res
+        .messages
+        .iter()
+        .all(|msg| msg.id == PUSH_REPLY_ID && msg.reply_on == cosmwasm_std::ReplyOn::Success));
+    let delivery = |amount: u128| Delivery {
+        coin: Coin::new(amount, "remote_denom"),
+        receiver: "dest_address".to_string(),
+    };
+    assert_eq!(
+        DELIVERY_QUEUE.load(deps.as_ref().storage).unwrap(),
+        vec![delivery(100), delivery(200)]
+    );
+    for sequence_id in [5u64, 6] {
+        reply(
+            deps.as_mut().into_empty(),
+            mock_env(),
+            Reply {
+                id: PUSH_REPLY_ID,
+                result: SubMsgResult::Ok(SubMsgResponse {
+                    events: vec![],
+                    data: Some(
+                        to_json_binary(&MsgSubmitTxResponse {
+                            sequence_id,
+                            channel: "channel".to_string(),
+                        })
+                        .unwrap(),
+                    ),
+                }),
+            },
+        )
+        .unwrap();
+    }
+    assert!(DELIVERY_QUEUE
+        .load(deps.as_ref().storage)
+        .unwrap()
+        .is_empty());
+    assert_eq!(
+        DELIVERIES
+            .load(deps.as_ref().storage, ("channel", 5))
+            .unwrap(),
+        delivery(100)
+    );
+
+    let request = |sequence: u64| RequestPacket {
+        sequence: Some(sequence),
+        ..get_timeout_request("channel")
+    };
+    let env = mock_env();
+    let res = sudo(
+        deps.as_mut(),
+        env.clone(),
+        SudoMsg::Response {
+            request: request(5),
+            data: Binary::from(
+                cosmos_sdk_proto::cosmos::base::abci::v1beta1::TxMsgData::default().encode_to_vec(),
+            ),
+        },
+    )
+    .unwrap();
+    assert_eq!(
+        res.messages,
+        vec![SubMsg::new(CosmosMsg::Wasm(WasmMsg::Execute {
+            contract_addr: "core".to_string(),
+            msg: to_json_binary(&drop_staking_base::msg::pump::ReceiverExecuteMsg::PumpHook(
+                drop_staking_base::msg::pump::ResponseHookMsg::Success(
+                    drop_staking_base::msg::pump::ResponseHookSuccessMsg {
+                        request_id: 5,
+                        request: request(5),
+                        coin: Coin::new(100u128, "remote_denom"),
+                        receiver: "dest_address".to_string(),
+                        local_height: env.block.height,
+                    }
+                )
+            ))
+            .unwrap(),
+            funds: vec![],
+        }))]
+    );
+    assert!(!DELIVERIES.has(deps.as_ref().storage, ("channel", 5)));
+
+    let res = sudo(
+        deps.as_mut(),
+        env,
+        SudoMsg::Error {
+            request: request(6),
+            details: "failed".to_string(),
+        },
+    )
+    .unwrap();
+    assert_eq!(
+        res.messages,
+        vec![SubMsg::new(CosmosMsg::Wasm(WasmMsg::Execute {
+            contract_addr: "core".to_string(),
+            msg: to_json_binary(&drop_staking_base::msg::pump::ReceiverExecuteMsg::PumpHook(
+                drop_staking_base::msg::pump::ResponseHookMsg::Error(
+                    drop_staking_base::msg::pump::ResponseHookErrorMsg {
+                        request_id: 6,
+                        request: request(6),
+                        coin: Coin::new(200u128, "remote_denom"),
+                        receiver: "dest_address".to_string(),
+                        details: "failed".to_string(),
+                    }
+                )
+            ))
+            .unwrap(),
+            funds: vec![],
+        }))]
+    );
+    assert!(!DELIVERIES.has(deps.as_ref().storage, ("channel", 6)));
+}
+
+fn get_timeout_request(channel_id: &str) -> RequestPacket {
+    RequestPacket {
+        sequence: Some(1u64),
+        source_port: Some("port".to_string()),
+        source_channel: Some(channel_id.to_string()),
+        destination_port: Some("destination_port".to_string()),
+        destination_channel: Some("destination_channel".to_string()),
+        data: None,
+        timeout_height: None,
+        timeout_timestamp: None,
+    }
+}
+
+#[test]
+fn test_sudo_timeout_recovers_ica() {
+    let mut deps = mock_dependencies(&coins(1000, "untrn"));
+    let mut config = get_default_config();
+    config.ica_recovery = Some(IcaRecoveryConfig {
+        register_fee: Coin::new(1000, "untrn"),
+        hook_receiver: Some("core".to_string()),
+    });
+    CONFIG.save(deps.as_mut().storage, &config).unwrap();
+    ICA.set_address(deps.as_mut().storage, "ica_address", "port", "channel")
+        .unwrap();
+    // timeout of a packet sent over another channel doesn't affect the ICA
+    let res = sudo(
+        deps.as_mut(),
+        mock_env(),
+        SudoMsg::Timeout {
+            request: get_timeout_request("transfer_channel"),
+        },
+    )
+    .unwrap();
+    assert!(res.messages.is_empty());
+    assert_eq!(
+        ICA.load(deps.as_ref().storage).unwrap(),
+        IcaState::Registered {
+            ica_address: "ica_address".to_string(),
+            port_id: "port".to_string(),
+            channel_id: "channel".to_string(),
+        }
+    );
+    let res = sudo(
+        deps.as_mut(),
+        mock_env(),
+        SudoMsg::Timeout {
+            request: get_timeout_request("channel"),
+        },
+    )
+    .unwrap();
+    assert_eq!(
+        res.messages,
+        vec![SubMsg::new(CosmosMsg::Custom(
+            NeutronMsg::register_interchain_account(
+                "connection".to_string(),
+                "drop_PUMP".to_string(),
+                Some(coins(1000, "untrn")),
+            )
+        ))]
+    );
+    assert_eq!(
+        ICA.load(deps.as_ref().storage).unwrap(),
+        IcaState::InProgress
+    );
+    let res = sudo(
+        deps.as_mut(),
+        mock_env(),
+        SudoMsg::OpenAck {
+            port_id: "port".to_string(),
+            channel_id: "channel-1".to_string(),
+            counterparty_channel_id: "channel-2".to_string(),
+            counterparty_version: serde_json_wasm::to_string(&OpenAckVersion {
+                version: "ics27-1".to_string(),
+                controller_connection_id: "connection".to_string(),
+                host_connection_id: "host_connection".to_string(),
+                address: "ica_address".to_string(),
+                encoding: "proto3".to_string(),
+                tx_type: "sdk_multi_msg".to_string(),
+            })
+            .unwrap(),
+        },
+    )
+    .unwrap();
+    assert_eq!(
+        res,
+        Response::new().add_message(CosmosMsg::Wasm(WasmMsg::Execute {
+            contract_addr: "core".to_string(),
+            msg: to_json_binary(&IcaRecoveryReceiverExecuteMsg::IcaRecoveredHook(
+                IcaRecoveredHookMsg {
+                    ica_id: "drop_PUMP".to_string(),
+                    ica_address: "ica_address".to_string(),
+                    port_id: "port".to_string(),
+                    channel_id: "channel-1".to_string(),
+                }
+            ))
+            .unwrap(),
+            funds: vec![],
+        }))
+    );
+}
+
+#[test]
+fn test_sudo_timeout_purges_channel_deliveries() {
+    let mut deps = mock_dependencies(&[]);
+    CONFIG
+        .save(
+            deps.as_mut().storage,
+            &Config {
+                reply_to: Some(Addr::unchecked("core")),
+                ..get_default_config()
+            },
+        )
+        .unwrap();
+    ICA.set_address(deps.as_mut().storage, "ica_address", "port", "channel")
+        .unwrap();
+    for (seq_id, amount) in [(1u64, 100u128), (2u64, 200u128)] {
+        DELIVERIES
+            .save(
+                deps.as_mut().storage,
+                ("channel", seq_id),
+                &Delivery {
+                    coin: Coin::new(amount, "remote_denom"),
+                    receiver: "dest_address".to_string(),
+                },
+            )
+            .unwrap();
+    }
+    let request = get_timeout_request("channel");
+    let res = sudo(
+        deps.as_mut(),
+        mock_env(),
+        SudoMsg::Timeout {
+            request: request.clone(),
+        },
+    )
+    .unwrap();
+    let hook = |request_id: u64, amount: u128, details: &str| {
+        SubMsg::new(CosmosMsg::Wasm(WasmMsg::Execute {
+            contract_addr: "core".to_string(),
+            msg: to_json_binary(&drop_staking_base::msg::pump::ReceiverExecuteMsg::PumpHook(
+                drop_staking_base::msg::pump::ResponseHookMsg::Error(
+                    drop_staking_base::msg::pump::ResponseHookErrorMsg {
+                        request_id,
+                        request: RequestPacket {
+                            sequence: Some(request_id),
+                            ..request.clone()
+                        },
+                        coin: Coin::new(amount, "remote_denom"),
+                        receiver: "dest_address".to_string(),
+                        details: details.to_string(),
+                    },
+                ),
+            ))
+            .unwrap(),
+            funds: vec![],
+        }))
+    };
+    assert_eq!(
+        res.messages,
+        vec![hook(1, 100, "timeout"), hook(2, 200, "channel closed")]
+    );
+    assert_eq!(
+        res.events[0].attributes.last().unwrap(),
+        ("purged_deliveries", "1")
+    );
+    assert!(DELIVERIES
+        .prefix("channel")
+        .keys(
+            deps.as_ref().storage,
+            None,
+            None,
+            cosmwasm_std::Order::Ascending
+        )
+        .next()
+        .is_none());
+}
+
+#[test]
+fn test_migrate_remembers_ica_address() {
+    let mut deps = mock_dependencies(&[]);
+    let mut config = get_default_config();
+    config.ica_recovery = Some(IcaRecoveryConfig {
+        register_fee: Coin::new(1000, "untrn"),
+        hook_receiver: Some("core".to_string()),
+    });
+    CONFIG.save(deps.as_mut().storage, &config).unwrap();
+    cw2::set_contract_version(
+        deps.as_mut().storage,
+        "crates.io:drop-staking__drop-pump",
+        "1.0.0",
+    )
+    .unwrap();
+    // ICA registered before the recovery flow has no last address
+    deps.as_mut().storage.set(
+        b"ica",
+        &to_json_vec(&IcaState::Registered {
+            ica_address: "ica_address".to_string(),
+            port_id: "port".to_string(),
+            channel_id: "channel".to_string(),
+        })
+        .unwrap(),
+    );
+    crate::contract::migrate(
+        deps.as_mut(),
+        mock_env(),
+        drop_staking_base::msg::pump::MigrateMsg {},
+    )
+    .unwrap();
+    let res = sudo(
+        deps.as_mut(),
+        mock_env(),
+        SudoMsg::OpenAck {
+            port_id: "port".to_string(),
+            channel_id: "channel-1".to_string(),
+            counterparty_channel_id: "channel-2".to_string(),
+            counterparty_version: serde_json_wasm::to_string(&OpenAckVersion {
+                version: "ics27-1".to_string(),
+                controller_connection_id: "connection".to_string(),
+                host_connection_id: "host_connection".to_string(),
+                address: "ica_address".to_string(),
+                encoding: "proto3".to_string(),
+                tx_type: "sdk_multi_msg".to_string(),
+            })
+            .unwrap(),
+        },
+    )
+    .unwrap();
+    assert_eq!(res.messages.len(), 1);
+}
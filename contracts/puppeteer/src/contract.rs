@@ -13,49 +13,64 @@ use cosmos_sdk_proto::cosmos::{
     authz::v1beta1::{GenericAuthorization, Grant, MsgGrant, MsgGrantResponse},
     bank::v1beta1::{MsgSend, MsgSendResponse},
     base::{abci::v1beta1::TxMsgData, v1beta1::Coin},
+    gov::v1beta1::{
+        MsgVote, MsgVoteResponse, MsgVoteWeighted, MsgVoteWeightedResponse,
+        WeightedVoteOption as ProtoWeightedVoteOption,
+    },
+    staking::v1beta1::MsgDelegate,
 };
 use cosmos_sdk_proto::{
     cosmos::{authz::v1beta1::MsgExec, distribution::v1beta1::MsgSetWithdrawAddress},
     traits::MessageExt,
 };
 use cosmwasm_std::{
-    attr, ensure_eq, to_json_binary, Addr, Attribute, CosmosMsg, Deps, Order, Reply, StdError,
-    SubMsg, Timestamp, Uint128, WasmMsg,
+    attr, coins, ensure, ensure_eq, to_json_binary, Addr, Attribute, BankMsg, Coins, CosmosMsg,
+    Decimal, Deps, Order, Reply, StdError, Storage, SubMsg, Timestamp, Uint128, WasmMsg,
 };
 use cosmwasm_std::{Binary, DepsMut, Env, MessageInfo, Response, StdResult};
+use cw_storage_plus::{Bound, Item};
 use drop_helpers::{
     answer::response,
     ibc_client_state::query_client_state,
     ibc_fee::query_ibc_fee,
+    ibc_transfer::{validate_memo, DEFAULT_MAX_MEMO_LENGTH},
+    ica::IcaState,
     icq::{
         new_delegations_and_balance_query_msg, new_multiple_balances_query_msg,
         update_multiple_balances_query_msg,
     },
     interchain::prepare_any_msg,
     validation::validate_addresses,
+    version::version_to_u32,
 };
 use drop_puppeteer_base::{
     error::{ContractError, ContractResult},
     msg::{
         IBCTransferReason, QueryMsg, ReceiverExecuteMsg, ResponseAnswer, ResponseHookErrorMsg,
-        ResponseHookMsg, ResponseHookSuccessMsg, Transaction, TransferReadyBatchesMsg,
+        ResponseHookMsg, ResponseHookSuccessMsg, Transaction, TransferReadyBatchesMsg, VoteOption,
+        WeightedVoteOption,
     },
     proto::MsgIBCTransfer,
     state::{
-        Delegations, PuppeteerBase, RedeemShareItem, ReplyMsg, TxState, TxStateStatus,
+        Delegations, PuppeteerBase, RedeemShareItem, ReplyMsg, TxResult, TxState, TxStateStatus,
         UnbondingDelegation, ICA_ID, LOCAL_DENOM,
     },
 };
 use drop_staking_base::{
     msg::puppeteer::{
-        BalancesResponse, DelegationsResponse, ExecuteMsg, InstantiateMsg, MigrateMsg, QueryExtMsg,
+        BalancesResponse, DelegationsResponse, DelegationsStatusResponse, ExecuteMsg,
+        InstantiateMsg, MigrateMsg, PendingDelegationsSnapshot, QueryExtMsg, RedelegationEntries,
+    },
+    state::puppeteer::{
+        Config, ConfigOptional, KVQueryType, QueryPeriods, NON_NATIVE_REWARD_BALANCES,
+        REDELEGATIONS,
     },
-    state::puppeteer::{Config, ConfigOptional, KVQueryType, NON_NATIVE_REWARD_BALANCES},
 };
 use neutron_sdk::{
     bindings::{msg::NeutronMsg, query::NeutronQuery, types::ProtobufAny},
-    interchain_queries::v045::{
-        new_register_delegator_unbonding_delegations_query_msg, types::Balances,
+    interchain_queries::{
+        get_registered_query,
+        v045::{new_register_delegator_unbonding_delegations_query_msg, types::Balances},
     },
     interchain_txs::helpers::decode_message_response,
     sudo::msg::{RequestPacket, RequestPacketTimeoutHeight, SudoMsg},
@@ -100,6 +115,11 @@ pub fn instantiate(
         delegations_queries_chunk_size: msg
             .delegations_queries_chunk_size
             .unwrap_or(DEFAULT_DELEGATIONS_QUERIES_CHUNK_SIZE),
+        ica_recovery: None,
+        query_periods: QueryPeriods::default(),
+        query_deposit_receiver: None,
+        delegations_max_height_skew: 0,
+        max_memo_length: DEFAULT_MAX_MEMO_LENGTH,
     };
     Puppeteer::default().instantiate(deps, config, owner)
 }
@@ -123,6 +143,8 @@ pub fn query(
                     .collect::<StdResult<Vec<_>>>()?,
             )
             .map_err(ContractError::Std),
+            QueryExtMsg::Redelegations {} => query_redelegations(deps, env),
+            QueryExtMsg::DelegationsStatus {} => query_delegations_status(deps, env),
             QueryExtMsg::Ownership {} => {
                 let owner = cw_ownable::get_ownership(deps.storage)?;
                 to_json_binary(&owner).map_err(ContractError::Std)
@@ -142,6 +164,31 @@ fn query_kv_query_ids(deps: Deps<NeutronQuery>) -> ContractResult<Binary> {
     Ok(to_json_binary(&kv_query_ids?)?)
 }
 
+fn query_redelegations(deps: Deps<NeutronQuery>, env: Env) -> ContractResult<Binary> {
+    let now = env.block.time.seconds();
+    let redelegations = REDELEGATIONS
+        .range(deps.storage, None, None, Order::Ascending)
+        .map(|res| {
+            res.map(
+                |((validator_from, validator_to), completion_times)| RedelegationEntries {
+                    validator_from,
+                    validator_to,
+                    completion_times: completion_times
+                        .into_iter()
+                        .filter(|completion_time| *completion_time > now)
+                        .collect(),
+                },
+            )
+        })
+        .filter(|res| {
+            res.as_ref()
+                .map_or(true, |entries| !entries.completion_times.is_empty())
+        })
+        .collect::<StdResult<Vec<_>>>()?;
+
+    Ok(to_json_binary(&redelegations)?)
+}
+
 fn query_delegations(deps: Deps<NeutronQuery>) -> ContractResult<Binary> {
     let puppeteer_base = Puppeteer::default();
     match puppeteer_base
@@ -171,6 +218,68 @@ fn query_delegations(deps: Deps<NeutronQuery>) -> ContractResult<Binary> {
     .map_err(ContractError::Std)
 }
 
+fn query_delegations_status(deps: Deps<NeutronQuery>, env: Env) -> ContractResult<Binary> {
+    let puppeteer_base = Puppeteer::default();
+    let mut chunk_ids = puppeteer_base
+        .delegations_and_balances_query_id_chunk
+        .range(deps.storage, None, None, Order::Ascending)
+        .map(|res| res.map(|(_query_id, chunk_id)| chunk_id))
+        .collect::<StdResult<Vec<_>>>()?;
+    chunk_ids.sort();
+    let chunk_heights = chunk_ids
+        .iter()
+        .map(|chunk_id| {
+            Ok((
+                *chunk_id,
+                puppeteer_base
+                    .delegations_and_balances_chunk_heights
+                    .may_load(deps.storage, *chunk_id)?
+                    .unwrap_or_default(),
+            ))
+        })
+        .collect::<StdResult<Vec<_>>>()?;
+    let last_complete_key = puppeteer_base
+        .last_complete_delegations_and_balances_key
+        .may_load(deps.storage)?;
+    let last_complete_age = match last_complete_key {
+        Some(key) => Some(
+            env.block.time.seconds()
+                - puppeteer_base
+                    .delegations_and_balances
+                    .load(deps.storage, &key)?
+                    .timestamp
+                    .seconds(),
+        ),
+        None => None,
+    };
+    let pending = puppeteer_base
+        .delegations_and_balances
+        .range(
+            deps.storage,
+            last_complete_key.map(|key| Bound::ExclusiveRaw(key.to_be_bytes().to_vec())),
+            None,
+            Order::Ascending,
+        )
+        .map(|res| {
+            res.map(|(remote_height, state)| PendingDelegationsSnapshot {
+                remote_height,
+                missing_chunks: chunk_ids
+                    .iter()
+                    .filter(|chunk_id| !state.collected_chunks.contains(chunk_id))
+                    .copied()
+                    .collect(),
+                collected_chunks: state.collected_chunks,
+            })
+        })
+        .collect::<StdResult<Vec<_>>>()?;
+    Ok(to_json_binary(&DelegationsStatusResponse {
+        chunk_heights,
+        last_complete_remote_height: last_complete_key,
+        last_complete_age,
+        pending,
+    })?)
+}
+
 fn query_balances(deps: Deps<NeutronQuery>) -> ContractResult<Binary> {
     let puppeteer_base = Puppeteer::default();
     match puppeteer_base
@@ -220,6 +329,7 @@ pub fn execute(
 ) -> ContractResult<Response<NeutronMsg>> {
     let puppeteer_base = Puppeteer::default();
     match msg {
+        ExecuteMsg::Delegate { items, reply_to } => execute_delegate(deps, info, items, reply_to),
         ExecuteMsg::Undelegate {
             items,
             batch_id,
@@ -255,11 +365,32 @@ pub fn execute(
         ExecuteMsg::RegisterNonNativeRewardsBalancesQuery { denoms } => {
             register_non_native_rewards_balances_query(deps, info, denoms)
         }
-        ExecuteMsg::IBCTransfer { reply_to, reason } => {
-            execute_ibc_transfer(deps, env, info, reason, reply_to)
-        }
-        ExecuteMsg::Transfer { items, reply_to } => execute_transfer(deps, info, items, reply_to),
+        ExecuteMsg::IBCTransfer {
+            reason,
+            memo,
+            reply_to,
+        } => execute_ibc_transfer(deps, env, info, reason, memo, reply_to),
+        ExecuteMsg::Transfer {
+            items,
+            memo,
+            reply_to,
+        } => execute_transfer(deps, info, items, memo, reply_to),
+        ExecuteMsg::Vote {
+            proposal_id,
+            option,
+            reply_to,
+        } => execute_vote(deps, info, proposal_id, option, reply_to),
+        ExecuteMsg::WeightedVote {
+            proposal_id,
+            options,
+            reply_to,
+        } => execute_weighted_vote(deps, info, proposal_id, options, reply_to),
         ExecuteMsg::UpdateConfig { new_config } => execute_update_config(deps, info, new_config),
+        ExecuteMsg::UpdateQueryPeriods { periods } => {
+            execute_update_query_periods(deps, info, periods)
+        }
+        ExecuteMsg::RemoveQueries { query_ids } => execute_remove_queries(deps, info, query_ids),
+        ExecuteMsg::ResyncQueries { validators } => execute_resync_queries(deps, info, validators),
         ExecuteMsg::UpdateOwnership(action) => {
             let attrs = vec![attr("action", "update_ownership")];
             cw_ownable::update_ownership(deps.into_empty(), &env.block, &info.sender, action)?;
@@ -327,6 +458,35 @@ fn execute_update_config(
         config.timeout = timeout;
     }
 
+    if let Some(delegations_max_height_skew) = new_config.delegations_max_height_skew {
+        config.delegations_max_height_skew = delegations_max_height_skew;
+        attrs.push(attr(
+            "delegations_max_height_skew",
+            delegations_max_height_skew.to_string(),
+        ));
+    }
+
+    if let Some(max_memo_length) = new_config.max_memo_length {
+        config.max_memo_length = max_memo_length;
+        attrs.push(attr("max_memo_length", max_memo_length.to_string()));
+    }
+
+    if let Some(query_deposit_receiver) = new_config.query_deposit_receiver {
+        config.query_deposit_receiver = Some(deps.api.addr_validate(&query_deposit_receiver)?);
+        attrs.push(attr("query_deposit_receiver", query_deposit_receiver));
+    }
+
+    if let Some(ica_recovery) = new_config.ica_recovery {
+        if let Some(hook_receiver) = &ica_recovery.hook_receiver {
+            deps.api.addr_validate(hook_receiver)?;
+        }
+        attrs.push(attr(
+            "ica_register_fee",
+            ica_recovery.register_fee.to_string(),
+        ));
+        config.ica_recovery = Some(ica_recovery);
+    }
+
     puppeteer_base.update_config(deps.into_empty(), &config)?;
 
     Ok(response("config_update", CONTRACT_NAME, attrs))
@@ -337,12 +497,15 @@ fn execute_ibc_transfer(
     env: Env,
     info: MessageInfo,
     reason: IBCTransferReason,
+    memo: Option<String>,
     reply_to: String,
 ) -> ContractResult<Response<NeutronMsg>> {
     let puppeteer_base = Puppeteer::default();
     let config = puppeteer_base.config.load(deps.storage)?;
     validate_sender(&config, &info.sender)?;
-    puppeteer_base.validate_tx_idle_state(deps.as_ref())?;
+    let memo = memo.unwrap_or_default();
+    validate_memo(&memo, config.max_memo_length)?;
+    puppeteer_base.validate_tx_queue_not_full(deps.as_ref())?;
     // exclude fees, no need to send local denom tokens to remote zone
     let message_funds: Vec<_> = info
         .funds
@@ -371,7 +534,7 @@ fn execute_ibc_transfer(
             revision_height: None,
         },
         timeout_timestamp: env.block.time.plus_seconds(config.timeout).nanos(),
-        memo: "".to_string(),
+        memo,
         fee: query_ibc_fee(deps.as_ref(), LOCAL_DENOM)?,
     };
     let submsg = puppeteer_base.msg_with_sudo_callback(
@@ -389,6 +552,161 @@ fn execute_ibc_transfer(
     Ok(Response::default().add_submessages(vec![submsg]))
 }
 
+fn execute_update_query_periods(
+    deps: DepsMut<NeutronQuery>,
+    info: MessageInfo,
+    periods: QueryPeriods,
+) -> ContractResult<Response<NeutronMsg>> {
+    cw_ownable::assert_owner(deps.storage, &info.sender)?;
+    let puppeteer_base = Puppeteer::default();
+    let mut config = puppeteer_base.config.load(deps.storage)?;
+    let mut attrs = vec![attr("action", "update_query_periods")];
+    let mut updated_types = vec![];
+    if let Some(period) = periods.delegations_and_balance {
+        config.query_periods.delegations_and_balance = Some(period);
+        updated_types.push(KVQueryType::DelegationsAndBalance);
+        attrs.push(attr("delegations_and_balance", period.to_string()));
+    }
+    if let Some(period) = periods.unbonding_delegations {
+        config.query_periods.unbonding_delegations = Some(period);
+        updated_types.push(KVQueryType::UnbondingDelegations);
+        attrs.push(attr("unbonding_delegations", period.to_string()));
+    }
+    if let Some(period) = periods.non_native_rewards_balances {
+        config.query_periods.non_native_rewards_balances = Some(period);
+        updated_types.push(KVQueryType::NonNativeRewardsBalances);
+        attrs.push(attr("non_native_rewards_balances", period.to_string()));
+    }
+    puppeteer_base.config.save(deps.storage, &config)?;
+    // update already registered queries in place
+    let messages = puppeteer_base
+        .kv_queries
+        .range(deps.storage, None, None, Order::Ascending)
+        .collect::<StdResult<Vec<(u64, KVQueryType)>>>()?
+        .into_iter()
+        .filter(|(_, query_type)| updated_types.contains(query_type))
+        .map(|(query_id, query_type)| {
+            NeutronMsg::update_interchain_query(
+                query_id,
+                None,
+                Some(config.query_period(&query_type)),
+                None,
+            )
+        })
+        .collect::<NeutronResult<Vec<_>>>()?;
+    attrs.push(attr("updated_queries", messages.len().to_string()));
+    Ok(response("update_query_periods", CONTRACT_NAME, attrs).add_messages(messages))
+}
+
+fn execute_remove_queries(
+    deps: DepsMut<NeutronQuery>,
+    info: MessageInfo,
+    query_ids: Vec<u64>,
+) -> ContractResult<Response<NeutronMsg>> {
+    cw_ownable::assert_owner(deps.storage, &info.sender)?;
+    let attrs = vec![
+        attr("action", "remove_queries"),
+        attr("query_ids", format!("{:?}", query_ids)),
+    ];
+    let messages = remove_queries_msgs(deps, &query_ids)?;
+    Ok(response("remove_queries", CONTRACT_NAME, attrs).add_messages(messages))
+}
+
+fn execute_resync_queries(
+    deps: DepsMut<NeutronQuery>,
+    info: MessageInfo,
+    validators: Vec<String>,
+) -> ContractResult<Response<NeutronMsg>> {
+    cw_ownable::assert_owner(deps.storage, &info.sender)?;
+    let puppeteer_base = Puppeteer::default();
+    let mut stale_query_ids = vec![];
+    for item in puppeteer_base
+        .kv_queries
+        .range(deps.storage, None, None, Order::Ascending)
+    {
+        let (query_id, query_type) = item?;
+        let is_stale = match query_type {
+            KVQueryType::DelegationsAndBalance => !puppeteer_base
+                .delegations_and_balances_query_id_chunk
+                .has(deps.storage, query_id),
+            KVQueryType::UnbondingDelegations => {
+                match puppeteer_base
+                    .unbonding_delegations
+                    .idx
+                    .query_id
+                    .item(deps.storage, query_id)?
+                {
+                    Some((_, unbonding_delegation)) => {
+                        !validators.contains(&unbonding_delegation.validator_address)
+                    }
+                    None => true,
+                }
+            }
+            KVQueryType::NonNativeRewardsBalances => false,
+        };
+        if is_stale {
+            stale_query_ids.push(query_id);
+        }
+    }
+    let attrs = vec![
+        attr("action", "resync_queries"),
+        attr("query_ids", format!("{:?}", stale_query_ids)),
+    ];
+    let messages = remove_queries_msgs(deps, &stale_query_ids)?;
+    Ok(response("resync_queries", CONTRACT_NAME, attrs).add_messages(messages))
+}
+
+/// Removes the queries with their state and sends the returned deposits to the configured receiver
+fn remove_queries_msgs(
+    deps: DepsMut<NeutronQuery>,
+    query_ids: &[u64],
+) -> ContractResult<Vec<CosmosMsg<NeutronMsg>>> {
+    let puppeteer_base = Puppeteer::default();
+    let config = puppeteer_base.config.load(deps.storage)?;
+    let mut deposit = Coins::default();
+    let mut messages = vec![];
+    for query_id in query_ids {
+        let registered_query = get_registered_query(deps.as_ref(), *query_id)?;
+        for coin in registered_query.registered_query.deposit {
+            deposit.add(coin)?;
+        }
+        puppeteer_base.kv_queries.remove(deps.storage, *query_id);
+        if let Some(chunk_id) = puppeteer_base
+            .delegations_and_balances_query_id_chunk
+            .may_load(deps.storage, *query_id)?
+        {
+            puppeteer_base
+                .delegations_and_balances_query_id_chunk
+                .remove(deps.storage, *query_id);
+            puppeteer_base
+                .delegations_and_balances_chunk_heights
+                .remove(deps.storage, chunk_id);
+        }
+        if let Some((_, unbonding_delegation)) = puppeteer_base
+            .unbonding_delegations
+            .idx
+            .query_id
+            .item(deps.storage, *query_id)?
+        {
+            puppeteer_base
+                .unbonding_delegations
+                .remove(deps.storage, &unbonding_delegation.validator_address)?;
+        }
+        messages.push(CosmosMsg::Custom(NeutronMsg::remove_interchain_query(
+            *query_id,
+        )));
+    }
+    if let Some(receiver) = config.query_deposit_receiver {
+        if !deposit.is_empty() {
+            messages.push(CosmosMsg::Bank(BankMsg::Send {
+                to_address: receiver.to_string(),
+                amount: deposit.into_vec(),
+            }));
+        }
+    }
+    Ok(messages)
+}
+
 fn register_non_native_rewards_balances_query(
     deps: DepsMut<NeutronQuery>,
     info: MessageInfo,
@@ -423,7 +741,7 @@ fn register_non_native_rewards_balances_query(
                 config.connection_id.clone(),
                 ica.clone(),
                 denoms,
-                config.update_period,
+                config.query_period(&KVQueryType::NonNativeRewardsBalances),
             )?,
             ReplyMsg::KvNonNativeRewardsBalances.to_reply_id(),
         ));
@@ -463,6 +781,15 @@ fn register_delegations_and_balance_query(
             NeutronMsg::remove_interchain_query(*query_id)
         })
         .collect::<Vec<_>>();
+    let chunk_ids: Vec<u16> = puppeteer_base
+        .delegations_and_balances_chunk_heights
+        .keys(deps.storage, None, None, Order::Ascending)
+        .collect::<StdResult<Vec<_>>>()?;
+    for chunk_id in chunk_ids {
+        puppeteer_base
+            .delegations_and_balances_chunk_heights
+            .remove(deps.storage, chunk_id);
+    }
 
     let mut submessages = vec![];
     let ica = puppeteer_base.ica.get_address(deps.storage)?;
@@ -477,7 +804,7 @@ fn register_delegations_and_balance_query(
                 ica.clone(),
                 config.remote_denom.clone(),
                 chunk.to_vec(),
-                config.update_period,
+                config.query_period(&KVQueryType::DelegationsAndBalance),
                 config.sdk_version.as_str(),
             )?,
             ReplyMsg::KvDelegationsAndBalance { i: i as u16 }.to_reply_id(),
@@ -528,7 +855,7 @@ fn register_unbonding_delegations_query(
                     config.connection_id.clone(),
                     delegator.clone(),
                     vec![validator],
-                    config.update_period,
+                    config.query_period(&KVQueryType::UnbondingDelegations),
                 )?,
                 ReplyMsg::KvUnbondingDelegations {
                     validator_index: i as u16,
@@ -551,7 +878,7 @@ fn execute_setup_protocol(
     let puppeteer_base = Puppeteer::default();
     let config: Config = puppeteer_base.config.load(deps.storage)?;
     validate_sender(&config, &info.sender)?;
-    puppeteer_base.validate_tx_idle_state(deps.as_ref())?;
+    puppeteer_base.validate_tx_queue_not_full(deps.as_ref())?;
     let ica = puppeteer_base.ica.get_address(deps.storage)?;
     let mut any_msgs = vec![];
     let grant_msg = MsgGrant {
@@ -609,13 +936,16 @@ fn execute_transfer(
     mut deps: DepsMut<NeutronQuery>,
     info: MessageInfo,
     items: Vec<(String, cosmwasm_std::Coin)>,
+    memo: Option<String>,
     reply_to: String,
 ) -> ContractResult<Response<NeutronMsg>> {
     let puppeteer_base = Puppeteer::default();
     deps.api.addr_validate(&reply_to)?;
     let config: Config = puppeteer_base.config.load(deps.storage)?;
     validate_sender(&config, &info.sender)?;
-    puppeteer_base.validate_tx_idle_state(deps.as_ref())?;
+    let memo = memo.unwrap_or_default();
+    validate_memo(&memo, config.max_memo_length)?;
+    puppeteer_base.validate_tx_queue_not_full(deps.as_ref())?;
     let ica = puppeteer_base.ica.get_address(deps.storage)?;
     let mut any_msgs = vec![];
     for (val, amount) in items.clone() {
@@ -636,10 +966,11 @@ fn execute_transfer(
             "/cosmos.bank.v1beta1.MsgSend",
         )?);
     }
-    let submsg = compose_submsg(
+    let submsg = compose_submsg_with_memo(
         deps.branch(),
         config.clone(),
         any_msgs,
+        memo,
         Transaction::Transfer {
             interchain_account_id: ICA_ID.to_string(),
             items,
@@ -651,6 +982,118 @@ fn execute_transfer(
     Ok(Response::default().add_submessages(vec![submsg]))
 }
 
+fn execute_vote(
+    mut deps: DepsMut<NeutronQuery>,
+    info: MessageInfo,
+    proposal_id: u64,
+    option: VoteOption,
+    reply_to: String,
+) -> ContractResult<Response<NeutronMsg>> {
+    let puppeteer_base = Puppeteer::default();
+    deps.api.addr_validate(&reply_to)?;
+    let config: Config = puppeteer_base.config.load(deps.storage)?;
+    validate_sender(&config, &info.sender)?;
+    puppeteer_base.validate_tx_queue_not_full(deps.as_ref())?;
+    let ica = puppeteer_base.ica.get_address(deps.storage)?;
+    let vote_msg = MsgVote {
+        proposal_id,
+        voter: ica.to_string(),
+        option: option.to_proto(),
+    };
+    let submsg = compose_submsg(
+        deps.branch(),
+        config,
+        vec![prepare_any_msg(vote_msg, "/cosmos.gov.v1beta1.MsgVote")?],
+        Transaction::Vote {
+            interchain_account_id: ICA_ID.to_string(),
+            proposal_id,
+            option,
+        },
+        reply_to,
+        ReplyMsg::SudoPayload.to_reply_id(),
+    )?;
+
+    Ok(Response::default().add_submessages(vec![submsg]))
+}
+
+fn execute_weighted_vote(
+    mut deps: DepsMut<NeutronQuery>,
+    info: MessageInfo,
+    proposal_id: u64,
+    options: Vec<WeightedVoteOption>,
+    reply_to: String,
+) -> ContractResult<Response<NeutronMsg>> {
+    let puppeteer_base = Puppeteer::default();
+    deps.api.addr_validate(&reply_to)?;
+    let config: Config = puppeteer_base.config.load(deps.storage)?;
+    validate_sender(&config, &info.sender)?;
+    validate_weighted_vote_options(&options)?;
+    puppeteer_base.validate_tx_queue_not_full(deps.as_ref())?;
+    let ica = puppeteer_base.ica.get_address(deps.storage)?;
+    let vote_msg = MsgVoteWeighted {
+        proposal_id,
+        voter: ica.to_string(),
+        options: options
+            .iter()
+            .map(|item| ProtoWeightedVoteOption {
+                option: item.option.to_proto(),
+                // sdk.Dec is encoded as an integer with 18 decimal places, same as Decimal atomics
+                weight: item.weight.atomics().to_string(),
+            })
+            .collect(),
+    };
+    let submsg = compose_submsg(
+        deps.branch(),
+        config,
+        vec![prepare_any_msg(
+            vote_msg,
+            "/cosmos.gov.v1beta1.MsgVoteWeighted",
+        )?],
+        Transaction::WeightedVote {
+            interchain_account_id: ICA_ID.to_string(),
+            proposal_id,
+            options,
+        },
+        reply_to,
+        ReplyMsg::SudoPayload.to_reply_id(),
+    )?;
+
+    Ok(Response::default().add_submessages(vec![submsg]))
+}
+
+fn validate_weighted_vote_options(options: &[WeightedVoteOption]) -> ContractResult<()> {
+    ensure!(
+        !options.is_empty(),
+        ContractError::InvalidVoteOptions {
+            reason: "no options provided".to_string()
+        }
+    );
+    let mut total = Decimal::zero();
+    for (i, item) in options.iter().enumerate() {
+        ensure!(
+            !item.weight.is_zero(),
+            ContractError::InvalidVoteOptions {
+                reason: format!("zero weight for {:?}", item.option)
+            }
+        );
+        ensure!(
+            !options[..i].iter().any(|other| other.option == item.option),
+            ContractError::InvalidVoteOptions {
+                reason: format!("duplicated option {:?}", item.option)
+            }
+        );
+        total = total.checked_add(item.weight)?;
+    }
+    ensure_eq!(
+        total,
+        Decimal::one(),
+        ContractError::InvalidVoteOptions {
+            reason: "weights must sum up to 1".to_string()
+        }
+    );
+    Ok(())
+}
+
 fn execute_claim_rewards_and_optionaly_transfer(
     mut deps: DepsMut<NeutronQuery>,
     info: MessageInfo,
@@ -662,7 +1105,7 @@ fn execute_claim_rewards_and_optionaly_transfer(
     deps.api.addr_validate(&reply_to)?;
     let config: Config = puppeteer_base.config.load(deps.storage)?;
     validate_sender(&config, &info.sender)?;
-    puppeteer_base.validate_tx_idle_state(deps.as_ref())?;
+    puppeteer_base.validate_tx_queue_not_full(deps.as_ref())?;
     let ica = puppeteer_base.ica.get_address(deps.storage)?;
     let mut any_msgs = vec![];
     if let Some(transfer) = transfer.clone() {
@@ -716,6 +1159,50 @@ fn execute_claim_rewards_and_optionaly_transfer(
     Ok(Response::default().add_submessages(vec![submsg]))
 }
 
+fn execute_delegate(
+    mut deps: DepsMut<NeutronQuery>,
+    info: MessageInfo,
+    items: Vec<(String, Uint128)>,
+    reply_to: String,
+) -> ContractResult<Response<NeutronMsg>> {
+    let puppeteer_base = Puppeteer::default();
+    deps.api.addr_validate(&reply_to)?;
+    let config: Config = puppeteer_base.config.load(deps.storage)?;
+    validate_sender(&config, &info.sender)?;
+    puppeteer_base.validate_tx_queue_not_full(deps.as_ref())?;
+    let delegator = puppeteer_base.ica.get_address(deps.storage)?;
+    let mut any_msgs = vec![];
+    for (validator, amount) in items.iter() {
+        let delegate_msg = MsgDelegate {
+            delegator_address: delegator.to_string(),
+            validator_address: validator.to_string(),
+            amount: Some(Coin {
+                denom: config.remote_denom.to_string(),
+                amount: amount.to_string(),
+            }),
+        };
+        any_msgs.push(prepare_any_msg(
+            delegate_msg,
+            "/cosmos.staking.v1beta1.MsgDelegate",
+        )?);
+    }
+
+    let submsg = compose_submsg(
+        deps.branch(),
+        config.clone(),
+        any_msgs,
+        Transaction::Delegate {
+            interchain_account_id: ICA_ID.to_string(),
+            denom: config.remote_denom,
+            items,
+        },
+        reply_to,
+        ReplyMsg::SudoPayload.to_reply_id(),
+    )?;
+
+    Ok(Response::default().add_submessages(vec![submsg]))
+}
+
 fn execute_undelegate(
     mut deps: DepsMut<NeutronQuery>,
     info: MessageInfo,
@@ -727,7 +1214,7 @@ fn execute_undelegate(
     deps.api.addr_validate(&reply_to)?;
     let config: Config = puppeteer_base.config.load(deps.storage)?;
     validate_sender(&config, &info.sender)?;
-    puppeteer_base.validate_tx_idle_state(deps.as_ref())?;
+    puppeteer_base.validate_tx_queue_not_full(deps.as_ref())?;
     let delegator = puppeteer_base.ica.get_address(deps.storage)?;
     let mut undelegation_msgs = vec![];
     for (validator, amount) in items.iter() {
@@ -782,7 +1269,7 @@ fn execute_redelegate(
     deps.api.addr_validate(&reply_to)?;
     let config: Config = puppeteer_base.config.load(deps.storage)?;
     validate_sender(&config, &info.sender)?;
-    puppeteer_base.validate_tx_idle_state(deps.as_ref())?;
+    puppeteer_base.validate_tx_queue_not_full(deps.as_ref())?;
     let delegator = puppeteer_base.ica.get_address(deps.storage)?;
     let redelegate_msg = MsgBeginRedelegate {
         delegator_address: delegator,
@@ -826,7 +1313,7 @@ fn execute_tokenize_share(
     deps.api.addr_validate(&reply_to)?;
     let config: Config = puppeteer_base.config.load(deps.storage)?;
     validate_sender(&config, &info.sender)?;
-    puppeteer_base.validate_tx_idle_state(deps.as_ref())?;
+    puppeteer_base.validate_tx_queue_not_full(deps.as_ref())?;
     let delegator = puppeteer_base.ica.get_address(deps.storage)?;
     let tokenize_msg = MsgTokenizeShares {
         delegator_address: delegator.clone(),
@@ -869,7 +1356,7 @@ fn execute_redeem_shares(
     ];
     let puppeteer_base = Puppeteer::default();
     deps.api.addr_validate(&reply_to)?;
-    puppeteer_base.validate_tx_idle_state(deps.as_ref())?;
+    puppeteer_base.validate_tx_queue_not_full(deps.as_ref())?;
     let config: Config = puppeteer_base.config.load(deps.storage)?;
     validate_sender(&config, &info.sender)?;
     let delegator = puppeteer_base.ica.get_address(deps.storage)?;
@@ -901,9 +1388,29 @@ fn execute_redeem_shares(
 }
 
 fn compose_submsg(
+    deps: DepsMut<NeutronQuery>,
+    config: Config,
+    any_msgs: Vec<ProtobufAny>,
+    transaction: Transaction,
+    reply_to: String,
+    reply_id: u64,
+) -> NeutronResult<SubMsg<NeutronMsg>> {
+    compose_submsg_with_memo(
+        deps,
+        config,
+        any_msgs,
+        "".to_string(),
+        transaction,
+        reply_to,
+        reply_id,
+    )
+}
+
+fn compose_submsg_with_memo(
     mut deps: DepsMut<NeutronQuery>,
     config: Config,
     any_msgs: Vec<ProtobufAny>,
+    memo: String,
     transaction: Transaction,
     reply_to: String,
     reply_id: u64,
@@ -915,7 +1422,7 @@ fn compose_submsg(
         connection_id,
         ICA_ID.to_string(),
         any_msgs,
-        "".to_string(),
+        memo,
         config.timeout,
         ibc_fee,
     );
@@ -961,6 +1468,7 @@ pub fn sudo(
                         env,
                         query_id,
                         &config.sdk_version,
+                        config.delegations_max_height_skew,
                     ),
                 KVQueryType::NonNativeRewardsBalances => puppeteer_base.sudo_kv_query_result(
                     deps,
@@ -1013,8 +1521,7 @@ fn sudo_response(
         .clone()
         .source_port
         .ok_or_else(|| StdError::generic_err("source_port not found"))?;
-    let tx_state = puppeteer_base.tx_state.load(deps.storage)?;
-    puppeteer_base.validate_tx_waiting_state(deps.as_ref())?;
+    let tx_state = puppeteer_base.take_tx(deps.storage, &channel_id, seq_id)?;
     let reply_to = tx_state
         .reply_to
         .ok_or_else(|| StdError::generic_err("reply_to not found"))?;
@@ -1025,22 +1532,24 @@ fn sudo_response(
         "WASMDEBUG: transaction: {transaction:?}",
         transaction = transaction
     ));
-    puppeteer_base.tx_state.save(
-        deps.storage,
-        &TxState {
-            status: TxStateStatus::Idle,
-            seq_id: None,
-            transaction: None,
-            reply_to: None,
-        },
-    )?;
     let answers = match transaction {
         Transaction::IBCTransfer { .. } => vec![ResponseAnswer::IBCTransfer(MsgIBCTransfer {})],
         _ => {
             let msg_data: TxMsgData = TxMsgData::decode(data.as_slice())?;
-            get_answers_from_msg_data(deps.as_ref(), msg_data)?
+            let config = puppeteer_base.config.load(deps.storage)?;
+            get_answers_from_msg_data(deps.as_ref(), msg_data, &config.sdk_version)?
         }
     };
+    record_redelegation(deps.storage, &env, &transaction, &answers)?;
+    puppeteer_base.record_tx_result(
+        deps.storage,
+        &env,
+        &channel_id,
+        seq_id,
+        TxResult::Success {
+            answers: answers.clone(),
+        },
+    )?;
 
     let client_state = query_client_state(&deps.as_ref(), channel_id, port_id)?;
     let remote_height = client_state
@@ -1084,20 +1593,80 @@ fn sudo_response(
     Ok(response("sudo-response", "puppeteer", attrs).add_messages(msgs))
 }
 
-fn get_answers_from_msg_data(
+/// Keeps track of redelegation entries in progress as the remote chain limits
+/// the number of simultaneous entries for every pair of validators
+fn record_redelegation(
+    storage: &mut dyn Storage,
+    env: &Env,
+    transaction: &Transaction,
+    answers: &[ResponseAnswer],
+) -> StdResult<()> {
+    if let Transaction::Redelegate {
+        validator_from,
+        validator_to,
+        ..
+    } = transaction
+    {
+        let now = env.block.time.seconds();
+        let key = (validator_from.to_string(), validator_to.to_string());
+        let mut completion_times: Vec<u64> = REDELEGATIONS
+            .may_load(storage, key.clone())?
+            .unwrap_or_default()
+            .into_iter()
+            .filter(|completion_time| *completion_time > now)
+            .collect();
+        completion_times.extend(answers.iter().filter_map(|answer| {
+            match answer {
+                ResponseAnswer::BeginRedelegateResponse(response) => response
+                    .completion_time
+                    .as_ref()
+                    .map(|completion_time| completion_time.seconds as u64),
+                _ => None,
+            }
+        }));
+        REDELEGATIONS.save(storage, key, &completion_times)?;
+    }
+    Ok(())
+}
+
+pub(crate) fn get_answers_from_msg_data(
     deps: Deps<NeutronQuery>,
     msg_data: TxMsgData,
+    sdk_version: &str,
 ) -> NeutronResult<Vec<ResponseAnswer>> {
-    let mut answers = vec![];
+    // SDK 0.50 hosts only fill msg_responses, older ones use the deprecated data field
+    // (or msg_responses as well since ibc-go v5), so we fall back to it if data is empty
     #[allow(deprecated)]
-    for item in msg_data.data {
-        let answer = match item.msg_type.as_str() {
+    let items: Vec<(String, Vec<u8>)> =
+        if version_to_u32(sdk_version)? >= version_to_u32("0.50.0")? || msg_data.data.is_empty() {
+            msg_data
+                .msg_responses
+                .into_iter()
+                .map(|any| {
+                    let msg_type = any
+                        .type_url
+                        .strip_suffix("Response")
+                        .unwrap_or(&any.type_url)
+                        .to_string();
+                    (msg_type, any.value)
+                })
+                .collect()
+        } else {
+            msg_data
+                .data
+                .into_iter()
+                .map(|item| (item.msg_type, item.data))
+                .collect()
+        };
+    let mut answers = vec![];
+    for (msg_type, data) in items {
+        let answer = match msg_type.as_str() {
             "/cosmos.staking.v1beta1.MsgDelegate" => {
-                let _out: MsgDelegateResponse = decode_message_response(&item.data)?;
+                let _out: MsgDelegateResponse = decode_message_response(&data)?;
                 ResponseAnswer::DelegateResponse(drop_puppeteer_base::proto::MsgDelegateResponse {})
             }
             "/cosmos.staking.v1beta1.MsgUndelegate" => {
-                let out: MsgUndelegateResponse = decode_message_response(&item.data)?;
+                let out: MsgUndelegateResponse = decode_message_response(&data)?;
                 ResponseAnswer::UndelegateResponse(
                     drop_puppeteer_base::proto::MsgUndelegateResponse {
                         completion_time: out.completion_time.map(|t| t.into()),
@@ -1105,7 +1674,7 @@ fn get_answers_from_msg_data(
                 )
             }
             "/cosmos.staking.v1beta1.MsgTokenizeShares" => {
-                let out: MsgTokenizeSharesResponse = decode_message_response(&item.data)?;
+                let out: MsgTokenizeSharesResponse = decode_message_response(&data)?;
                 ResponseAnswer::TokenizeSharesResponse(
                     drop_puppeteer_base::proto::MsgTokenizeSharesResponse {
                         amount: out.amount.map(convert_coin).transpose()?,
@@ -1113,7 +1682,7 @@ fn get_answers_from_msg_data(
                 )
             }
             "/cosmos.staking.v1beta1.MsgBeginRedelegate" => {
-                let out: MsgBeginRedelegateResponse = decode_message_response(&item.data)?;
+                let out: MsgBeginRedelegateResponse = decode_message_response(&data)?;
                 ResponseAnswer::BeginRedelegateResponse(
                     drop_puppeteer_base::proto::MsgBeginRedelegateResponse {
                         completion_time: out.completion_time.map(|t| t.into()),
@@ -1121,13 +1690,13 @@ fn get_answers_from_msg_data(
                 )
             }
             "/cosmos.authz.v1beta1.MsgGrant" => {
-                let _out: MsgGrantResponse = decode_message_response(&item.data)?;
+                let _out: MsgGrantResponse = decode_message_response(&data)?;
                 ResponseAnswer::GrantDelegateResponse(
                     drop_puppeteer_base::proto::MsgGrantResponse {},
                 )
             }
             "/cosmos.staking.v1beta1.MsgRedeemTokensForShares" => {
-                let out: MsgRedeemTokensforSharesResponse = decode_message_response(&item.data)?;
+                let out: MsgRedeemTokensforSharesResponse = decode_message_response(&data)?;
                 ResponseAnswer::RedeemTokensforSharesResponse(
                     drop_puppeteer_base::proto::MsgRedeemTokensforSharesResponse {
                         amount: out.amount.map(convert_coin).transpose()?,
@@ -1135,12 +1704,22 @@ fn get_answers_from_msg_data(
                 )
             }
             "/cosmos.bank.v1beta1.MsgSend" => {
-                let _out: MsgSendResponse = decode_message_response(&item.data)?;
+                let _out: MsgSendResponse = decode_message_response(&data)?;
                 ResponseAnswer::TransferResponse(drop_puppeteer_base::proto::MsgSendResponse {})
             }
+            "/cosmos.gov.v1beta1.MsgVote" => {
+                let _out: MsgVoteResponse = decode_message_response(&data)?;
+                ResponseAnswer::VoteResponse(drop_puppeteer_base::proto::MsgVoteResponse {})
+            }
+            "/cosmos.gov.v1beta1.MsgVoteWeighted" => {
+                let _out: MsgVoteWeightedResponse = decode_message_response(&data)?;
+                ResponseAnswer::VoteWeightedResponse(
+                    drop_puppeteer_base::proto::MsgVoteWeightedResponse {},
+                )
+            }
             _ => {
                 deps.api.debug(
-                    format!("This type of acknowledgement is not implemented: {item:?}").as_str(),
+                    format!("This type of acknowledgement is not implemented: {msg_type}").as_str(),
                 );
                 ResponseAnswer::UnknownResponse {}
             }
@@ -1161,7 +1740,7 @@ fn convert_coin(coin: crate::proto::cosmos::base::v1beta1::Coin) -> StdResult<co
 
 fn sudo_error(
     deps: DepsMut<NeutronQuery>,
-    _env: Env,
+    env: Env,
     request: RequestPacket,
     details: String,
 ) -> NeutronResult<Response<NeutronMsg>> {
@@ -1174,15 +1753,26 @@ fn sudo_error(
     deps.api.debug(&format!(
         "WASMDEBUG: sudo_error: request: {request:?} details: {details:?}",
     ));
-    let tx_state = puppeteer_base.tx_state.load(deps.storage)?;
-    puppeteer_base.validate_tx_waiting_state(deps.as_ref())?;
-
     let seq_id = request
         .sequence
         .ok_or_else(|| StdError::generic_err("sequence not found"))?;
+    let channel_id = request
+        .source_channel
+        .clone()
+        .ok_or_else(|| StdError::generic_err("source_channel not found"))?;
+    let tx_state = puppeteer_base.take_tx(deps.storage, &channel_id, seq_id)?;
     let transaction = tx_state
         .transaction
         .ok_or_else(|| StdError::generic_err("transaction not found"))?;
+    puppeteer_base.record_tx_result(
+        deps.storage,
+        &env,
+        &channel_id,
+        seq_id,
+        TxResult::Error {
+            details: details.clone(),
+        },
+    )?;
     let msg = CosmosMsg::Wasm(WasmMsg::Execute {
         contract_addr: tx_state
             .reply_to
@@ -1191,34 +1781,34 @@ fn sudo_error(
             ResponseHookErrorMsg {
                 request_id: seq_id,
                 request,
-                transaction,
+                transaction: transaction.clone(),
                 details,
             },
         )))?,
-        funds: vec![],
+        funds: refunded_funds(&transaction),
     });
-    puppeteer_base.tx_state.save(
-        deps.storage,
-        &TxState {
-            status: TxStateStatus::Idle,
-            seq_id: None,
-            transaction: None,
-            reply_to: None,
-        },
-    )?;
     Ok(response("sudo-error", "puppeteer", attrs).add_message(msg))
 }
 
+/// A failed or timed out IBC transfer is refunded to the puppeteer, so the funds are
+/// returned to the contract which requested the transfer along with the error hook
+fn refunded_funds(transaction: &Transaction) -> Vec<cosmwasm_std::Coin> {
+    match transaction {
+        Transaction::IBCTransfer { denom, amount, .. } => coins(*amount, denom),
+        _ => vec![],
+    }
+}
+
 fn sudo_timeout(
     deps: DepsMut<NeutronQuery>,
-    _env: Env,
+    env: Env,
     request: RequestPacket,
 ) -> NeutronResult<Response<NeutronMsg>> {
     deps.api.debug(&format!(
         "WASMDEBUG: sudo_timeout: request: {request:?}",
         request = request
     ));
-    let attrs = vec![
+    let mut attrs = vec![
         attr("action", "sudo_timeout"),
         attr("request_id", request.sequence.unwrap_or(0).to_string()),
     ];
@@ -1226,48 +1816,86 @@ fn sudo_timeout(
     let seq_id = request
         .sequence
         .ok_or_else(|| StdError::generic_err("sequence not found"))?;
-    let tx_state = puppeteer_base.tx_state.load(deps.storage)?;
+    let channel_id = request
+        .source_channel
+        .clone()
+        .ok_or_else(|| StdError::generic_err("source_channel not found"))?;
+    let tx_state = puppeteer_base.take_tx(deps.storage, &channel_id, seq_id)?;
     let transaction = tx_state
         .transaction
         .ok_or_else(|| StdError::generic_err("transaction not found"))?;
-    puppeteer_base.validate_tx_waiting_state(deps.as_ref())?;
-    puppeteer_base.ica.set_timeout(deps.storage)?;
-    puppeteer_base.tx_state.save(
-        deps.storage,
-        &TxState {
-            status: TxStateStatus::Idle,
-            seq_id: None,
-            transaction: None,
-            reply_to: None,
-        },
-    )?;
-    deps.api.debug(&format!(
-        "WASMDEBUG: sudo_timeout: request: {request:?}",
-        request = request
-    ));
-    let msg = CosmosMsg::Wasm(WasmMsg::Execute {
+    puppeteer_base.record_tx_result(deps.storage, &env, &channel_id, seq_id, TxResult::Timeout)?;
+    let mut msgs = vec![CosmosMsg::Wasm(WasmMsg::Execute {
         contract_addr: tx_state
             .reply_to
             .ok_or_else(|| StdError::generic_err("reply_to not found"))?,
         msg: to_json_binary(&ReceiverExecuteMsg::PuppeteerHook(ResponseHookMsg::Error(
             ResponseHookErrorMsg {
                 request_id: seq_id,
-                request,
-                transaction,
+                request: request.clone(),
+                transaction: transaction.clone(),
                 details: "Timeout".to_string(),
             },
         )))?,
-        funds: vec![],
-    });
-    Ok(response("sudo-timeout", "puppeteer", attrs).add_message(msg))
+        funds: refunded_funds(&transaction),
+    })];
+    // the ordered ICA channel is closed after a timeout, so the rest of its queue is dropped.
+    // Transfers on other channels are acked or refunded independently
+    let ica_timed_out = puppeteer_base.ica.timeout(deps.storage, &channel_id)?;
+    let purged = if ica_timed_out {
+        puppeteer_base.take_channel_txs(deps.storage, &channel_id)?
+    } else {
+        vec![]
+    };
+    if !purged.is_empty() {
+        attrs.push(attr("purged_txs", purged.len().to_string()));
+    }
+    for (purged_seq_id, purged_tx) in purged {
+        let details = "Channel closed".to_string();
+        puppeteer_base.record_tx_result(
+            deps.storage,
+            &env,
+            &channel_id,
+            purged_seq_id,
+            TxResult::Error {
+                details: details.clone(),
+            },
+        )?;
+        if let (Some(reply_to), Some(transaction)) = (purged_tx.reply_to, purged_tx.transaction) {
+            msgs.push(CosmosMsg::Wasm(WasmMsg::Execute {
+                contract_addr: reply_to,
+                msg: to_json_binary(&ReceiverExecuteMsg::PuppeteerHook(ResponseHookMsg::Error(
+                    ResponseHookErrorMsg {
+                        request_id: purged_seq_id,
+                        request: RequestPacket {
+                            sequence: Some(purged_seq_id),
+                            data: None,
+                            ..request.clone()
+                        },
+                        transaction,
+                        details,
+                    },
+                )))?,
+                funds: vec![],
+            }));
+        }
+    }
+    let recovery_msgs = if ica_timed_out {
+        puppeteer_base.ica_recovery_msgs(deps, &env)?
+    } else {
+        vec![]
+    };
+    Ok(response("sudo-timeout", "puppeteer", attrs)
+        .add_messages(msgs)
+        .add_messages(recovery_msgs))
 }
 
 #[cfg_attr(not(feature = "library"), cosmwasm_std::entry_point)]
-pub fn reply(deps: DepsMut, _env: Env, msg: Reply) -> StdResult<Response> {
+pub fn reply(deps: DepsMut, env: Env, msg: Reply) -> StdResult<Response> {
     let puppeteer_base: PuppeteerBase<'_, Config, KVQueryType> = Puppeteer::default();
     match ReplyMsg::from_reply_id(msg.id) {
-        ReplyMsg::SudoPayload => puppeteer_base.submit_tx_reply(deps, msg),
-        ReplyMsg::IbcTransfer => puppeteer_base.submit_ibc_transfer_reply(deps, msg),
+        ReplyMsg::SudoPayload => puppeteer_base.submit_tx_reply(deps, env, msg),
+        ReplyMsg::IbcTransfer => puppeteer_base.submit_ibc_transfer_reply(deps, env, msg),
         ReplyMsg::KvDelegationsAndBalance { i } => puppeteer_base
             .register_delegations_and_balance_query_reply(
                 deps,
@@ -1308,12 +1936,38 @@ pub fn migrate(
         cw2::get_contract_version(deps.storage)?.version.parse()?;
 
     if storage_version < version {
+        migrate_tx_state(deps.storage)?;
         cw2::set_contract_version(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
     }
+    Puppeteer::default().ica.init_last_address(deps.storage)?;
 
     Ok(Response::new())
 }
 
+/// Moves the transaction awaiting acknowledgement from the legacy single slot into the queue
+fn migrate_tx_state(storage: &mut dyn Storage) -> ContractResult<()> {
+    let legacy_tx_state: Item<TxState> = Item::new("sudo_payload");
+    let puppeteer_base = Puppeteer::default();
+    if let Some(tx_state) = legacy_tx_state.may_load(storage)? {
+        if let (TxStateStatus::WaitingForAck, Some(seq_id)) = (&tx_state.status, tx_state.seq_id) {
+            let channel_id = match tx_state.transaction {
+                Some(Transaction::IBCTransfer { .. }) => {
+                    puppeteer_base.config.load(storage)?.transfer_channel_id
+                }
+                _ => match puppeteer_base.ica.load(storage)? {
+                    IcaState::Registered { channel_id, .. } => channel_id,
+                    _ => return Err(StdError::generic_err("ICA is not registered").into()),
+                },
+            };
+            puppeteer_base
+                .tx_queue
+                .save(storage, (channel_id, seq_id), &tx_state)?;
+        }
+        legacy_tx_state.remove(storage);
+    }
+    Ok(())
+}
+
 fn validate_sender(config: &Config, sender: &Addr) -> StdResult<()> {
     if config.allowed_senders.contains(sender) {
         Ok(())
@@ -4,8 +4,8 @@ use cosmwasm_schema::schemars;
 use cosmwasm_std::{
     coin, coins, from_json,
     testing::{mock_env, mock_info},
-    to_json_binary, Addr, Binary, CosmosMsg, Decimal256, DepsMut, Event, Response, StdError,
-    SubMsg, Timestamp, Uint128, Uint64,
+    to_json_binary, Addr, Binary, CosmosMsg, Decimal, Decimal256, DepsMut, Event, Response,
+    StdError, SubMsg, Timestamp, Uint128, Uint64,
 };
 use drop_helpers::{
     ibc_client_state::{
@@ -19,7 +19,7 @@ use drop_puppeteer_base::state::{
 };
 use drop_staking_base::{
     msg::puppeteer::InstantiateMsg,
-    state::puppeteer::{Config, ConfigOptional, KVQueryType},
+    state::puppeteer::{Config, ConfigOptional, KVQueryType, QueryPeriods},
 };
 use neutron_sdk::{
     bindings::{
@@ -34,10 +34,15 @@ use neutron_sdk::{
 };
 use prost::Message;
 use schemars::_serde_json::to_string;
+use std::str::FromStr;
 
 use std::vec;
 
 fn build_interchain_query_response() -> Binary {
+    build_interchain_query_response_at(123456)
+}
+
+fn build_interchain_query_response_at(height: u64) -> Binary {
     let res: Vec<StorageValue> = from_json(
         r#"[
         {
@@ -161,7 +166,7 @@ fn build_interchain_query_response() -> Binary {
         to_string(&QueryRegisteredQueryResultResponse {
             result: InterchainQueryResult {
                 kv_results: res,
-                height: 123456,
+                height,
                 revision: 2,
             },
         })
@@ -212,6 +217,10 @@ fn test_update_config() {
             port_id: Some("new_port_id".to_string()),
             sdk_version: Some("0.47.0".to_string()),
             timeout: Some(101u64),
+            ica_recovery: None,
+            query_deposit_receiver: None,
+            delegations_max_height_skew: None,
+            max_memo_length: Some(128),
         },
     };
     let deps_mut = deps.as_mut();
@@ -232,6 +241,7 @@ fn test_update_config() {
                     ("transfer_channel_id", "new_transfer_channel_id"),
                     ("sdk_version", "0.47.0"),
                     ("timeout", "101"),
+                    ("max_memo_length", "128"),
                 ])
         )
     );
@@ -248,8 +258,247 @@ fn test_update_config() {
             transfer_channel_id: "new_transfer_channel_id".to_string(),
             sdk_version: "0.47.0".to_string(),
             timeout: 101u64,
+            ica_recovery: None,
+            query_periods: QueryPeriods::default(),
+            query_deposit_receiver: None,
+            delegations_max_height_skew: 0,
+            max_memo_length: 128,
+        }
+    );
+}
+
+#[test]
+fn test_update_query_periods() {
+    let mut deps = mock_dependencies(&[]);
+    let puppeteer_base = base_init(&mut deps.as_mut());
+    for (query_id, query_type) in [
+        (1u64, KVQueryType::DelegationsAndBalance),
+        (2u64, KVQueryType::UnbondingDelegations),
+        (3u64, KVQueryType::UnbondingDelegations),
+        (4u64, KVQueryType::NonNativeRewardsBalances),
+    ] {
+        puppeteer_base
+            .kv_queries
+            .save(deps.as_mut().storage, query_id, &query_type)
+            .unwrap();
+    }
+    let msg = drop_staking_base::msg::puppeteer::ExecuteMsg::UpdateQueryPeriods {
+        periods: QueryPeriods {
+            delegations_and_balance: None,
+            unbonding_delegations: Some(3600u64),
+            non_native_rewards_balances: Some(1200u64),
+        },
+    };
+    let res = crate::contract::execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("not_owner", &[]),
+        msg.clone(),
+    );
+    assert!(res.is_err());
+    let res =
+        crate::contract::execute(deps.as_mut(), mock_env(), mock_info("owner", &[]), msg).unwrap();
+    assert_eq!(
+        res,
+        Response::new()
+            .add_messages(vec![
+                NeutronMsg::update_interchain_query(2, None, Some(3600), None).unwrap(),
+                NeutronMsg::update_interchain_query(3, None, Some(3600), None).unwrap(),
+                NeutronMsg::update_interchain_query(4, None, Some(1200), None).unwrap(),
+            ])
+            .add_event(
+                Event::new("crates.io:drop-neutron-contracts__drop-puppeteer-update_query_periods")
+                    .add_attributes(vec![
+                        ("action", "update_query_periods"),
+                        ("unbonding_delegations", "3600"),
+                        ("non_native_rewards_balances", "1200"),
+                        ("updated_queries", "3"),
+                    ])
+            )
+    );
+    let config = puppeteer_base.config.load(deps.as_ref().storage).unwrap();
+    assert_eq!(
+        config.query_period(&KVQueryType::DelegationsAndBalance),
+        60u64
+    );
+    assert_eq!(
+        config.query_period(&KVQueryType::UnbondingDelegations),
+        3600u64
+    );
+    assert_eq!(
+        config.query_period(&KVQueryType::NonNativeRewardsBalances),
+        1200u64
+    );
+}
+
+fn registered_query_response(query_id: u64, deposit: u128) -> Binary {
+    to_json_binary(&schemars::_serde_json::json!({
+        "registered_query": {
+            "id": query_id,
+            "owner": "puppeteer",
+            "query_type": "kv",
+            "keys": [],
+            "transactions_filter": "",
+            "connection_id": "connection_id",
+            "update_period": 60,
+            "last_submitted_result_local_height": 0,
+            "last_submitted_result_remote_height": {
+                "revision_number": 0,
+                "revision_height": 0
+            },
+            "deposit": [{ "denom": "untrn", "amount": deposit.to_string() }],
+            "submit_timeout": 0,
+            "registered_at_height": 0
         }
+    }))
+    .unwrap()
+}
+
+fn setup_queries(deps: &mut DepsMut<NeutronQuery>) -> PuppeteerBase<'static, Config, KVQueryType> {
+    let puppeteer_base = base_init(deps);
+    let mut config = get_base_config();
+    config.query_deposit_receiver = Some(Addr::unchecked("deposit_receiver"));
+    puppeteer_base.config.save(deps.storage, &config).unwrap();
+    for (query_id, query_type) in [
+        (1u64, KVQueryType::DelegationsAndBalance),
+        (2u64, KVQueryType::UnbondingDelegations),
+        (3u64, KVQueryType::DelegationsAndBalance),
+        (4u64, KVQueryType::UnbondingDelegations),
+        (5u64, KVQueryType::NonNativeRewardsBalances),
+    ] {
+        puppeteer_base
+            .kv_queries
+            .save(deps.storage, query_id, &query_type)
+            .unwrap();
+    }
+    puppeteer_base
+        .delegations_and_balances_query_id_chunk
+        .save(deps.storage, 1, &0)
+        .unwrap();
+    for (query_id, validator) in [(2u64, "valoper1"), (4u64, "valoper2")] {
+        puppeteer_base
+            .unbonding_delegations
+            .save(
+                deps.storage,
+                validator,
+                &drop_puppeteer_base::state::UnbondingDelegation {
+                    validator_address: validator.to_string(),
+                    query_id,
+                    unbonding_delegations: vec![],
+                    last_updated_height: 0,
+                },
+            )
+            .unwrap();
+    }
+    puppeteer_base
+}
+
+#[test]
+fn test_remove_queries() {
+    let mut deps = mock_dependencies(&[]);
+    deps.querier
+        .add_registered_queries(1, registered_query_response(1, 1000));
+    deps.querier
+        .add_registered_queries(2, registered_query_response(2, 1000));
+    let puppeteer_base = setup_queries(&mut deps.as_mut());
+    let msg = drop_staking_base::msg::puppeteer::ExecuteMsg::RemoveQueries {
+        query_ids: vec![1, 2],
+    };
+    let res = crate::contract::execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("not_owner", &[]),
+        msg.clone(),
+    );
+    assert!(res.is_err());
+    let res =
+        crate::contract::execute(deps.as_mut(), mock_env(), mock_info("owner", &[]), msg).unwrap();
+    assert_eq!(
+        res,
+        Response::new()
+            .add_messages(vec![
+                CosmosMsg::Custom(NeutronMsg::remove_interchain_query(1)),
+                CosmosMsg::Custom(NeutronMsg::remove_interchain_query(2)),
+                CosmosMsg::Bank(cosmwasm_std::BankMsg::Send {
+                    to_address: "deposit_receiver".to_string(),
+                    amount: coins(2000, "untrn"),
+                }),
+            ])
+            .add_event(
+                Event::new("crates.io:drop-neutron-contracts__drop-puppeteer-remove_queries")
+                    .add_attributes(vec![("action", "remove_queries"), ("query_ids", "[1, 2]")])
+            )
+    );
+    let kv_queries = puppeteer_base
+        .kv_queries
+        .keys(
+            deps.as_ref().storage,
+            None,
+            None,
+            cosmwasm_std::Order::Ascending,
+        )
+        .collect::<cosmwasm_std::StdResult<Vec<u64>>>()
+        .unwrap();
+    assert_eq!(kv_queries, vec![3, 4, 5]);
+    assert!(!puppeteer_base
+        .delegations_and_balances_query_id_chunk
+        .has(deps.as_ref().storage, 1));
+    assert!(puppeteer_base
+        .unbonding_delegations
+        .may_load(deps.as_ref().storage, "valoper1")
+        .unwrap()
+        .is_none());
+}
+
+#[test]
+fn test_resync_queries() {
+    let mut deps = mock_dependencies(&[]);
+    deps.querier
+        .add_registered_queries(3, registered_query_response(3, 1000));
+    deps.querier
+        .add_registered_queries(4, registered_query_response(4, 1000));
+    let puppeteer_base = setup_queries(&mut deps.as_mut());
+    let res = crate::contract::execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("owner", &[]),
+        drop_staking_base::msg::puppeteer::ExecuteMsg::ResyncQueries {
+            validators: vec!["valoper1".to_string()],
+        },
+    )
+    .unwrap();
+    assert_eq!(
+        res,
+        Response::new()
+            .add_messages(vec![
+                CosmosMsg::Custom(NeutronMsg::remove_interchain_query(3)),
+                CosmosMsg::Custom(NeutronMsg::remove_interchain_query(4)),
+                CosmosMsg::Bank(cosmwasm_std::BankMsg::Send {
+                    to_address: "deposit_receiver".to_string(),
+                    amount: coins(2000, "untrn"),
+                }),
+            ])
+            .add_event(
+                Event::new("crates.io:drop-neutron-contracts__drop-puppeteer-resync_queries")
+                    .add_attributes(vec![("action", "resync_queries"), ("query_ids", "[3, 4]")])
+            )
     );
+    let kv_queries = puppeteer_base
+        .kv_queries
+        .keys(
+            deps.as_ref().storage,
+            None,
+            None,
+            cosmwasm_std::Order::Ascending,
+        )
+        .collect::<cosmwasm_std::StdResult<Vec<u64>>>()
+        .unwrap();
+    assert_eq!(kv_queries, vec![1, 2, 5]);
+    assert!(puppeteer_base
+        .unbonding_delegations
+        .may_load(deps.as_ref().storage, "valoper2")
+        .unwrap()
+        .is_none());
 }
 
 #[test]
@@ -338,7 +587,10 @@ fn test_execute_setup_protocol() {
             ReplyMsg::SudoPayload.to_reply_id()
         ))
     );
-    let tx_state = pupeteer_base.tx_state.load(deps.as_ref().storage).unwrap();
+    let tx_state = pupeteer_base
+        .tx_in_progress
+        .load(deps.as_ref().storage)
+        .unwrap();
     assert_eq!(
         tx_state,
         drop_puppeteer_base::state::TxState {
@@ -355,7 +607,7 @@ fn test_execute_setup_protocol() {
 }
 
 #[test]
-fn test_execute_undelegate() {
+fn test_execute_delegate() {
     let mut deps = mock_dependencies(&[]);
     deps.querier.add_custom_query_response(|_| {
         to_json_binary(&MinIbcFeeResponse {
@@ -364,15 +616,13 @@ fn test_execute_undelegate() {
         .unwrap()
     });
     let puppeteer_base = base_init(&mut deps.as_mut());
-    let msg = drop_staking_base::msg::puppeteer::ExecuteMsg::Undelegate {
-        batch_id: 0u128,
+    let msg = drop_staking_base::msg::puppeteer::ExecuteMsg::Delegate {
         items: vec![("valoper1".to_string(), Uint128::from(1000u128))],
         reply_to: "some_reply_to".to_string(),
     };
-    let env = mock_env();
     let res = crate::contract::execute(
         deps.as_mut(),
-        env,
+        mock_env(),
         mock_info("not_allowed_sender", &[]),
         msg.clone(),
     );
@@ -389,29 +639,18 @@ fn test_execute_undelegate() {
         msg,
     )
     .unwrap();
-
-    let msg = cosmos_sdk_proto::Any {
-        type_url: "/cosmos.staking.v1beta1.MsgUndelegate".to_string(),
-        value: cosmos_sdk_proto::cosmos::staking::v1beta1::MsgUndelegate {
-            delegator_address: "ica_address".to_string(),
-            validator_address: "valoper1".to_string(),
-            amount: Some(cosmos_sdk_proto::cosmos::base::v1beta1::Coin {
-                denom: "remote_denom".to_string(),
-                amount: "1000".to_string(),
-            }),
-        }
-        .to_bytes()
-        .unwrap(),
-    };
-
-    let grant_msg = cosmos_sdk_proto::cosmos::authz::v1beta1::MsgExec {
-        grantee: "ica_address".to_string(),
-        msgs: vec![msg],
+    let msg = cosmos_sdk_proto::cosmos::staking::v1beta1::MsgDelegate {
+        delegator_address: "ica_address".to_string(),
+        validator_address: "valoper1".to_string(),
+        amount: Some(cosmos_sdk_proto::cosmos::base::v1beta1::Coin {
+            denom: "remote_denom".to_string(),
+            amount: "1000".to_string(),
+        }),
     };
-    let mut buf = Vec::with_capacity(grant_msg.encoded_len());
-    grant_msg.encode(&mut buf).unwrap();
+    let mut buf = Vec::with_capacity(msg.encoded_len());
+    msg.encode(&mut buf).unwrap();
     let any_msg = neutron_sdk::bindings::types::ProtobufAny {
-        type_url: "/cosmos.authz.v1beta1.MsgExec".to_string(),
+        type_url: "/cosmos.staking.v1beta1.MsgDelegate".to_string(),
         value: Binary::from(buf),
     };
     assert_eq!(
@@ -428,15 +667,17 @@ fn test_execute_undelegate() {
             ReplyMsg::SudoPayload.to_reply_id()
         ))
     );
-    let tx_state = puppeteer_base.tx_state.load(deps.as_ref().storage).unwrap();
+    let tx_state = puppeteer_base
+        .tx_in_progress
+        .load(deps.as_ref().storage)
+        .unwrap();
     assert_eq!(
         tx_state,
         drop_puppeteer_base::state::TxState {
             seq_id: None,
             status: drop_puppeteer_base::state::TxStateStatus::InProgress,
             reply_to: Some("some_reply_to".to_string()),
-            transaction: Some(drop_puppeteer_base::msg::Transaction::Undelegate {
-                batch_id: 0u128,
+            transaction: Some(drop_puppeteer_base::msg::Transaction::Delegate {
                 interchain_account_id: "DROP".to_string(),
                 denom: "remote_denom".to_string(),
                 items: vec![("valoper1".to_string(), Uint128::from(1000u128))]
@@ -446,7 +687,7 @@ fn test_execute_undelegate() {
 }
 
 #[test]
-fn test_execute_redeem_share() {
+fn test_execute_vote() {
     let mut deps = mock_dependencies(&[]);
     deps.querier.add_custom_query_response(|_| {
         to_json_binary(&MinIbcFeeResponse {
@@ -455,46 +696,25 @@ fn test_execute_redeem_share() {
         .unwrap()
     });
     let puppeteer_base = base_init(&mut deps.as_mut());
-    let msg = drop_staking_base::msg::puppeteer::ExecuteMsg::RedeemShares {
-        items: vec![drop_puppeteer_base::state::RedeemShareItem {
-            amount: Uint128::from(1000u128),
-            remote_denom: "remote_denom".to_string(),
-            local_denom: "local_denom".to_string(),
-        }],
-        reply_to: "some_reply_to".to_string(),
-    };
-    let env = mock_env();
-    let res = crate::contract::execute(
-        deps.as_mut(),
-        env,
-        mock_info("not_allowed_sender", &[]),
-        msg.clone(),
-    );
-    assert_eq!(
-        res.unwrap_err(),
-        drop_puppeteer_base::error::ContractError::Std(StdError::generic_err(
-            "Sender is not allowed"
-        ))
-    );
     let res = crate::contract::execute(
         deps.as_mut(),
         mock_env(),
         mock_info("allowed_sender", &[]),
-        msg,
+        drop_staking_base::msg::puppeteer::ExecuteMsg::Vote {
+            proposal_id: 42,
+            option: drop_puppeteer_base::msg::VoteOption::Abstain,
+            reply_to: "some_reply_to".to_string(),
+        },
     )
     .unwrap();
-    let msg = crate::proto::liquidstaking::staking::v1beta1::MsgRedeemTokensforShares {
-        amount: Some(crate::proto::cosmos::base::v1beta1::Coin {
-            denom: "remote_denom".to_string(),
-            amount: "1000".to_string(),
-        }),
-        delegator_address: "ica_address".to_string(),
+    let msg = cosmos_sdk_proto::cosmos::gov::v1beta1::MsgVote {
+        proposal_id: 42,
+        voter: "ica_address".to_string(),
+        option: 2,
     };
-    let mut buf = Vec::with_capacity(msg.encoded_len());
-    msg.encode(&mut buf).unwrap();
     let any_msg = neutron_sdk::bindings::types::ProtobufAny {
-        type_url: "/cosmos.staking.v1beta1.MsgRedeemTokensForShares".to_string(),
-        value: Binary::from(buf),
+        type_url: "/cosmos.gov.v1beta1.MsgVote".to_string(),
+        value: Binary::from(msg.encode_to_vec()),
     };
     assert_eq!(
         res,
@@ -508,76 +728,749 @@ fn test_execute_redeem_share() {
                 get_standard_fees()
             )),
             ReplyMsg::SudoPayload.to_reply_id()
-        )).add_attributes(vec![("action", "redeem_share"), ("items", "[RedeemShareItem { amount: Uint128(1000), remote_denom: \"remote_denom\", local_denom: \"local_denom\" }]")])
+        ))
     );
-    let tx_state = puppeteer_base.tx_state.load(deps.as_ref().storage).unwrap();
     assert_eq!(
-        tx_state,
-        drop_puppeteer_base::state::TxState {
-            seq_id: None,
-            status: drop_puppeteer_base::state::TxStateStatus::InProgress,
-            reply_to: Some("some_reply_to".to_string()),
-            transaction: Some(drop_puppeteer_base::msg::Transaction::RedeemShares {
-                interchain_account_id: "DROP".to_string(),
-                items: vec![drop_puppeteer_base::state::RedeemShareItem {
-                    amount: Uint128::from(1000u128),
-                    remote_denom: "remote_denom".to_string(),
-                    local_denom: "local_denom".to_string()
-                }]
-            })
-        }
+        puppeteer_base
+            .tx_in_progress
+            .load(deps.as_ref().storage)
+            .unwrap()
+            .transaction,
+        Some(drop_puppeteer_base::msg::Transaction::Vote {
+            interchain_account_id: "DROP".to_string(),
+            proposal_id: 42,
+            option: drop_puppeteer_base::msg::VoteOption::Abstain,
+        })
     );
 }
 
 #[test]
-fn test_sudo_response_tx_state_wrong() {
-    // Test that the contract returns an error if the tx state is not in progress
+fn test_execute_weighted_vote() {
     let mut deps = mock_dependencies(&[]);
-    let puppeteer_base = base_init(&mut deps.as_mut());
-    let msg = SudoMsg::Response {
-        request: neutron_sdk::sudo::msg::RequestPacket {
-            sequence: Some(1u64),
-            source_port: Some("source_port".to_string()),
-            source_channel: Some("source_channel".to_string()),
-            destination_port: Some("destination_port".to_string()),
-            destination_channel: Some("destination_channel".to_string()),
-            data: None,
-            timeout_height: None,
-            timeout_timestamp: None,
-        },
-        data: Binary::from(vec![]),
+    deps.querier.add_custom_query_response(|_| {
+        to_json_binary(&MinIbcFeeResponse {
+            min_fee: get_standard_fees(),
+        })
+        .unwrap()
+    });
+    base_init(&mut deps.as_mut());
+    let weighted = |items: Vec<(drop_puppeteer_base::msg::VoteOption, &str)>| {
+        drop_staking_base::msg::puppeteer::ExecuteMsg::WeightedVote {
+            proposal_id: 42,
+            options: items
+                .into_iter()
+                .map(
+                    |(option, weight)| drop_puppeteer_base::msg::WeightedVoteOption {
+                        option,
+                        weight: Decimal::from_str(weight).unwrap(),
+                    },
+                )
+                .collect(),
+            reply_to: "some_reply_to".to_string(),
+        }
     };
-    let env = mock_env();
-    puppeteer_base
-        .tx_state
-        .save(
-            deps.as_mut().storage,
-            &drop_puppeteer_base::state::TxState {
-                seq_id: None,
-                status: drop_puppeteer_base::state::TxStateStatus::Idle,
-                reply_to: None,
-                transaction: None,
-            },
+    use drop_puppeteer_base::msg::VoteOption::{Abstain, Yes};
+    for (msg, reason) in [
+        (weighted(vec![]), "no options provided"),
+        (
+            weighted(vec![(Yes, "0.5"), (Abstain, "0.4")]),
+            "weights must sum up to 1",
+        ),
+        (
+            weighted(vec![(Yes, "0.5"), (Yes, "0.5")]),
+            "duplicated option Yes",
+        ),
+        (
+            weighted(vec![(Yes, "1"), (Abstain, "0")]),
+            "zero weight for Abstain",
+        ),
+    ] {
+        let err = crate::contract::execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("allowed_sender", &[]),
+            msg,
         )
-        .unwrap();
-    let res = crate::contract::sudo(deps.as_mut(), env, msg);
+        .unwrap_err();
+        assert_eq!(
+            err,
+            drop_puppeteer_base::error::ContractError::InvalidVoteOptions {
+                reason: reason.to_string()
+            }
+        );
+    }
+    let res = crate::contract::execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("allowed_sender", &[]),
+        weighted(vec![(Yes, "0.7"), (Abstain, "0.3")]),
+    )
+    .unwrap();
+    let msg = cosmos_sdk_proto::cosmos::gov::v1beta1::MsgVoteWeighted {
+        proposal_id: 42,
+        voter: "ica_address".to_string(),
+        options: vec![
+            cosmos_sdk_proto::cosmos::gov::v1beta1::WeightedVoteOption {
+                option: 1,
+                weight: "700000000000000000".to_string(),
+            },
+            cosmos_sdk_proto::cosmos::gov::v1beta1::WeightedVoteOption {
+                option: 2,
+                weight: "300000000000000000".to_string(),
+            },
+        ],
+    };
+    let any_msg = neutron_sdk::bindings::types::ProtobufAny {
+        type_url: "/cosmos.gov.v1beta1.MsgVoteWeighted".to_string(),
+        value: Binary::from(msg.encode_to_vec()),
+    };
     assert_eq!(
-        res.unwrap_err(),
-        NeutronError::Std(StdError::generic_err(
-            "Transaction txState is not equal to expected: WaitingForAck"
+        res,
+        Response::new().add_submessage(SubMsg::reply_on_success(
+            CosmosMsg::Custom(NeutronMsg::submit_tx(
+                "connection_id".to_string(),
+                "DROP".to_string(),
+                vec![any_msg],
+                "".to_string(),
+                100u64,
+                get_standard_fees()
+            )),
+            ReplyMsg::SudoPayload.to_reply_id()
         ))
     );
 }
 
 #[test]
-fn test_sudo_kv_query_result() {
+fn test_execute_ibc_transfer_memo() {
     let mut deps = mock_dependencies(&[]);
-
-    let query_id = 1u64;
-
-    deps.querier
-        .add_query_response(query_id, build_interchain_query_response());
-
+    deps.querier.add_custom_query_response(|_| {
+        to_json_binary(&MinIbcFeeResponse {
+            min_fee: get_standard_fees(),
+        })
+        .unwrap()
+    });
+    base_init(&mut deps.as_mut());
+    let msg = |memo: String| drop_staking_base::msg::puppeteer::ExecuteMsg::IBCTransfer {
+        reason: drop_puppeteer_base::msg::IBCTransferReason::Stake,
+        memo: Some(memo),
+        reply_to: "some_reply_to".to_string(),
+    };
+    let err = crate::contract::execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("allowed_sender", &[coin(1000, "base_denom")]),
+        msg("a".repeat(257)),
+    )
+    .unwrap_err();
+    assert_eq!(
+        err,
+        drop_puppeteer_base::error::ContractError::Std(StdError::generic_err(
+            "Memo is too long: 257 > 256"
+        ))
+    );
+    let memo = r#"{"wasm":{"contract":"withdrawal_manager","msg":{}}}"#.to_string();
+    let res = crate::contract::execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("allowed_sender", &[coin(1000, "base_denom")]),
+        msg(memo.clone()),
+    )
+    .unwrap();
+    match &res.messages[0].msg {
+        CosmosMsg::Custom(NeutronMsg::IbcTransfer {
+            memo: sent_memo,
+            receiver,
+            ..
+        }) => {
+            assert_eq!(sent_memo, &memo);
+            assert_eq!(receiver, "ica_address");
+        }
+        msg => panic!("unexpected message: {msg:?}"),
+    }
+}
+
+#[test]
+fn test_execute_undelegate() {
+    let mut deps = mock_dependencies(&[]);
+    deps.querier.add_custom_query_response(|_| {
+        to_json_binary(&MinIbcFeeResponse {
+            min_fee: get_standard_fees(),
+        })
+        .unwrap()
+    });
+    let puppeteer_base = base_init(&mut deps.as_mut());
+    let msg = drop_staking_base::msg::puppeteer::ExecuteMsg::Undelegate {
+        batch_id: 0u128,
+        items: vec![("valoper1".to_string(), Uint128::from(1000u128))],
+        reply_to: "some_reply_to".to_string(),
+    };
+    let env = mock_env();
+    let res = crate::contract::execute(
+        deps.as_mut(),
+        env,
+        mock_info("not_allowed_sender", &[]),
+        msg.clone(),
+    );
+    assert_eq!(
+        res.unwrap_err(),
+        drop_puppeteer_base::error::ContractError::Std(StdError::generic_err(
+            "Sender is not allowed"
+        ))
+    );
+    let res = crate::contract::execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("allowed_sender", &[]),
+        msg,
+    )
+    .unwrap();
+
+    let msg = cosmos_sdk_proto::Any {
+        type_url: "/cosmos.staking.v1beta1.MsgUndelegate".to_string(),
+        value: cosmos_sdk_proto::cosmos::staking::v1beta1::MsgUndelegate {
+            delegator_address: "ica_address".to_string(),
+            validator_address: "valoper1".to_string(),
+            amount: Some(cosmos_sdk_proto::cosmos::base::v1beta1::Coin {
+                denom: "remote_denom".to_string(),
+                amount: "1000".to_string(),
+            }),
+        }
+        .to_bytes()
+        .unwrap(),
+    };
+
+    let grant_msg = cosmos_sdk_proto::cosmos::authz::v1beta1::MsgExec {
+        grantee: "ica_address".to_string(),
+        msgs: vec![msg],
+    };
+    let mut buf = Vec::with_capacity(grant_msg.encoded_len());
+    grant_msg.encode(&mut buf).unwrap();
+    let any_msg = neutron_sdk::bindings::types::ProtobufAny {
+        type_url: "/cosmos.authz.v1beta1.MsgExec".to_string(),
+        value: Binary::from(buf),
+    };
+    assert_eq!(
+        res,
+        Response::new().add_submessage(SubMsg::reply_on_success(
+            CosmosMsg::Custom(NeutronMsg::submit_tx(
+                "connection_id".to_string(),
+                "DROP".to_string(),
+                vec![any_msg],
+                "".to_string(),
+                100u64,
+                get_standard_fees()
+            )),
+            ReplyMsg::SudoPayload.to_reply_id()
+        ))
+    );
+    let tx_state = puppeteer_base
+        .tx_in_progress
+        .load(deps.as_ref().storage)
+        .unwrap();
+    assert_eq!(
+        tx_state,
+        drop_puppeteer_base::state::TxState {
+            seq_id: None,
+            status: drop_puppeteer_base::state::TxStateStatus::InProgress,
+            reply_to: Some("some_reply_to".to_string()),
+            transaction: Some(drop_puppeteer_base::msg::Transaction::Undelegate {
+                batch_id: 0u128,
+                interchain_account_id: "DROP".to_string(),
+                denom: "remote_denom".to_string(),
+                items: vec![("valoper1".to_string(), Uint128::from(1000u128))]
+            })
+        }
+    );
+}
+
+#[test]
+fn test_execute_undelegate_tx_queue_is_full() {
+    let mut deps = mock_dependencies(&[]);
+    let puppeteer_base = base_init(&mut deps.as_mut());
+    for seq_id in 0..drop_puppeteer_base::state::TX_QUEUE_MAX_SIZE as u64 {
+        puppeteer_base
+            .tx_queue
+            .save(
+                deps.as_mut().storage,
+                ("channel".to_string(), seq_id),
+                &drop_puppeteer_base::state::TxState {
+                    seq_id: Some(seq_id),
+                    status: drop_puppeteer_base::state::TxStateStatus::WaitingForAck,
+                    reply_to: Some("some_reply_to".to_string()),
+                    transaction: None,
+                },
+            )
+            .unwrap();
+    }
+    let res = crate::contract::execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("allowed_sender", &[]),
+        drop_staking_base::msg::puppeteer::ExecuteMsg::Undelegate {
+            batch_id: 0u128,
+            items: vec![("valoper1".to_string(), Uint128::from(1000u128))],
+            reply_to: "some_reply_to".to_string(),
+        },
+    );
+    assert_eq!(
+        res.unwrap_err(),
+        drop_puppeteer_base::error::ContractError::NeutronError(NeutronError::Std(
+            StdError::generic_err("Transaction queue is full: 10")
+        ))
+    );
+}
+
+#[test]
+fn test_submit_tx_reply_enqueues_tx() {
+    let mut deps = mock_dependencies(&[]);
+    let puppeteer_base = base_init(&mut deps.as_mut());
+    let tx_state = drop_puppeteer_base::state::TxState {
+        seq_id: None,
+        status: drop_puppeteer_base::state::TxStateStatus::InProgress,
+        reply_to: Some("some_reply_to".to_string()),
+        transaction: None,
+    };
+    puppeteer_base
+        .tx_in_progress
+        .save(deps.as_mut().storage, &tx_state)
+        .unwrap();
+    puppeteer_base
+        .tx_queue
+        .save(
+            deps.as_mut().storage,
+            ("channel".to_string(), 1u64),
+            &drop_puppeteer_base::state::TxState {
+                seq_id: Some(1u64),
+                status: drop_puppeteer_base::state::TxStateStatus::WaitingForAck,
+                reply_to: Some("other_reply_to".to_string()),
+                transaction: None,
+            },
+        )
+        .unwrap();
+    crate::contract::reply(
+        deps.as_mut().into_empty(),
+        mock_env(),
+        cosmwasm_std::Reply {
+            id: ReplyMsg::SudoPayload.to_reply_id(),
+            result: cosmwasm_std::SubMsgResult::Ok(cosmwasm_std::SubMsgResponse {
+                events: vec![],
+                data: Some(
+                    to_json_binary(&neutron_sdk::bindings::msg::MsgSubmitTxResponse {
+                        sequence_id: 2u64,
+                        channel: "channel".to_string(),
+                    })
+                    .unwrap(),
+                ),
+            }),
+        },
+    )
+    .unwrap();
+    assert!(puppeteer_base
+        .tx_in_progress
+        .may_load(deps.as_ref().storage)
+        .unwrap()
+        .is_none());
+    let res = crate::contract::query(
+        deps.as_ref(),
+        mock_env(),
+        drop_puppeteer_base::msg::QueryMsg::TxQueue {},
+    )
+    .unwrap();
+    assert_eq!(
+        from_json::<Vec<drop_puppeteer_base::state::TxState>>(res).unwrap(),
+        vec![
+            drop_puppeteer_base::state::TxState {
+                seq_id: Some(1u64),
+                status: drop_puppeteer_base::state::TxStateStatus::WaitingForAck,
+                reply_to: Some("other_reply_to".to_string()),
+                transaction: None,
+            },
+            drop_puppeteer_base::state::TxState {
+                seq_id: Some(2u64),
+                status: drop_puppeteer_base::state::TxStateStatus::WaitingForAck,
+                reply_to: Some("some_reply_to".to_string()),
+                transaction: None,
+            },
+        ]
+    );
+    let res = crate::contract::query(
+        deps.as_ref(),
+        mock_env(),
+        drop_puppeteer_base::msg::QueryMsg::TxState {},
+    )
+    .unwrap();
+    assert_eq!(
+        from_json::<drop_puppeteer_base::state::TxState>(res).unwrap(),
+        drop_puppeteer_base::state::TxState {
+            seq_id: Some(1u64),
+            status: drop_puppeteer_base::state::TxStateStatus::WaitingForAck,
+            reply_to: Some("other_reply_to".to_string()),
+            transaction: None,
+        }
+    );
+}
+
+#[test]
+fn test_execute_redeem_share() {
+    let mut deps = mock_dependencies(&[]);
+    deps.querier.add_custom_query_response(|_| {
+        to_json_binary(&MinIbcFeeResponse {
+            min_fee: get_standard_fees(),
+        })
+        .unwrap()
+    });
+    let puppeteer_base = base_init(&mut deps.as_mut());
+    let msg = drop_staking_base::msg::puppeteer::ExecuteMsg::RedeemShares {
+        items: vec![drop_puppeteer_base::state::RedeemShareItem {
+            amount: Uint128::from(1000u128),
+            remote_denom: "remote_denom".to_string(),
+            local_denom: "local_denom".to_string(),
+        }],
+        reply_to: "some_reply_to".to_string(),
+    };
+    let env = mock_env();
+    let res = crate::contract::execute(
+        deps.as_mut(),
+        env,
+        mock_info("not_allowed_sender", &[]),
+        msg.clone(),
+    );
+    assert_eq!(
+        res.unwrap_err(),
+        drop_puppeteer_base::error::ContractError::Std(StdError::generic_err(
+            "Sender is not allowed"
+        ))
+    );
+    let res = crate::contract::execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("allowed_sender", &[]),
+        msg,
+    )
+    .unwrap();
+    let msg = crate::proto::liquidstaking::staking::v1beta1::MsgRedeemTokensforShares {
+        amount: Some(crate::proto::cosmos::base::v1beta1::Coin {
+            denom: "remote_denom".to_string(),
+            amount: "1000".to_string(),
+        }),
+        delegator_address: "ica_address".to_string(),
+    };
+    let mut buf = Vec::with_capacity(msg.encoded_len());
+    msg.encode(&mut buf).unwrap();
+    let any_msg = neutron_sdk::bindings::types::ProtobufAny {
+        type_url: "/cosmos.staking.v1beta1.MsgRedeemTokensForShares".to_string(),
+        value: Binary::from(buf),
+    };
+    assert_eq!(
+        res,
+        Response::new().add_submessage(SubMsg::reply_on_success(
+            CosmosMsg::Custom(NeutronMsg::submit_tx(
+                "connection_id".to_string(),
+                "DROP".to_string(),
+                vec![any_msg],
+                "".to_string(),
+                100u64,
+                get_standard_fees()
+            )),
+            ReplyMsg::SudoPayload.to_reply_id()
+        )).add_attributes(vec![("action", "redeem_share"), ("items", "[RedeemShareItem { amount: Uint128(1000), remote_denom: \"remote_denom\", local_denom: \"local_denom\" }]")])
+    );
+    let tx_state = puppeteer_base
+        .tx_in_progress
+        .load(deps.as_ref().storage)
+        .unwrap();
+    assert_eq!(
+        tx_state,
+        drop_puppeteer_base::state::TxState {
+            seq_id: None,
+            status: drop_puppeteer_base::state::TxStateStatus::InProgress,
+            reply_to: Some("some_reply_to".to_string()),
+            transaction: Some(drop_puppeteer_base::msg::Transaction::RedeemShares {
+                interchain_account_id: "DROP".to_string(),
+                items: vec![drop_puppeteer_base::state::RedeemShareItem {
+                    amount: Uint128::from(1000u128),
+                    remote_denom: "remote_denom".to_string(),
+                    local_denom: "local_denom".to_string()
+                }]
+            })
+        }
+    );
+}
+
+#[test]
+fn test_transaction_history() {
+    let mut deps = mock_dependencies(&[]);
+    let puppeteer_base = base_init(&mut deps.as_mut());
+    let redelegate = drop_puppeteer_base::msg::Transaction::Redelegate {
+        interchain_account_id: "DROP".to_string(),
+        validator_from: "validator_from".to_string(),
+        validator_to: "validator_to".to_string(),
+        denom: "remote_denom".to_string(),
+        amount: 100u128,
+    };
+    let undelegate = drop_puppeteer_base::msg::Transaction::Undelegate {
+        interchain_account_id: "DROP".to_string(),
+        items: vec![("validator".to_string(), Uint128::from(100u128))],
+        denom: "remote_denom".to_string(),
+        batch_id: 0u128,
+    };
+    for (seq_id, transaction) in [
+        (1u64, redelegate.clone()),
+        (2u64, undelegate.clone()),
+        (3u64, redelegate.clone()),
+    ] {
+        puppeteer_base
+            .tx_in_progress
+            .save(
+                deps.as_mut().storage,
+                &drop_puppeteer_base::state::TxState {
+                    seq_id: None,
+                    status: drop_puppeteer_base::state::TxStateStatus::InProgress,
+                    reply_to: Some("reply_to_contract".to_string()),
+                    transaction: Some(transaction),
+                },
+            )
+            .unwrap();
+        crate::contract::reply(
+            deps.as_mut().into_empty(),
+            mock_env(),
+            cosmwasm_std::Reply {
+                id: ReplyMsg::SudoPayload.to_reply_id(),
+                result: cosmwasm_std::SubMsgResult::Ok(cosmwasm_std::SubMsgResponse {
+                    events: vec![],
+                    data: Some(
+                        to_json_binary(&neutron_sdk::bindings::msg::MsgSubmitTxResponse {
+                            sequence_id: seq_id,
+                            channel: "channel".to_string(),
+                        })
+                        .unwrap(),
+                    ),
+                }),
+            },
+        )
+        .unwrap();
+    }
+    let mut env = mock_env();
+    env.block.height += 10;
+    crate::contract::sudo(
+        deps.as_mut(),
+        env,
+        SudoMsg::Timeout {
+            request: neutron_sdk::sudo::msg::RequestPacket {
+                sequence: Some(1u64),
+                source_port: Some("port".to_string()),
+                source_channel: Some("channel".to_string()),
+                destination_port: Some("destination_port".to_string()),
+                destination_channel: Some("destination_channel".to_string()),
+                data: None,
+                timeout_height: None,
+                timeout_timestamp: None,
+            },
+        },
+    )
+    .unwrap();
+    let height = mock_env().block.height;
+    let res = crate::contract::query(
+        deps.as_ref(),
+        mock_env(),
+        drop_puppeteer_base::msg::QueryMsg::TransactionHistory {
+            start_after: None,
+            limit: None,
+            kind: Some(drop_puppeteer_base::msg::TransactionKind::Redelegate),
+        },
+    )
+    .unwrap();
+    assert_eq!(
+        from_json::<Vec<drop_puppeteer_base::state::TxHistoryEntry>>(res).unwrap(),
+        vec![
+            drop_puppeteer_base::state::TxHistoryEntry {
+                id: 0u64,
+                channel_id: "channel".to_string(),
+                seq_id: 1u64,
+                transaction: redelegate.clone(),
+                submit_height: height,
+                result: drop_puppeteer_base::state::TxResult::Timeout,
+                result_height: Some(height + 10),
+            },
+            drop_puppeteer_base::state::TxHistoryEntry {
+                id: 2u64,
+                channel_id: "channel".to_string(),
+                seq_id: 3u64,
+                transaction: redelegate,
+                submit_height: height,
+                result: drop_puppeteer_base::state::TxResult::Pending,
+                result_height: None,
+            },
+        ]
+    );
+    let res = crate::contract::query(
+        deps.as_ref(),
+        mock_env(),
+        drop_puppeteer_base::msg::QueryMsg::TransactionHistory {
+            start_after: Some(0u64),
+            limit: Some(1u32),
+            kind: None,
+        },
+    )
+    .unwrap();
+    assert_eq!(
+        from_json::<Vec<drop_puppeteer_base::state::TxHistoryEntry>>(res).unwrap(),
+        vec![drop_puppeteer_base::state::TxHistoryEntry {
+            id: 1u64,
+            channel_id: "channel".to_string(),
+            seq_id: 2u64,
+            transaction: undelegate,
+            submit_height: height,
+            result: drop_puppeteer_base::state::TxResult::Pending,
+            result_height: None,
+        }]
+    );
+}
+
+#[test]
+fn test_transaction_history_pruning() {
+    let mut deps = mock_dependencies(&[]);
+    let puppeteer_base = base_init(&mut deps.as_mut());
+    let transaction = drop_puppeteer_base::msg::Transaction::Redelegate {
+        interchain_account_id: "DROP".to_string(),
+        validator_from: "validator_from".to_string(),
+        validator_to: "validator_to".to_string(),
+        denom: "remote_denom".to_string(),
+        amount: 100u128,
+    };
+    let entry = drop_puppeteer_base::state::TxHistoryEntry {
+        id: 0u64,
+        channel_id: "channel".to_string(),
+        seq_id: 1u64,
+        transaction: transaction.clone(),
+        submit_height: 1u64,
+        result: drop_puppeteer_base::state::TxResult::Success { answers: vec![] },
+        result_height: Some(2u64),
+    };
+    puppeteer_base
+        .tx_history
+        .save(deps.as_mut().storage, 0u64, &entry)
+        .unwrap();
+    puppeteer_base
+        .tx_history
+        .save(
+            deps.as_mut().storage,
+            1u64,
+            &drop_puppeteer_base::state::TxHistoryEntry {
+                id: 1u64,
+                seq_id: 2u64,
+                ..entry
+            },
+        )
+        .unwrap();
+    puppeteer_base
+        .tx_history_next_id
+        .save(
+            deps.as_mut().storage,
+            &drop_puppeteer_base::state::TX_HISTORY_MAX_SIZE,
+        )
+        .unwrap();
+    puppeteer_base
+        .tx_in_progress
+        .save(
+            deps.as_mut().storage,
+            &drop_puppeteer_base::state::TxState {
+                seq_id: None,
+                status: drop_puppeteer_base::state::TxStateStatus::InProgress,
+                reply_to: None,
+                transaction: Some(transaction),
+            },
+        )
+        .unwrap();
+    crate::contract::reply(
+        deps.as_mut().into_empty(),
+        mock_env(),
+        cosmwasm_std::Reply {
+            id: ReplyMsg::SudoPayload.to_reply_id(),
+            result: cosmwasm_std::SubMsgResult::Ok(cosmwasm_std::SubMsgResponse {
+                events: vec![],
+                data: Some(
+                    to_json_binary(&neutron_sdk::bindings::msg::MsgSubmitTxResponse {
+                        sequence_id: 3u64,
+                        channel: "channel".to_string(),
+                    })
+                    .unwrap(),
+                ),
+            }),
+        },
+    )
+    .unwrap();
+    let res = crate::contract::query(
+        deps.as_ref(),
+        mock_env(),
+        drop_puppeteer_base::msg::QueryMsg::TransactionHistory {
+            start_after: None,
+            limit: Some(1000u32),
+            kind: None,
+        },
+    )
+    .unwrap();
+    let ids: Vec<u64> = from_json::<Vec<drop_puppeteer_base::state::TxHistoryEntry>>(res)
+        .unwrap()
+        .into_iter()
+        .map(|entry| entry.id)
+        .collect();
+    assert_eq!(
+        ids,
+        vec![1u64, drop_puppeteer_base::state::TX_HISTORY_MAX_SIZE]
+    );
+}
+
+#[test]
+fn test_sudo_response_tx_not_in_queue() {
+    // Test that the contract returns an error if the tx is not waiting for ack
+    let mut deps = mock_dependencies(&[]);
+    let puppeteer_base = base_init(&mut deps.as_mut());
+    let msg = SudoMsg::Response {
+        request: neutron_sdk::sudo::msg::RequestPacket {
+            sequence: Some(1u64),
+            source_port: Some("source_port".to_string()),
+            source_channel: Some("source_channel".to_string()),
+            destination_port: Some("destination_port".to_string()),
+            destination_channel: Some("destination_channel".to_string()),
+            data: None,
+            timeout_height: None,
+            timeout_timestamp: None,
+        },
+        data: Binary::from(vec![]),
+    };
+    let env = mock_env();
+    puppeteer_base
+        .tx_queue
+        .save(
+            deps.as_mut().storage,
+            ("other_channel".to_string(), 1u64),
+            &drop_puppeteer_base::state::TxState {
+                seq_id: Some(1u64),
+                status: drop_puppeteer_base::state::TxStateStatus::WaitingForAck,
+                reply_to: Some("reply_to_contract".to_string()),
+                transaction: None,
+            },
+        )
+        .unwrap();
+    let res = crate::contract::sudo(deps.as_mut(), env, msg);
+    assert_eq!(
+        res.unwrap_err(),
+        NeutronError::Std(StdError::generic_err(
+            "Transaction 1 on channel source_channel not found in queue"
+        ))
+    );
+}
+
+#[test]
+fn test_sudo_kv_query_result() {
+    let mut deps = mock_dependencies(&[]);
+
+    let query_id = 1u64;
+
+    deps.querier
+        .add_query_response(query_id, build_interchain_query_response());
+
     let puppeteer_base = base_init(&mut deps.as_mut());
 
     let msg = SudoMsg::KVQueryResult { query_id };
@@ -649,6 +1542,143 @@ fn test_sudo_kv_query_result() {
     );
 }
 
+fn setup_delegations_chunks(
+    deps: &mut DepsMut<NeutronQuery>,
+    max_height_skew: u64,
+) -> PuppeteerBase<'static, Config, KVQueryType> {
+    let puppeteer_base = base_init(deps);
+    let mut config = get_base_config();
+    config.delegations_max_height_skew = max_height_skew;
+    puppeteer_base.config.save(deps.storage, &config).unwrap();
+    for (query_id, chunk_id) in [(1u64, 0u16), (2u64, 1u16)] {
+        puppeteer_base
+            .kv_queries
+            .save(deps.storage, query_id, &KVQueryType::DelegationsAndBalance)
+            .unwrap();
+        puppeteer_base
+            .delegations_and_balances_query_id_chunk
+            .save(deps.storage, query_id, &chunk_id)
+            .unwrap();
+    }
+    puppeteer_base
+}
+
+fn query_delegations_status(
+    deps: cosmwasm_std::Deps<NeutronQuery>,
+) -> drop_staking_base::msg::puppeteer::DelegationsStatusResponse {
+    from_json(
+        crate::contract::query(
+            deps,
+            mock_env(),
+            drop_puppeteer_base::msg::QueryMsg::Extension {
+                msg: drop_staking_base::msg::puppeteer::QueryExtMsg::DelegationsStatus {},
+            },
+        )
+        .unwrap(),
+    )
+    .unwrap()
+}
+
+#[test]
+fn test_sudo_kv_query_result_chunks_height_skew() {
+    let mut deps = mock_dependencies(&[]);
+    let puppeteer_base = setup_delegations_chunks(&mut deps.as_mut(), 10);
+    deps.querier
+        .add_query_response(1, build_interchain_query_response_at(100));
+    deps.querier
+        .add_query_response(2, build_interchain_query_response_at(105));
+    crate::contract::sudo(
+        deps.as_mut(),
+        mock_env(),
+        SudoMsg::KVQueryResult { query_id: 1 },
+    )
+    .unwrap();
+    assert_eq!(
+        query_delegations_status(deps.as_ref()),
+        drop_staking_base::msg::puppeteer::DelegationsStatusResponse {
+            chunk_heights: vec![(0, 100), (1, 0)],
+            last_complete_remote_height: None,
+            last_complete_age: None,
+            pending: vec![
+                drop_staking_base::msg::puppeteer::PendingDelegationsSnapshot {
+                    remote_height: 100,
+                    collected_chunks: vec![0],
+                    missing_chunks: vec![1],
+                }
+            ],
+        }
+    );
+    crate::contract::sudo(
+        deps.as_mut(),
+        mock_env(),
+        SudoMsg::KVQueryResult { query_id: 2 },
+    )
+    .unwrap();
+    let state = puppeteer_base
+        .delegations_and_balances
+        .load(&deps.storage, &100)
+        .unwrap();
+    assert_eq!(state.collected_chunks, vec![0, 1]);
+    assert_eq!(state.data.delegations.delegations.len(), 4);
+    assert_eq!(
+        query_delegations_status(deps.as_ref()),
+        drop_staking_base::msg::puppeteer::DelegationsStatusResponse {
+            chunk_heights: vec![(0, 100), (1, 105)],
+            last_complete_remote_height: Some(100),
+            last_complete_age: Some(0),
+            pending: vec![],
+        }
+    );
+}
+
+#[test]
+fn test_sudo_kv_query_result_abandoned_snapshot() {
+    let mut deps = mock_dependencies(&[]);
+    let puppeteer_base = setup_delegations_chunks(&mut deps.as_mut(), 10);
+    for height in [100u64, 120u64] {
+        deps.querier
+            .add_query_response(1, build_interchain_query_response_at(height));
+        crate::contract::sudo(
+            deps.as_mut(),
+            mock_env(),
+            SudoMsg::KVQueryResult { query_id: 1 },
+        )
+        .unwrap();
+    }
+    assert_eq!(query_delegations_status(deps.as_ref()).pending.len(), 2);
+    deps.querier
+        .add_query_response(2, build_interchain_query_response_at(125));
+    let res = crate::contract::sudo(
+        deps.as_mut(),
+        mock_env(),
+        SudoMsg::KVQueryResult { query_id: 2 },
+    )
+    .unwrap();
+    assert_eq!(
+        res,
+        Response::new().add_event(
+            Event::new("puppeteer-base-delegations_snapshot_abandoned").add_attributes(vec![
+                ("remote_height", "100"),
+                ("chunk_id", "1"),
+                ("chunk_remote_height", "125"),
+                ("collected_chunks", "[0]"),
+            ])
+        )
+    );
+    assert!(puppeteer_base
+        .delegations_and_balances
+        .may_load(&deps.storage, &100)
+        .unwrap()
+        .is_none());
+    assert_eq!(
+        puppeteer_base
+            .last_complete_delegations_and_balances_key
+            .load(&deps.storage)
+            .unwrap(),
+        120
+    );
+}
+
 #[test]
 fn test_sudo_response_ok() {
     let mut deps = mock_dependencies(&[]);
@@ -713,11 +1743,12 @@ fn test_sudo_response_ok() {
     };
     let env = mock_env();
     puppeteer_base
-        .tx_state
+        .tx_queue
         .save(
             deps.as_mut().storage,
+            ("source_channel".to_string(), 1u64),
             &drop_puppeteer_base::state::TxState {
-                seq_id: None,
+                seq_id: Some(1u64),
                 status: drop_puppeteer_base::state::TxStateStatus::WaitingForAck,
                 reply_to: Some("reply_to_contract".to_string()),
                 transaction: Some(transaction.clone()),
@@ -761,16 +1792,7 @@ fn test_sudo_response_ok() {
             channel_id: "channel".to_string(),
         }
     );
-    let state = puppeteer_base.tx_state.load(deps.as_ref().storage).unwrap();
-    assert_eq!(
-        state,
-        drop_puppeteer_base::state::TxState {
-            seq_id: None,
-            status: drop_puppeteer_base::state::TxStateStatus::Idle,
-            reply_to: None,
-            transaction: None,
-        }
-    );
+    assert!(puppeteer_base.tx_queue.is_empty(deps.as_ref().storage));
 }
 
 #[test]
@@ -799,11 +1821,12 @@ fn test_sudo_response_error() {
     };
     let env = mock_env();
     puppeteer_base
-        .tx_state
+        .tx_queue
         .save(
             deps.as_mut().storage,
+            ("source_channel".to_string(), 1u64),
             &drop_puppeteer_base::state::TxState {
-                seq_id: None,
+                seq_id: Some(1u64),
                 status: drop_puppeteer_base::state::TxStateStatus::WaitingForAck,
                 reply_to: Some("reply_to_contract".to_string()),
                 transaction: Some(transaction.clone()),
@@ -827,7 +1850,7 @@ fn test_sudo_response_error() {
                     ))
                 ))
                 .unwrap(),
-                funds: vec![]
+                funds: coins(1000, "remote_denom")
             }))
             .add_event(Event::new("puppeteer-sudo-error").add_attributes(vec![
                 ("action", "sudo_error"),
@@ -844,16 +1867,96 @@ fn test_sudo_response_error() {
             channel_id: "channel".to_string(),
         }
     );
-    let state = puppeteer_base.tx_state.load(deps.as_ref().storage).unwrap();
-    assert_eq!(
-        state,
-        drop_puppeteer_base::state::TxState {
-            seq_id: None,
-            status: drop_puppeteer_base::state::TxStateStatus::Idle,
-            reply_to: None,
-            transaction: None,
+    assert!(puppeteer_base.tx_queue.is_empty(deps.as_ref().storage));
+}
+
+fn tx_msg_data_fixture(
+    sdk_version: &str,
+) -> cosmos_sdk_proto::cosmos::base::abci::v1beta1::TxMsgData {
+    let undelegate_response =
+        crate::proto::liquidstaking::staking::v1beta1::MsgUndelegateResponse {
+            completion_time: Some(prost_types::Timestamp {
+                seconds: 1_700_000_000,
+                nanos: 0,
+            }),
         }
-    );
+        .encode_to_vec();
+    let delegate_response =
+        crate::proto::liquidstaking::staking::v1beta1::MsgDelegateResponse {}.encode_to_vec();
+    #[allow(deprecated)]
+    match sdk_version {
+        // ibc-go v4 hosts put responses into the deprecated data field
+        "0.45.16" => cosmos_sdk_proto::cosmos::base::abci::v1beta1::TxMsgData {
+            data: vec![
+                cosmos_sdk_proto::cosmos::base::abci::v1beta1::MsgData {
+                    msg_type: "/cosmos.staking.v1beta1.MsgUndelegate".to_string(),
+                    data: undelegate_response,
+                },
+                cosmos_sdk_proto::cosmos::base::abci::v1beta1::MsgData {
+                    msg_type: "/cosmos.staking.v1beta1.MsgDelegate".to_string(),
+                    data: delegate_response,
+                },
+            ],
+            msg_responses: vec![],
+        },
+        _ => cosmos_sdk_proto::cosmos::base::abci::v1beta1::TxMsgData {
+            data: vec![],
+            msg_responses: vec![
+                cosmos_sdk_proto::Any {
+                    type_url: "/cosmos.staking.v1beta1.MsgUndelegateResponse".to_string(),
+                    value: undelegate_response,
+                },
+                cosmos_sdk_proto::Any {
+                    type_url: "/cosmos.staking.v1beta1.MsgDelegateResponse".to_string(),
+                    value: delegate_response,
+                },
+            ],
+        },
+    }
+}
+
+#[test]
+fn test_get_answers_from_msg_data_sdk_versions() {
+    let deps = mock_dependencies(&[]);
+    for sdk_version in ["0.45.16", "0.47.10", "0.50.9"] {
+        let msg_data = cosmos_sdk_proto::cosmos::base::abci::v1beta1::TxMsgData::decode(
+            tx_msg_data_fixture(sdk_version).encode_to_vec().as_slice(),
+        )
+        .unwrap();
+        let answers =
+            crate::contract::get_answers_from_msg_data(deps.as_ref(), msg_data, sdk_version)
+                .unwrap();
+        assert_eq!(
+            answers,
+            vec![
+                drop_puppeteer_base::msg::ResponseAnswer::UndelegateResponse(
+                    drop_puppeteer_base::proto::MsgUndelegateResponse {
+                        completion_time: Some(drop_puppeteer_base::proto::Timestamp {
+                            seconds: 1_700_000_000,
+                            nanos: 0,
+                        }),
+                    }
+                ),
+                drop_puppeteer_base::msg::ResponseAnswer::DelegateResponse(
+                    drop_puppeteer_base::proto::MsgDelegateResponse {}
+                ),
+            ],
+            "sdk {sdk_version}"
+        );
+    }
+}
+
+#[test]
+fn test_get_answers_from_msg_data_sdk_0_50_ignores_data() {
+    let deps = mock_dependencies(&[]);
+    #[allow(deprecated)]
+    let msg_data = cosmos_sdk_proto::cosmos::base::abci::v1beta1::TxMsgData {
+        data: tx_msg_data_fixture("0.45.16").data,
+        msg_responses: vec![],
+    };
+    let answers =
+        crate::contract::get_answers_from_msg_data(deps.as_ref(), msg_data, "0.50.9").unwrap();
+    assert!(answers.is_empty());
 }
 
 #[test]
@@ -887,7 +1990,7 @@ fn test_sudo_response_timeout() {
     let request = neutron_sdk::sudo::msg::RequestPacket {
         sequence: Some(1u64),
         source_port: Some("source_port".to_string()),
-        source_channel: Some("source_channel".to_string()),
+        source_channel: Some("channel".to_string()),
         destination_port: Some("destination_port".to_string()),
         destination_channel: Some("destination_channel".to_string()),
         data: None,
@@ -905,11 +2008,12 @@ fn test_sudo_response_timeout() {
     };
     let env = mock_env();
     puppeteer_base
-        .tx_state
+        .tx_queue
         .save(
             deps.as_mut().storage,
+            ("channel".to_string(), 1u64),
             &drop_puppeteer_base::state::TxState {
-                seq_id: None,
+                seq_id: Some(1u64),
                 status: drop_puppeteer_base::state::TxStateStatus::WaitingForAck,
                 reply_to: Some("reply_to_contract".to_string()),
                 transaction: Some(transaction.clone()),
@@ -933,7 +2037,7 @@ fn test_sudo_response_timeout() {
                     ))
                 ))
                 .unwrap(),
-                funds: vec![]
+                funds: coins(1000, "remote_denom")
             }))
             .add_event(
                 Event::new("puppeteer-sudo-timeout")
@@ -942,18 +2046,240 @@ fn test_sudo_response_timeout() {
     );
     let ica = puppeteer_base.ica.load(deps.as_ref().storage).unwrap();
     assert_eq!(ica, drop_helpers::ica::IcaState::Timeout);
-    let state = puppeteer_base.tx_state.load(deps.as_ref().storage).unwrap();
+    assert!(puppeteer_base.tx_queue.is_empty(deps.as_ref().storage));
+}
+
+#[test]
+fn test_sudo_timeout_purges_channel_queue() {
+    let mut deps = mock_dependencies(&[]);
+    let puppeteer_base = base_init(&mut deps.as_mut());
+    let request = neutron_sdk::sudo::msg::RequestPacket {
+        sequence: Some(1u64),
+        source_port: Some("source_port".to_string()),
+        source_channel: Some("channel".to_string()),
+        destination_port: Some("destination_port".to_string()),
+        destination_channel: Some("destination_channel".to_string()),
+        data: None,
+        timeout_height: None,
+        timeout_timestamp: None,
+    };
+    let transaction = drop_puppeteer_base::msg::Transaction::IBCTransfer {
+        denom: "remote_denom".to_string(),
+        amount: 1000u128,
+        recipient: "recipient".to_string(),
+        reason: drop_puppeteer_base::msg::IBCTransferReason::Stake,
+    };
+    for (channel, seq_id, reply_to) in [
+        ("channel", 1u64, "reply_to_contract"),
+        ("channel", 2u64, "other_contract"),
+        ("other_channel", 1u64, "reply_to_contract"),
+    ] {
+        puppeteer_base
+            .tx_queue
+            .save(
+                deps.as_mut().storage,
+                (channel.to_string(), seq_id),
+                &drop_puppeteer_base::state::TxState {
+                    seq_id: Some(seq_id),
+                    status: drop_puppeteer_base::state::TxStateStatus::WaitingForAck,
+                    reply_to: Some(reply_to.to_string()),
+                    transaction: Some(transaction.clone()),
+                },
+            )
+            .unwrap();
+    }
+    let res = crate::contract::sudo(
+        deps.as_mut(),
+        mock_env(),
+        SudoMsg::Timeout {
+            request: request.clone(),
+        },
+    )
+    .unwrap();
+    let hook = |contract: &str,
+                request_id: u64,
+                request: neutron_sdk::sudo::msg::RequestPacket,
+                details: &str,
+                funds: Vec<cosmwasm_std::Coin>| {
+        CosmosMsg::Wasm(cosmwasm_std::WasmMsg::Execute {
+            contract_addr: contract.to_string(),
+            msg: to_json_binary(&drop_staking_base::msg::core::ExecuteMsg::PuppeteerHook(
+                Box::new(drop_puppeteer_base::msg::ResponseHookMsg::Error(
+                    drop_puppeteer_base::msg::ResponseHookErrorMsg {
+                        request_id,
+                        request,
+                        transaction: transaction.clone(),
+                        details: details.to_string(),
+                    },
+                )),
+            ))
+            .unwrap(),
+            funds,
+        })
+    };
     assert_eq!(
-        state,
-        drop_puppeteer_base::state::TxState {
-            seq_id: None,
-            status: drop_puppeteer_base::state::TxStateStatus::Idle,
-            reply_to: None,
-            transaction: None,
+        res,
+        Response::new()
+            .add_message(hook(
+                "reply_to_contract",
+                1,
+                request.clone(),
+                "Timeout",
+                coins(1000, "remote_denom")
+            ))
+            .add_message(hook(
+                "other_contract",
+                2,
+                neutron_sdk::sudo::msg::RequestPacket {
+                    sequence: Some(2u64),
+                    ..request.clone()
+                },
+                "Channel closed",
+                vec![]
+            ))
+            .add_event(Event::new("puppeteer-sudo-timeout").add_attributes(vec![
+                ("action", "sudo_timeout"),
+                ("request_id", "1"),
+                ("purged_txs", "1"),
+            ]))
+    );
+    let queue = puppeteer_base
+        .tx_queue
+        .keys(
+            deps.as_ref().storage,
+            None,
+            None,
+            cosmwasm_std::Order::Ascending,
+        )
+        .collect::<cosmwasm_std::StdResult<Vec<_>>>()
+        .unwrap();
+    assert_eq!(queue, vec![("other_channel".to_string(), 1u64)]);
+}
+
+#[test]
+fn test_sudo_timeout_transfer_channel_keeps_queue() {
+    let mut deps = mock_dependencies(&[]);
+    let puppeteer_base = base_init(&mut deps.as_mut());
+    let request = neutron_sdk::sudo::msg::RequestPacket {
+        sequence: Some(1u64),
+        source_port: Some("transfer".to_string()),
+        source_channel: Some("transfer_channel".to_string()),
+        destination_port: Some("transfer".to_string()),
+        destination_channel: Some("destination_channel".to_string()),
+        data: None,
+        timeout_height: None,
+        timeout_timestamp: None,
+    };
+    let transaction = drop_puppeteer_base::msg::Transaction::IBCTransfer {
+        denom: "remote_denom".to_string(),
+        amount: 1000u128,
+        recipient: "recipient".to_string(),
+        reason: drop_puppeteer_base::msg::IBCTransferReason::Stake,
+    };
+    for seq_id in [1u64, 2u64] {
+        puppeteer_base
+            .tx_queue
+            .save(
+                deps.as_mut().storage,
+                ("transfer_channel".to_string(), seq_id),
+                &drop_puppeteer_base::state::TxState {
+                    seq_id: Some(seq_id),
+                    status: drop_puppeteer_base::state::TxStateStatus::WaitingForAck,
+                    reply_to: Some("reply_to_contract".to_string()),
+                    transaction: Some(transaction.clone()),
+                },
+            )
+            .unwrap();
+    }
+    let res = crate::contract::sudo(
+        deps.as_mut(),
+        mock_env(),
+        SudoMsg::Timeout {
+            request: request.clone(),
+        },
+    )
+    .unwrap();
+    assert_eq!(
+        res,
+        Response::new()
+            .add_message(CosmosMsg::Wasm(cosmwasm_std::WasmMsg::Execute {
+                contract_addr: "reply_to_contract".to_string(),
+                msg: to_json_binary(&drop_staking_base::msg::core::ExecuteMsg::PuppeteerHook(
+                    Box::new(drop_puppeteer_base::msg::ResponseHookMsg::Error(
+                        drop_puppeteer_base::msg::ResponseHookErrorMsg {
+                            request_id: 1,
+                            request,
+                            transaction,
+                            details: "Timeout".to_string(),
+                        },
+                    )),
+                ))
+                .unwrap(),
+                funds: coins(1000, "remote_denom"),
+            }))
+            .add_event(
+                Event::new("puppeteer-sudo-timeout")
+                    .add_attributes(vec![("action", "sudo_timeout"), ("request_id", "1"),])
+            )
+    );
+    let queue = puppeteer_base
+        .tx_queue
+        .keys(
+            deps.as_ref().storage,
+            None,
+            None,
+            cosmwasm_std::Order::Ascending,
+        )
+        .collect::<cosmwasm_std::StdResult<Vec<_>>>()
+        .unwrap();
+    assert_eq!(queue, vec![("transfer_channel".to_string(), 2u64)]);
+    assert_eq!(
+        puppeteer_base.ica.load(deps.as_ref().storage).unwrap(),
+        drop_helpers::ica::IcaState::Registered {
+            ica_address: "ica_address".to_string(),
+            port_id: "port".to_string(),
+            channel_id: "channel".to_string(),
         }
     );
 }
 
+#[test]
+fn test_query_redelegations() {
+    let mut deps = mock_dependencies(&[]);
+    let env = mock_env();
+    let now = env.block.time.seconds();
+    drop_staking_base::state::puppeteer::REDELEGATIONS
+        .save(
+            deps.as_mut().storage,
+            ("valoper1".to_string(), "valoper2".to_string()),
+            &vec![now - 10, now + 10, now + 20],
+        )
+        .unwrap();
+    drop_staking_base::state::puppeteer::REDELEGATIONS
+        .save(
+            deps.as_mut().storage,
+            ("valoper2".to_string(), "valoper3".to_string()),
+            &vec![now],
+        )
+        .unwrap();
+    let res = crate::contract::query(
+        deps.as_ref(),
+        env,
+        drop_puppeteer_base::msg::QueryMsg::Extension {
+            msg: drop_staking_base::msg::puppeteer::QueryExtMsg::Redelegations {},
+        },
+    )
+    .unwrap();
+    assert_eq!(
+        from_json::<Vec<drop_staking_base::msg::puppeteer::RedelegationEntries>>(res).unwrap(),
+        vec![drop_staking_base::msg::puppeteer::RedelegationEntries {
+            validator_from: "valoper1".to_string(),
+            validator_to: "valoper2".to_string(),
+            completion_times: vec![now + 10, now + 20],
+        }]
+    );
+}
+
 mod register_delegations_and_balance_query {
     use cosmwasm_std::{testing::MockApi, MemoryStorage, OwnedDeps, StdResult};
     use drop_helpers::testing::WasmMockQuerier;
@@ -1163,6 +2489,11 @@ fn get_base_config() -> Config {
         transfer_channel_id: "transfer_channel_id".to_string(),
         sdk_version: "0.47.10".to_string(),
         timeout: 100u64,
+        ica_recovery: None,
+        query_periods: QueryPeriods::default(),
+        query_deposit_receiver: None,
+        delegations_max_height_skew: 0,
+        max_memo_length: 256,
     }
 }
 
@@ -5,8 +5,10 @@ use drop_helpers::answer::response;
 use drop_helpers::pause::{is_paused, pause_guard, set_pause, unpause, PauseInfoResponse};
 use drop_staking_base::error::rewards_manager::{ContractError, ContractResult};
 use drop_staking_base::msg::reward_handler::HandlerExecuteMsg;
-use drop_staking_base::msg::rewards_manager::{ExecuteMsg, InstantiateMsg, MigrateMsg, QueryMsg};
-use drop_staking_base::state::rewards_manager::{HandlerConfig, REWARDS_HANDLERS};
+use drop_staking_base::msg::rewards_manager::{
+    ExecuteMsg, InstantiateMsg, MigrateMsg, PendingRewardsResponseItem, QueryMsg,
+};
+use drop_staking_base::state::rewards_manager::{HandlerConfig, LAST_EXCHANGE, REWARDS_HANDLERS};
 use neutron_sdk::bindings::{msg::NeutronMsg, query::NeutronQuery};
 
 const CONTRACT_NAME: &str = concat!("crates.io:drop-staking__", env!("CARGO_PKG_NAME"));
@@ -35,6 +37,7 @@ pub fn query(deps: Deps, env: Env, msg: QueryMsg) -> StdResult<Binary> {
     match msg {
         QueryMsg::Ownership {} => Ok(to_json_binary(&get_ownership(deps.storage)?)?),
         QueryMsg::Handlers {} => query_handlers(deps, env),
+        QueryMsg::PendingRewards {} => query_pending_rewards(deps, env),
         QueryMsg::PauseInfo {} => query_pause_info(deps),
     }
 }
@@ -58,6 +61,35 @@ fn query_handlers(deps: Deps, _env: Env) -> StdResult<Binary> {
     to_json_binary(&handlers)
 }
 
+fn query_pending_rewards(deps: Deps, env: Env) -> StdResult<Binary> {
+    let handlers: Vec<HandlerConfig> = REWARDS_HANDLERS
+        .range(deps.storage, None, None, Order::Ascending)
+        .map(|item| item.map(|(_key, value)| value))
+        .collect::<StdResult<_>>()?;
+
+    let mut pending = vec![];
+    for handler in handlers {
+        let balance = deps
+            .querier
+            .query_balance(env.contract.address.to_string(), &handler.denom)?
+            .amount;
+        let last_exchange = LAST_EXCHANGE.may_load(deps.storage, handler.denom.clone())?;
+        let next_exchange = last_exchange.map_or(0, |last| last.saturating_add(handler.cooldown));
+        pending.push(PendingRewardsResponseItem {
+            exchangeable: !balance.is_zero()
+                && balance >= handler.min_rewards
+                && env.block.time.seconds() >= next_exchange,
+            denom: handler.denom,
+            balance,
+            min_rewards: handler.min_rewards,
+            last_exchange,
+            next_exchange,
+        });
+    }
+
+    to_json_binary(&pending)
+}
+
 #[cfg_attr(not(feature = "library"), cosmwasm_std::entry_point)]
 pub fn execute(
     deps: DepsMut,
@@ -122,6 +154,7 @@ fn exec_add_handler(
             attr("denom", config.denom),
             attr("address", config.address),
             attr("min_rewards", config.min_rewards.to_string()),
+            attr("cooldown", config.cooldown.to_string()),
         ],
     ))
 }
@@ -134,6 +167,7 @@ fn exec_remove_handler(
     cw_ownable::assert_owner(deps.storage, &info.sender)?;
 
     REWARDS_HANDLERS.remove(deps.storage, denom.clone());
+    LAST_EXCHANGE.remove(deps.storage, denom.clone());
 
     Ok(response(
         "remove_handler",
@@ -146,14 +180,23 @@ fn exec_exchange_rewards(
     deps: DepsMut,
     env: Env,
     _info: MessageInfo,
-    denoms: Vec<String>,
+    denoms: Option<Vec<String>>,
 ) -> ContractResult<Response> {
     pause_guard(deps.storage)?;
 
     let mut messages: Vec<CosmosMsg> = Vec::new();
     let mut attrs: Vec<Attribute> = Vec::new();
     let mut coins = vec![];
-    ensure!(!denoms.is_empty(), ContractError::EmptyDenomsList);
+    let denoms = match denoms {
+        Some(denoms) => {
+            ensure!(!denoms.is_empty(), ContractError::EmptyDenomsList);
+            denoms
+        }
+        None => REWARDS_HANDLERS
+            .keys(deps.storage, None, None, Order::Ascending)
+            .collect::<StdResult<_>>()?,
+    };
+    let now = env.block.time.seconds();
     for denom in &denoms {
         let balance = deps
             .querier
@@ -164,10 +207,18 @@ fn exec_exchange_rewards(
         if REWARDS_HANDLERS.has(deps.storage, denom.clone()) {
             let handler = REWARDS_HANDLERS.load(deps.storage, denom.clone())?;
 
-            if amount < handler.min_rewards {
+            if amount.is_zero() || amount < handler.min_rewards {
                 continue;
             }
 
+            if let Some(last_exchange) = LAST_EXCHANGE.may_load(deps.storage, denom.clone())? {
+                if now < last_exchange.saturating_add(handler.cooldown) {
+                    attrs.push(attr("cooldown", denom));
+                    continue;
+                }
+            }
+            LAST_EXCHANGE.save(deps.storage, denom.clone(), &now)?;
+
             let exchange_rewards_msg = CosmosMsg::Wasm(WasmMsg::Execute {
                 contract_addr: handler.address,
                 msg: to_json_binary(&HandlerExecuteMsg::Exchange {})?,
@@ -8,8 +8,8 @@ use cw_multi_test::{custom_app, App, Contract, ContractWrapper, Executor};
 use drop_helpers::answer::{attr_coin, response};
 use drop_helpers::pause::PauseInfoResponse;
 use drop_staking_base::msg::reward_handler::HandlerExecuteMsg;
-use drop_staking_base::msg::rewards_manager::QueryMsg;
 use drop_staking_base::msg::rewards_manager::{ExecuteMsg, InstantiateMsg};
+use drop_staking_base::msg::rewards_manager::{PendingRewardsResponseItem, QueryMsg};
 use drop_staking_base::state::rewards_manager::HandlerConfig;
 
 const OWNER_ADDR: &str = "owner_address";
@@ -296,7 +296,7 @@ fn test_paused_error() {
             Addr::unchecked(OWNER_ADDR),
             rewards_manager_contract.clone(),
             &ExecuteMsg::ExchangeRewards {
-                denoms: vec!["ueth".to_string()],
+                denoms: Some(vec!["ueth".to_string()]),
             },
             &[],
         )
@@ -326,6 +326,7 @@ fn test_add_remove_handler() {
         address: handler_contract.to_string(),
         denom: "ueth".to_string(),
         min_rewards: Uint128::zero(),
+        cooldown: 0,
     };
 
     let res = app
@@ -357,6 +358,7 @@ fn test_add_remove_handler() {
                 "min_rewards".to_string(),
                 handler_config.min_rewards.to_string()
             ),
+            Attribute::new("cooldown".to_string(), "0".to_string()),
         ]
     );
 
@@ -370,7 +372,8 @@ fn test_add_remove_handler() {
         vec![HandlerConfig {
             address: handler_config.address.clone(),
             denom: handler_config.denom.clone(),
-            min_rewards: Uint128::zero()
+            min_rewards: Uint128::zero(),
+            cooldown: 0,
         }]
     );
 
@@ -451,6 +454,7 @@ fn test_handler_call() {
         address: handler_contract.to_string(),
         denom: "ueth".to_string(),
         min_rewards: Uint128::zero(),
+        cooldown: 0,
     };
 
     let _res = app
@@ -469,7 +473,7 @@ fn test_handler_call() {
             Addr::unchecked(OWNER_ADDR),
             rewards_manager_contract.clone(),
             &ExecuteMsg::ExchangeRewards {
-                denoms: vec!["ueth".to_string()],
+                denoms: Some(vec!["ueth".to_string()]),
             },
             &[],
         )
@@ -517,6 +521,7 @@ fn test_empty_denoms_list() {
         address: handler_contract.to_string(),
         denom: "ueth".to_string(),
         min_rewards: Uint128::zero(),
+        cooldown: 0,
     };
 
     let _res = app
@@ -533,7 +538,9 @@ fn test_empty_denoms_list() {
     let res = app.execute_contract(
         Addr::unchecked(OWNER_ADDR),
         rewards_manager_contract.clone(),
-        &ExecuteMsg::ExchangeRewards { denoms: vec![] },
+        &ExecuteMsg::ExchangeRewards {
+            denoms: Some(vec![]),
+        },
         &[],
     );
     assert!(res.is_err());
@@ -582,12 +589,14 @@ fn test_two_handlers_call() {
         address: ueth_handler_contract.to_string(),
         denom: "ueth".to_string(),
         min_rewards: Uint128::zero(),
+        cooldown: 0,
     };
 
     let untrn_handler_config = HandlerConfig {
         address: untrn_handler_contract.to_string(),
         denom: "untrn".to_string(),
         min_rewards: Uint128::zero(),
+        cooldown: 0,
     };
 
     let _res = app
@@ -617,7 +626,7 @@ fn test_two_handlers_call() {
             Addr::unchecked(OWNER_ADDR),
             rewards_manager_contract.clone(),
             &ExecuteMsg::ExchangeRewards {
-                denoms: vec!["ueth".to_string(), "untrn".to_string()],
+                denoms: Some(vec!["ueth".to_string(), "untrn".to_string()]),
             },
             &[],
         )
@@ -647,3 +656,172 @@ fn test_two_handlers_call() {
         ]
     );
 }
+
+#[test]
+fn test_exchange_all_handlers_with_cooldown() {
+    let mut app = mock_app();
+
+    let sender_address = Addr::unchecked(SENDER_ADDR);
+
+    let ueth_handler_contract = instantiate_handler_contract(&mut app);
+    let untrn_handler_contract = instantiate_handler_contract(&mut app);
+
+    let rewards_manager_code_id = app.store_code(rewards_manager_contract());
+
+    let rewards_manager_contract = instantiate_rewards_manager_contract(
+        &mut app,
+        rewards_manager_code_id,
+        InstantiateMsg {
+            owner: OWNER_ADDR.to_string(),
+        },
+    );
+
+    for config in [
+        HandlerConfig {
+            address: ueth_handler_contract.to_string(),
+            denom: "ueth".to_string(),
+            min_rewards: Uint128::from(50u128),
+            cooldown: 100,
+        },
+        HandlerConfig {
+            address: untrn_handler_contract.to_string(),
+            denom: "untrn".to_string(),
+            min_rewards: Uint128::zero(),
+            cooldown: 0,
+        },
+    ] {
+        app.execute_contract(
+            Addr::unchecked(OWNER_ADDR),
+            rewards_manager_contract.clone(),
+            &ExecuteMsg::AddHandler { config },
+            &[],
+        )
+        .unwrap();
+    }
+
+    app.send_tokens(
+        sender_address.clone(),
+        rewards_manager_contract.clone(),
+        &coins(40, "ueth"),
+    )
+    .unwrap();
+
+    let pending: Vec<PendingRewardsResponseItem> = app
+        .wrap()
+        .query_wasm_smart(
+            rewards_manager_contract.clone(),
+            &QueryMsg::PendingRewards {},
+        )
+        .unwrap();
+    assert_eq!(
+        pending,
+        vec![
+            PendingRewardsResponseItem {
+                denom: "ueth".to_string(),
+                balance: Uint128::from(40u128),
+                min_rewards: Uint128::from(50u128),
+                last_exchange: None,
+                next_exchange: 0,
+                exchangeable: false,
+            },
+            PendingRewardsResponseItem {
+                denom: "untrn".to_string(),
+                balance: Uint128::zero(),
+                min_rewards: Uint128::zero(),
+                last_exchange: None,
+                next_exchange: 0,
+                exchangeable: false,
+            },
+        ]
+    );
+
+    app.send_tokens(
+        sender_address.clone(),
+        rewards_manager_contract.clone(),
+        &coins(60, "ueth"),
+    )
+    .unwrap();
+
+    let res = app
+        .execute_contract(
+            Addr::unchecked(SENDER_ADDR),
+            rewards_manager_contract.clone(),
+            &ExecuteMsg::ExchangeRewards { denoms: None },
+            &[],
+        )
+        .unwrap();
+    assert_eq!(
+        res.events[2].attributes[1..],
+        vec![
+            Attribute::new("total_denoms", "2"),
+            Attribute::new(
+                "coins",
+                format!("{:?}", vec![Coin::new(100, "ueth"), Coin::new(0, "untrn")])
+            ),
+        ]
+    );
+    assert_eq!(
+        res.events[1].attributes[1..],
+        vec![Attribute::new("denom", "ueth")]
+    );
+    assert_eq!(
+        res.events[4].attributes[2..],
+        vec![Attribute::new("received_funds", "100ueth")]
+    );
+
+    let now = app.block_info().time.seconds();
+    app.send_tokens(
+        sender_address,
+        rewards_manager_contract.clone(),
+        &coins(100, "ueth"),
+    )
+    .unwrap();
+
+    let pending: Vec<PendingRewardsResponseItem> = app
+        .wrap()
+        .query_wasm_smart(
+            rewards_manager_contract.clone(),
+            &QueryMsg::PendingRewards {},
+        )
+        .unwrap();
+    assert_eq!(
+        pending[0],
+        PendingRewardsResponseItem {
+            denom: "ueth".to_string(),
+            balance: Uint128::from(100u128),
+            min_rewards: Uint128::from(50u128),
+            last_exchange: Some(now),
+            next_exchange: now + 100,
+            exchangeable: false,
+        }
+    );
+
+    let res = app
+        .execute_contract(
+            Addr::unchecked(SENDER_ADDR),
+            rewards_manager_contract.clone(),
+            &ExecuteMsg::ExchangeRewards { denoms: None },
+            &[],
+        )
+        .unwrap();
+    assert_eq!(res.events.len(), 3);
+    assert_eq!(
+        res.events[1].attributes[1..],
+        vec![Attribute::new("cooldown", "ueth")]
+    );
+
+    app.update_block(|block| block.time = block.time.plus_seconds(100));
+
+    let res = app
+        .execute_contract(
+            Addr::unchecked(SENDER_ADDR),
+            rewards_manager_contract.clone(),
+            &ExecuteMsg::ExchangeRewards { denoms: None },
+            &[],
+        )
+        .unwrap();
+    assert_eq!(
+        res.events[4].attributes[2..],
+        vec![Attribute::new("received_funds", "100ueth")]
+    );
+}
@@ -1,11 +1,15 @@
-use cosmwasm_std::{attr, entry_point, to_json_binary, BankMsg, Coin, CosmosMsg, Deps, Uint128};
+use cosmwasm_std::{
+    attr, entry_point, from_json, to_json_binary, BankMsg, Coin, CosmosMsg, Deps, Uint128, WasmMsg,
+};
 use cosmwasm_std::{Binary, DepsMut, Env, MessageInfo, Response};
-use drop_helpers::answer::response;
+use drop_helpers::answer::{attr_coin, response};
 use drop_staking_base::{
     error::splitter::{ContractError, ContractResult},
-    msg::splitter::{ExecuteMsg, InstantiateMsg, MigrateMsg, QueryMsg},
+    msg::splitter::{DenomDistribution, ExecuteMsg, InstantiateMsg, MigrateMsg, QueryMsg},
     state::splitter::{Config, CONFIG},
 };
+use serde::de::IgnoredAny;
+use std::collections::BTreeMap;
 
 const CONTRACT_NAME: &str = concat!("crates.io:drop-staking__", env!("CARGO_PKG_NAME"));
 const CONTRACT_VERSION: &str = env!("CARGO_PKG_VERSION");
@@ -25,11 +29,15 @@ pub fn instantiate(
 }
 
 #[cfg_attr(not(feature = "library"), entry_point)]
-pub fn query(deps: Deps, _env: Env, msg: QueryMsg) -> ContractResult<Binary> {
+pub fn query(deps: Deps, env: Env, msg: QueryMsg) -> ContractResult<Binary> {
     match msg {
         QueryMsg::Config {} => {
             to_json_binary(&CONFIG.load(deps.storage)?).map_err(ContractError::from)
         }
+        QueryMsg::Preview {} => {
+            let config = CONFIG.load(deps.storage)?;
+            to_json_binary(&calc_distributions(deps, &env, &config)?).map_err(ContractError::from)
+        }
         QueryMsg::Ownership {} => query_ownership(deps),
     }
 }
@@ -69,35 +77,108 @@ pub fn execute_update_config(
 
 pub fn execute_distribute(deps: DepsMut, env: Env, _info: MessageInfo) -> ContractResult<Response> {
     let config: Config = CONFIG.load(deps.storage)?;
-    let mut attrs = vec![];
     let total_share: Uint128 = config.receivers.iter().map(|(_, share)| share).sum();
-    if total_share.is_zero() {
-        return Err(ContractError::NoShares {});
-    }
-    attrs.push(attr("total_shares", total_share));
-    let balance = deps
-        .querier
-        .query_balance(env.contract.address.to_string(), config.denom.to_string())?
-        .amount;
-    if balance < total_share {
+    let distributions = calc_distributions(deps.as_ref(), &env, &config)?;
+    if distributions.is_empty() {
         return Err(ContractError::InsufficientFunds {});
     }
-    let k = balance / total_share;
-    let messages = config
+    let mut attrs = vec![attr("total_shares", total_share)];
+    let mut coins: Vec<Vec<Coin>> = vec![vec![]; config.receivers.len()];
+    let mut dust_coins = vec![];
+    for distribution in distributions {
+        for (i, (receiver, amount)) in distribution.amounts.into_iter().enumerate() {
+            if distribution.denom == config.denom {
+                attrs.push(attr(&receiver, amount));
+            } else {
+                attrs.push(attr_coin(&receiver, amount, &distribution.denom));
+            }
+            if !amount.is_zero() {
+                coins[i].push(Coin::new(amount.u128(), &distribution.denom));
+            }
+        }
+        if !distribution.dust.is_zero() {
+            attrs.push(attr_coin("dust", distribution.dust, &distribution.denom));
+            if config.dust_receiver.is_some() {
+                dust_coins.push(Coin::new(distribution.dust.u128(), distribution.denom));
+            }
+        }
+    }
+    let mut messages = config
         .receivers
         .iter()
-        .map(|(receiver, share)| {
-            let amount = (share * k).u128();
-            attrs.push(attr(receiver, amount.to_string()));
-            CosmosMsg::Bank(BankMsg::Send {
-                to_address: receiver.to_string(),
-                amount: vec![Coin::new(amount, config.denom.to_string())],
-            })
+        .zip(coins)
+        .filter(|(_, amount)| !amount.is_empty())
+        .map(|((receiver, _), mut amount)| {
+            // the sdk only accepts coins sorted by denom
+            amount.sort_by(|a, b| a.denom.cmp(&b.denom));
+            let payload = config
+                .receiver_msgs
+                .iter()
+                .find(|(address, _)| address == receiver);
+            match payload {
+                Some((_, msg)) => CosmosMsg::Wasm(WasmMsg::Execute {
+                    contract_addr: receiver.to_string(),
+                    msg: msg.clone(),
+                    funds: amount,
+                }),
+                None => CosmosMsg::Bank(BankMsg::Send {
+                    to_address: receiver.to_string(),
+                    amount,
+                }),
+            }
         })
         .collect::<Vec<_>>();
+    if let Some(dust_receiver) = config.dust_receiver.filter(|_| !dust_coins.is_empty()) {
+        attrs.push(attr("dust_receiver", &dust_receiver));
+        dust_coins.sort_by(|a, b| a.denom.cmp(&b.denom));
+        messages.push(CosmosMsg::Bank(BankMsg::Send {
+            to_address: dust_receiver,
+            amount: dust_coins,
+        }));
+    }
     Ok(response("execute-distribute", CONTRACT_NAME, attrs).add_messages(messages))
 }
 
+/// Splits the balance of every configured denom between the receivers proportionally to their
+/// shares, skipping denoms below the minimal distribution
+fn calc_distributions(
+    deps: Deps,
+    env: &Env,
+    config: &Config,
+) -> ContractResult<Vec<DenomDistribution>> {
+    let total_share: Uint128 = config.receivers.iter().map(|(_, share)| share).sum();
+    if total_share.is_zero() {
+        return Err(ContractError::NoShares {});
+    }
+    let mut distributions = vec![];
+    for denom in std::iter::once(&config.denom).chain(config.extra_denoms.iter()) {
+        let balance = deps
+            .querier
+            .query_balance(env.contract.address.to_string(), denom)?
+            .amount;
+        if balance.is_zero() || balance < config.min_distribution {
+            continue;
+        }
+        let amounts: Vec<(String, Uint128)> = config
+            .receivers
+            .iter()
+            .map(|(receiver, share)| {
+                (
+                    receiver.to_string(),
+                    balance.multiply_ratio(*share, total_share),
+                )
+            })
+            .collect();
+        let distributed: Uint128 = amounts.iter().map(|(_, amount)| amount).sum();
+        distributions.push(DenomDistribution {
+            denom: denom.to_string(),
+            amounts,
+            dust: balance - distributed,
+        });
+    }
+    Ok(distributions)
+}
+
 #[cfg_attr(not(feature = "library"), entry_point)]
 pub fn migrate(deps: DepsMut, _env: Env, _msg: MigrateMsg) -> ContractResult<Response> {
     let version: semver::Version = CONTRACT_VERSION.parse()?;
@@ -118,5 +199,34 @@ fn verify_config(deps: Deps, config: &Config) -> ContractResult<()> {
             return Err(ContractError::ZeroShare {});
         }
     }
+    for (receiver, msg) in config.receiver_msgs.iter() {
+        if !config
+            .receivers
+            .iter()
+            .any(|(address, _)| address == receiver)
+        {
+            return Err(ContractError::UnknownReceiver {
+                receiver: receiver.to_string(),
+            });
+        }
+        from_json::<BTreeMap<String, IgnoredAny>>(msg).map_err(|err| {
+            ContractError::InvalidPayload {
+                receiver: receiver.to_string(),
+                details: err.to_string(),
+            }
+        })?;
+    }
+    if let Some(dust_receiver) = &config.dust_receiver {
+        deps.api.addr_validate(dust_receiver)?;
+    }
+    let mut denoms = vec![&config.denom];
+    for denom in config.extra_denoms.iter() {
+        if denoms.contains(&denom) {
+            return Err(ContractError::DuplicatedDenom {
+                denom: denom.to_string(),
+            });
+        }
+        denoms.push(denom);
+    }
     Ok(())
 }
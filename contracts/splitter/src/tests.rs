@@ -1,8 +1,11 @@
 use cosmwasm_std::{
-    attr, from_json, testing::mock_env, Addr, BankMsg, Coin, CosmosMsg, Event, Uint128,
+    attr, from_json, testing::mock_env, Addr, BankMsg, Binary, Coin, CosmosMsg, Event, SubMsg,
+    Uint128, WasmMsg,
 };
 use drop_helpers::testing::mock_dependencies;
-use drop_staking_base::state::splitter::Config;
+use drop_staking_base::{
+    error::splitter::ContractError, msg::splitter::DenomDistribution, state::splitter::Config,
+};
 
 #[test]
 fn change_splitter_config() {
@@ -10,6 +13,7 @@ fn change_splitter_config() {
     let instantiate_config: Config = Config {
         receivers: vec![("receiver1".to_string(), Uint128::from(1000000000u64))],
         denom: "drop".to_string(),
+        ..Default::default()
     };
     {
         let _ = crate::contract::instantiate(
@@ -55,6 +59,7 @@ fn change_splitter_config() {
                 ("receiver4".to_string(), Uint128::from(4u64)),
             ],
             denom: "drop".to_string(),
+            ..Default::default()
         };
         let _ = crate::contract::execute(
             deps.as_mut().into_empty(),
@@ -91,6 +96,7 @@ fn splitter_distribute() {
             ("receiver4".to_string(), Uint128::from(4u64)),
         ],
         denom: "drop".to_string(),
+        ..Default::default()
     };
     {
         let _ = crate::contract::instantiate(
@@ -150,3 +156,265 @@ fn splitter_distribute() {
         );
     }
 }
+
+#[test]
+fn splitter_distribute_multiple_denoms_with_dust() {
+    let mut deps = mock_dependencies(&[
+        Coin::new(11u128, "drop"),
+        Coin::new(7u128, "untrn"),
+        Coin::new(3u128, "uatom"),
+    ]);
+    let config: Config = Config {
+        receivers: vec![
+            ("receiver1".to_string(), Uint128::from(1u64)),
+            ("receiver2".to_string(), Uint128::from(2u64)),
+        ],
+        denom: "drop".to_string(),
+        extra_denoms: vec!["untrn".to_string(), "uatom".to_string()],
+        dust_receiver: Some("dust".to_string()),
+        min_distribution: Uint128::from(5u64),
+    };
+    crate::contract::instantiate(
+        deps.as_mut().into_empty(),
+        mock_env(),
+        cosmwasm_std::MessageInfo {
+            sender: Addr::unchecked("arbitrary_owner"),
+            funds: vec![],
+        },
+        drop_staking_base::msg::splitter::InstantiateMsg {
+            config: config.clone(),
+        },
+    )
+    .unwrap();
+    let preview: Vec<DenomDistribution> = from_json(
+        crate::contract::query(
+            deps.as_ref().into_empty(),
+            mock_env(),
+            drop_staking_base::msg::splitter::QueryMsg::Preview {},
+        )
+        .unwrap(),
+    )
+    .unwrap();
+    assert_eq!(
+        preview,
+        vec![
+            DenomDistribution {
+                denom: "drop".to_string(),
+                amounts: vec![
+                    ("receiver1".to_string(), Uint128::from(3u64)),
+                    ("receiver2".to_string(), Uint128::from(7u64)),
+                ],
+                dust: Uint128::from(1u64),
+            },
+            DenomDistribution {
+                denom: "untrn".to_string(),
+                amounts: vec![
+                    ("receiver1".to_string(), Uint128::from(2u64)),
+                    ("receiver2".to_string(), Uint128::from(4u64)),
+                ],
+                dust: Uint128::from(1u64),
+            },
+        ]
+    );
+    let response = crate::contract::execute(
+        deps.as_mut().into_empty(),
+        mock_env(),
+        cosmwasm_std::MessageInfo {
+            sender: Addr::unchecked("anyone"),
+            funds: vec![],
+        },
+        drop_staking_base::msg::splitter::ExecuteMsg::Distribute {},
+    )
+    .unwrap();
+    assert_eq!(
+        response,
+        cosmwasm_std::Response::new()
+            .add_event(
+                Event::new("crates.io:drop-staking__drop-splitter-execute-distribute")
+                    .add_attributes(vec![
+                        attr("total_shares", "3"),
+                        attr("receiver1", "3"),
+                        attr("receiver2", "7"),
+                        attr("dust", "1drop"),
+                        attr("receiver1", "2untrn"),
+                        attr("receiver2", "4untrn"),
+                        attr("dust", "1untrn"),
+                        attr("dust_receiver", "dust"),
+                    ])
+            )
+            .add_messages(vec![
+                CosmosMsg::Bank(BankMsg::Send {
+                    to_address: "receiver1".to_string(),
+                    amount: vec![Coin::new(3u128, "drop"), Coin::new(2u128, "untrn")]
+                }),
+                CosmosMsg::Bank(BankMsg::Send {
+                    to_address: "receiver2".to_string(),
+                    amount: vec![Coin::new(7u128, "drop"), Coin::new(4u128, "untrn")]
+                }),
+                CosmosMsg::Bank(BankMsg::Send {
+                    to_address: "dust".to_string(),
+                    amount: vec![Coin::new(1u128, "drop"), Coin::new(1u128, "untrn")]
+                }),
+            ])
+    );
+}
+
+#[test]
+fn splitter_distribute_below_min_distribution() {
+    let mut deps = mock_dependencies(&[Coin::new(10u128, "drop")]);
+    crate::contract::instantiate(
+        deps.as_mut().into_empty(),
+        mock_env(),
+        cosmwasm_std::MessageInfo {
+            sender: Addr::unchecked("arbitrary_owner"),
+            funds: vec![],
+        },
+        drop_staking_base::msg::splitter::InstantiateMsg {
+            config: Config {
+                receivers: vec![("receiver1".to_string(), Uint128::from(1u64))],
+                denom: "drop".to_string(),
+                min_distribution: Uint128::from(11u64),
+                ..Default::default()
+            },
+        },
+    )
+    .unwrap();
+    let error = crate::contract::execute(
+        deps.as_mut().into_empty(),
+        mock_env(),
+        cosmwasm_std::MessageInfo {
+            sender: Addr::unchecked("anyone"),
+            funds: vec![],
+        },
+        drop_staking_base::msg::splitter::ExecuteMsg::Distribute {},
+    )
+    .unwrap_err();
+    assert_eq!(error, ContractError::InsufficientFunds {});
+}
+
+#[test]
+fn splitter_duplicated_denom() {
+    let mut deps = mock_dependencies(&[]);
+    let error = crate::contract::instantiate(
+        deps.as_mut().into_empty(),
+        mock_env(),
+        cosmwasm_std::MessageInfo {
+            sender: Addr::unchecked("arbitrary_owner"),
+            funds: vec![],
+        },
+        drop_staking_base::msg::splitter::InstantiateMsg {
+            config: Config {
+                receivers: vec![("receiver1".to_string(), Uint128::from(1u64))],
+                denom: "drop".to_string(),
+                extra_denoms: vec!["untrn".to_string(), "drop".to_string()],
+                ..Default::default()
+            },
+        },
+    )
+    .unwrap_err();
+    assert_eq!(
+        error,
+        ContractError::DuplicatedDenom {
+            denom: "drop".to_string()
+        }
+    );
+}
+
+#[test]
+fn splitter_distribute_with_receiver_payload() {
+    let mut deps = mock_dependencies(&[Coin::new(10u128, "drop"), Coin::new(5u128, "adrop")]);
+    let payload = Binary::from(br#"{"stake":{}}"#.to_vec());
+    crate::contract::instantiate(
+        deps.as_mut().into_empty(),
+        mock_env(),
+        cosmwasm_std::MessageInfo {
+            sender: Addr::unchecked("arbitrary_owner"),
+            funds: vec![],
+        },
+        drop_staking_base::msg::splitter::InstantiateMsg {
+            config: Config {
+                receivers: vec![
+                    ("staker".to_string(), Uint128::from(4u64)),
+                    ("receiver".to_string(), Uint128::from(1u64)),
+                ],
+                denom: "drop".to_string(),
+                extra_denoms: vec!["adrop".to_string()],
+                receiver_msgs: vec![("staker".to_string(), payload.clone())],
+                ..Default::default()
+            },
+        },
+    )
+    .unwrap();
+    let response = crate::contract::execute(
+        deps.as_mut().into_empty(),
+        mock_env(),
+        cosmwasm_std::MessageInfo {
+            sender: Addr::unchecked("anyone"),
+            funds: vec![],
+        },
+        drop_staking_base::msg::splitter::ExecuteMsg::Distribute {},
+    )
+    .unwrap();
+    assert_eq!(
+        response.messages,
+        vec![
+            SubMsg::new(CosmosMsg::Wasm(WasmMsg::Execute {
+                contract_addr: "staker".to_string(),
+                msg: payload,
+                funds: vec![Coin::new(4u128, "adrop"), Coin::new(8u128, "drop")],
+            })),
+            SubMsg::new(CosmosMsg::Bank(BankMsg::Send {
+                to_address: "receiver".to_string(),
+                amount: vec![Coin::new(1u128, "adrop"), Coin::new(2u128, "drop")],
+            })),
+        ]
+    );
+}
+
+#[test]
+fn splitter_invalid_receiver_payload() {
+    let mut deps = mock_dependencies(&[]);
+    let instantiate = |deps: cosmwasm_std::DepsMut, receiver_msgs| {
+        crate::contract::instantiate(
+            deps,
+            mock_env(),
+            cosmwasm_std::MessageInfo {
+                sender: Addr::unchecked("arbitrary_owner"),
+                funds: vec![],
+            },
+            drop_staking_base::msg::splitter::InstantiateMsg {
+                config: Config {
+                    receivers: vec![("staker".to_string(), Uint128::from(1u64))],
+                    denom: "drop".to_string(),
+                    receiver_msgs,
+                    ..Default::default()
+                },
+            },
+        )
+    };
+    let error = instantiate(
+        deps.as_mut().into_empty(),
+        vec![("staker".to_string(), Binary::from(b"{\"stake\":".to_vec()))],
+    )
+    .unwrap_err();
+    assert!(
+        matches!(error, ContractError::InvalidPayload { receiver, .. } if receiver == "staker")
+    );
+    let error = instantiate(
+        deps.as_mut().into_empty(),
+        vec![("staker".to_string(), Binary::from(b"[1,2]".to_vec()))],
+    )
+    .unwrap_err();
+    assert!(matches!(error, ContractError::InvalidPayload { .. }));
+    let error = instantiate(
+        deps.as_mut().into_empty(),
+        vec![("treasury".to_string(), Binary::from(b"{}".to_vec()))],
+    )
+    .unwrap_err();
+    assert_eq!(
+        error,
+        ContractError::UnknownReceiver {
+            receiver: "treasury".to_string()
+        }
+    );
+}
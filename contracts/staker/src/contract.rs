@@ -5,21 +5,34 @@ use cosmos_sdk_proto::cosmos::staking::v1beta1::MsgDelegate;
 use cosmos_sdk_proto::traits::MessageExt;
 use cosmwasm_schema::serde::Serialize;
 use cosmwasm_std::{
-    attr, ensure, to_json_binary, CosmosMsg, Deps, Reply, StdError, SubMsg, Uint128, WasmMsg,
+    attr, ensure, to_json_binary, BankMsg, CosmosMsg, Deps, Reply, StdError, Storage, SubMsg,
+    Uint128, WasmMsg,
 };
 use cosmwasm_std::{Binary, DepsMut, Env, MessageInfo, Response, StdResult};
 use drop_helpers::{
-    answer::response, ibc_client_state::query_client_state, ibc_fee::query_ibc_fee,
-    interchain::prepare_any_msg, validation::validate_addresses,
+    answer::response,
+    ibc_client_state::query_client_state,
+    ibc_fee::query_ibc_fee,
+    ibc_transfer::{validate_memo, DEFAULT_MAX_MEMO_LENGTH},
+    ica::IcaRecoveredHookMsg,
+    icq::new_multiple_balances_query_msg,
+    interchain::prepare_any_msg,
+    query_id::get_query_id,
+    validation::validate_addresses,
 };
+use drop_puppeteer_base::r#trait::PuppeteerReconstruct;
 use drop_staking_base::{
-    msg::staker::{
-        ExecuteMsg, InstantiateMsg, MigrateMsg, OpenAckVersion, QueryMsg, ReceiverExecuteMsg,
-        ResponseHookErrorMsg, ResponseHookMsg, ResponseHookSuccessMsg,
+    msg::{
+        puppeteer::MultiBalances,
+        staker::{
+            ExecuteMsg, InstantiateMsg, MigrateMsg, OpenAckVersion, QueryMsg, ReceiverExecuteMsg,
+            ReconciliationResponse, ResponseHookErrorMsg, ResponseHookMsg, ResponseHookSuccessMsg,
+        },
     },
     state::staker::{
-        Config, ConfigOptional, ReplyMsg, Transaction, TxState, TxStateStatus, CONFIG, ICA, ICA_ID,
-        NON_STAKED_BALANCE, TX_STATE,
+        BalanceQueryConfig, Config, ConfigOptional, IcaBalance, ReplyMsg, Transaction, TxState,
+        TxStateStatus, BALANCE_QUERY_CONFIG, BALANCE_QUERY_ID, CONFIG, ICA, ICA_BALANCE, ICA_ID,
+        LAST_TICK, LAST_TX_REMOTE_HEIGHT, NON_STAKED_BALANCE, TX_STATE,
     },
 };
 use neutron_sdk::{
@@ -27,6 +40,7 @@ use neutron_sdk::{
         msg::{MsgIbcTransferResponse, MsgSubmitTxResponse, NeutronMsg},
         query::NeutronQuery,
     },
+    interchain_queries::queries::get_raw_interchain_query_result,
     sudo::msg::{RequestPacket, RequestPacketTimeoutHeight, SudoMsg},
     NeutronError, NeutronResult,
 };
@@ -73,6 +87,10 @@ pub fn instantiate(
             puppeteer_ica: None,
             min_ibc_transfer: msg.min_ibc_transfer,
             min_staking_amount: msg.min_staking_amount,
+            ica_recovery: None,
+            transfer_memo: None,
+            auto_stake: None,
+            max_memo_length: DEFAULT_MAX_MEMO_LENGTH,
         },
     )?;
     NON_STAKED_BALANCE.save(deps.storage, &Uint128::zero())?;
@@ -87,6 +105,9 @@ pub fn query(deps: Deps, env: Env, msg: QueryMsg) -> NeutronResult<Binary> {
         QueryMsg::NonStakedBalance {} => query_non_staked_balance(deps, env),
         QueryMsg::AllBalance {} => query_all_balance(deps, env),
         QueryMsg::TxState {} => query_tx_state(deps, env),
+        QueryMsg::Reconciliation {} => {
+            to_json_binary(&reconciliation(deps.storage)?).map_err(NeutronError::Std)
+        }
         QueryMsg::Ownership {} => {
             let ownership = cw_ownable::get_ownership(deps.storage)?;
             to_json_binary(&ownership).map_err(NeutronError::Std)
@@ -94,6 +115,23 @@ pub fn query(deps: Deps, env: Env, msg: QueryMsg) -> NeutronResult<Binary> {
     }
 }
 
+fn reconciliation(storage: &dyn Storage) -> StdResult<ReconciliationResponse> {
+    let non_staked_balance = NON_STAKED_BALANCE.load(storage)?;
+    let ica_balance = ICA_BALANCE.may_load(storage)?;
+    let last_tx_remote_height = LAST_TX_REMOTE_HEIGHT.may_load(storage)?;
+    let tx_state = TX_STATE.load(storage)?;
+    // a snapshot of the remote state before the last tx was acknowledged doesn't reflect it
+    let reconcilable = tx_state.status == TxStateStatus::Idle
+        && ica_balance.as_ref().map_or(false, |balance| {
+            last_tx_remote_height.map_or(true, |height| balance.remote_height > height)
+        });
+    Ok(ReconciliationResponse {
+        non_staked_balance,
+        ica_balance,
+        reconcilable,
+    })
+}
+
 fn query_tx_state(deps: Deps, _env: Env) -> NeutronResult<Binary> {
     let tx_state = TX_STATE.load(deps.storage)?;
     to_json_binary(&tx_state).map_err(NeutronError::Std)
@@ -144,6 +182,12 @@ pub fn execute(
         }
         ExecuteMsg::IBCTransfer {} => execute_ibc_transfer(deps, env),
         ExecuteMsg::Stake { items } => execute_stake(deps, info, items),
+        ExecuteMsg::Tick {} => execute_tick(deps, env, info),
+        ExecuteMsg::RegisterBalanceQuery {
+            update_period,
+            sdk_version,
+        } => execute_register_balance_query(deps, info, update_period, sdk_version),
+        ExecuteMsg::Reconcile {} => execute_reconcile(deps, info),
     }
 }
 
@@ -175,6 +219,23 @@ fn execute_update_config(
     if let Some(min_staking_amount) = new_config.min_staking_amount {
         config.min_staking_amount = min_staking_amount;
     }
+    if let Some(ica_recovery) = new_config.ica_recovery {
+        if let Some(hook_receiver) = &ica_recovery.hook_receiver {
+            deps.api.addr_validate(hook_receiver)?;
+        }
+        config.ica_recovery = Some(ica_recovery);
+    }
+    if let Some(max_memo_length) = new_config.max_memo_length {
+        config.max_memo_length = max_memo_length;
+    }
+    if let Some(transfer_memo) = new_config.transfer_memo {
+        validate_memo(&transfer_memo, config.max_memo_length)?;
+        config.transfer_memo = Some(transfer_memo).filter(|memo| !memo.is_empty());
+    }
+    if let Some(auto_stake) = new_config.auto_stake {
+        deps.api.addr_validate(&auto_stake.strategy_contract)?;
+        config.auto_stake = Some(auto_stake);
+    }
     CONFIG.save(deps.storage, &config)?;
     Ok(response("update_config", CONTRACT_NAME, attrs))
 }
@@ -209,6 +270,168 @@ fn execute_stake(
     if !config.allowed_senders.contains(&info.sender) {
         return Err(ContractError::Unauthorized {});
     }
+    stake(deps, config, items, Some(info.sender.to_string()))
+}
+
+fn execute_register_balance_query(
+    deps: DepsMut<NeutronQuery>,
+    info: MessageInfo,
+    update_period: u64,
+    sdk_version: String,
+) -> ContractResult<Response<NeutronMsg>> {
+    cw_ownable::assert_owner(deps.storage, &info.sender)?;
+    let config = CONFIG.load(deps.storage)?;
+    let ica = ICA.get_address(deps.storage)?;
+    BALANCE_QUERY_CONFIG.save(
+        deps.storage,
+        &BalanceQueryConfig {
+            update_period,
+            sdk_version,
+        },
+    )?;
+    let attrs = vec![
+        attr("action", "register_balance_query"),
+        attr("update_period", update_period.to_string()),
+    ];
+    let response = response("register_balance_query", CONTRACT_NAME, attrs);
+    match BALANCE_QUERY_ID.may_load(deps.storage)? {
+        Some(query_id) => Ok(response.add_message(NeutronMsg::update_interchain_query(
+            query_id,
+            None,
+            Some(update_period),
+            None,
+        )?)),
+        None => Ok(response.add_submessage(SubMsg::reply_on_success(
+            new_multiple_balances_query_msg(
+                config.connection_id,
+                ica,
+                vec![config.remote_denom],
+                update_period,
+            )?,
+            ReplyMsg::BalanceQuery.to_reply_id(),
+        ))),
+    }
+}
+
+fn execute_reconcile(
+    deps: DepsMut<NeutronQuery>,
+    info: MessageInfo,
+) -> ContractResult<Response<NeutronMsg>> {
+    cw_ownable::assert_owner(deps.storage, &info.sender)?;
+    let reconciliation = reconciliation(deps.storage)?;
+    ensure!(
+        reconciliation.reconcilable,
+        ContractError::InvalidState {
+            reason: "ica balance is outdated or tx_state is not idle".to_string()
+        }
+    );
+    let ica_balance = reconciliation
+        .ica_balance
+        .ok_or_else(|| StdError::generic_err("ica balance not found"))?;
+    NON_STAKED_BALANCE.save(deps.storage, &ica_balance.amount)?;
+    Ok(response(
+        "reconcile",
+        CONTRACT_NAME,
+        [
+            attr("action", "reconcile"),
+            attr(
+                "old_non_staked_balance",
+                reconciliation.non_staked_balance.to_string(),
+            ),
+            attr("new_non_staked_balance", ica_balance.amount.to_string()),
+            attr("remote_height", ica_balance.remote_height.to_string()),
+        ],
+    ))
+}
+
+/// Anyone may tick, so the caller pays the IBC fee of the tx it triggers.
+/// Attached funds are returned when there is nothing to do.
+fn execute_tick(
+    deps: DepsMut<NeutronQuery>,
+    env: Env,
+    info: MessageInfo,
+) -> ContractResult<Response<NeutronMsg>> {
+    let config = CONFIG.load(deps.storage)?;
+    let auto_stake = config
+        .auto_stake
+        .clone()
+        .ok_or(ContractError::AutoStakeDisabled {})?;
+    let now = env.block.time.seconds();
+    if let Some(last_tick) = LAST_TICK.may_load(deps.storage)? {
+        let next_tick = last_tick.saturating_add(auto_stake.min_tick_interval);
+        ensure!(now >= next_tick, ContractError::TickTooEarly { next_tick });
+    }
+    let tx_state = TX_STATE.load(deps.storage)?;
+    ensure!(
+        tx_state.status == TxStateStatus::Idle,
+        ContractError::InvalidState {
+            reason: "tx_state is not idle".to_string()
+        }
+    );
+    LAST_TICK.save(deps.storage, &now)?;
+
+    // funds already on the ICA go first, new ones are transferred on the next tick
+    let non_staked_balance = NON_STAKED_BALANCE.load(deps.storage)?;
+    if !non_staked_balance.is_zero() && non_staked_balance >= config.min_staking_amount {
+        let items: Vec<(String, Uint128)> = deps.querier.query_wasm_smart(
+            &auto_stake.strategy_contract,
+            &drop_staking_base::msg::strategy::QueryMsg::CalcDeposit {
+                deposit: non_staked_balance,
+            },
+        )?;
+        ensure_tick_fee(deps.as_ref(), &info)?;
+        return stake(deps, config, items, None);
+    }
+    let pending_amount = deps
+        .querier
+        .query_balance(&env.contract.address, &config.base_denom)?
+        .amount;
+    if !pending_amount.is_zero() && pending_amount >= config.min_ibc_transfer {
+        ensure_tick_fee(deps.as_ref(), &info)?;
+        return execute_ibc_transfer(deps, env);
+    }
+    let mut res = response(
+        "tick",
+        CONTRACT_NAME,
+        [attr("action", "tick"), attr("result", "nothing_to_do")],
+    );
+    if !info.funds.is_empty() {
+        res = res.add_message(BankMsg::Send {
+            to_address: info.sender.to_string(),
+            amount: info.funds,
+        });
+    }
+    Ok(res)
+}
+
+fn ensure_tick_fee(deps: Deps<NeutronQuery>, info: &MessageInfo) -> ContractResult<()> {
+    let fee = query_ibc_fee(deps, LOCAL_DENOM)?;
+    let required = fee
+        .ack_fee
+        .iter()
+        .chain(fee.timeout_fee.iter())
+        .fold(Uint128::zero(), |acc, coin| acc + coin.amount);
+    let paid = info
+        .funds
+        .iter()
+        .find(|coin| coin.denom == LOCAL_DENOM)
+        .map(|coin| coin.amount)
+        .unwrap_or_default();
+    ensure!(
+        paid >= required,
+        ContractError::InvalidFunds {
+            reason: format!("tick requires {required}{LOCAL_DENOM} to cover the IBC fee"),
+        }
+    );
+    Ok(())
+}
+
+fn stake(
+    deps: DepsMut<NeutronQuery>,
+    config: Config,
+    items: Vec<(String, Uint128)>,
+    reply_to: Option<String>,
+) -> ContractResult<Response<NeutronMsg>> {
     let tx_state = TX_STATE.load(deps.storage)?;
     ensure!(
         tx_state.status == TxStateStatus::Idle,
@@ -294,10 +517,10 @@ fn execute_stake(
         deps,
         cosmos_msg,
         Transaction::Stake {
-            amount: non_staked_balance,
+            amount: amount_to_stake,
         },
         ReplyMsg::SudoPayload.to_reply_id(),
-        Some(info.sender.to_string()),
+        reply_to,
     )?;
     Ok(response("stake", CONTRACT_NAME, attrs).add_submessage(submsg))
 }
@@ -345,7 +568,7 @@ fn execute_ibc_transfer(
             revision_height: None,
         },
         timeout_timestamp: env.block.time.plus_seconds(config.timeout).nanos(),
-        memo: "".to_string(),
+        memo: config.transfer_memo.unwrap_or_default(),
         fee,
     };
 
@@ -363,13 +586,18 @@ fn execute_ibc_transfer(
 }
 
 #[cfg_attr(not(feature = "library"), cosmwasm_std::entry_point)]
-pub fn sudo(deps: DepsMut<NeutronQuery>, env: Env, msg: SudoMsg) -> ContractResult<Response> {
+pub fn sudo(
+    deps: DepsMut<NeutronQuery>,
+    env: Env,
+    msg: SudoMsg,
+) -> ContractResult<Response<NeutronMsg>> {
     deps.api
         .debug(format!("WASMDEBUG: STAKER sudo: {:?}", msg).as_str());
     match msg {
         SudoMsg::Response { request, data } => sudo_response(deps, env, request, data),
         SudoMsg::Error { request, details } => sudo_error(deps, env, request, details),
         SudoMsg::Timeout { request } => sudo_timeout(deps, env, request),
+        SudoMsg::KVQueryResult { query_id } => sudo_kv_query_result(deps, env, query_id),
         SudoMsg::OpenAck {
             port_id,
             channel_id,
@@ -387,6 +615,55 @@ pub fn sudo(deps: DepsMut<NeutronQuery>, env: Env, msg: SudoMsg) -> ContractResu
     }
 }
 
+fn sudo_kv_query_result(
+    deps: DepsMut<NeutronQuery>,
+    env: Env,
+    query_id: u64,
+) -> ContractResult<Response<NeutronMsg>> {
+    ensure!(
+        BALANCE_QUERY_ID.may_load(deps.storage)? == Some(query_id),
+        ContractError::UnknownQueryId { query_id }
+    );
+    let config = CONFIG.load(deps.storage)?;
+    let query_config = BALANCE_QUERY_CONFIG.load(deps.storage)?;
+    let result = get_raw_interchain_query_result(deps.as_ref(), query_id)?.result;
+    if let Some(ica_balance) = ICA_BALANCE.may_load(deps.storage)? {
+        if ica_balance.remote_height >= result.height {
+            return Ok(response(
+                "sudo-kv-query-result",
+                CONTRACT_NAME,
+                [
+                    attr("action", "sudo_kv_query_result"),
+                    attr("result", "outdated"),
+                ],
+            ));
+        }
+    }
+    let amount = MultiBalances::reconstruct(&result.kv_results, &query_config.sdk_version)?
+        .coins
+        .into_iter()
+        .find(|coin| coin.denom == config.remote_denom)
+        .map(|coin| coin.amount)
+        .unwrap_or_default();
+    ICA_BALANCE.save(
+        deps.storage,
+        &IcaBalance {
+            amount,
+            remote_height: result.height,
+            local_height: env.block.height,
+        },
+    )?;
+    Ok(response(
+        "sudo-kv-query-result",
+        CONTRACT_NAME,
+        [
+            attr("action", "sudo_kv_query_result"),
+            attr("ica_balance", amount.to_string()),
+            attr("remote_height", result.height.to_string()),
+        ],
+    ))
+}
+
 pub fn sudo_open_ack(
     deps: DepsMut<NeutronQuery>,
     _env: Env,
@@ -394,12 +671,28 @@ pub fn sudo_open_ack(
     channel_id: String,
     _counterparty_channel_id: String,
     counterparty_version: String,
-) -> ContractResult<Response> {
+) -> ContractResult<Response<NeutronMsg>> {
     let parsed_version: Result<OpenAckVersion, _> =
         serde_json_wasm::from_str(counterparty_version.as_str());
     if let Ok(parsed_version) = parsed_version {
-        ICA.set_address(deps.storage, parsed_version.address, port_id, channel_id)?;
-        Ok(Response::default())
+        let recovered = ICA.set_address(
+            deps.storage,
+            parsed_version.address.clone(),
+            port_id.clone(),
+            channel_id.clone(),
+        )?;
+        let mut msgs = vec![];
+        if recovered {
+            if let Some(recovery) = CONFIG.load(deps.storage)?.ica_recovery {
+                msgs.extend(recovery.hook_msg(IcaRecoveredHookMsg {
+                    ica_id: ICA_ID.to_string(),
+                    ica_address: parsed_version.address,
+                    port_id,
+                    channel_id,
+                })?);
+            }
+        }
+        Ok(Response::default().add_messages(msgs))
     } else {
         Err(ContractError::Std(StdError::generic_err(
             "can't parse version",
@@ -412,7 +705,7 @@ fn sudo_response(
     env: Env,
     request: RequestPacket,
     _data: Binary,
-) -> ContractResult<Response> {
+) -> ContractResult<Response<NeutronMsg>> {
     let attrs = vec![
         attr("action", "sudo_response"),
         attr("request_id", request.sequence.unwrap_or(0).to_string()),
@@ -458,6 +751,7 @@ fn sudo_response(
         .latest_height
         .ok_or_else(|| StdError::generic_err("IBC client state latest_height not found"))?
         .revision_height;
+    LAST_TX_REMOTE_HEIGHT.save(deps.storage, &remote_height.u64())?;
 
     let mut msgs = vec![];
     if let Some(reply_to) = reply_to {
@@ -499,17 +793,34 @@ fn msg_with_sudo_callback<C: Into<CosmosMsg<X>> + Serialize, X>(
 
 fn sudo_timeout(
     deps: DepsMut<NeutronQuery>,
-    _env: Env,
+    env: Env,
     request: RequestPacket,
-) -> ContractResult<Response> {
+) -> ContractResult<Response<NeutronMsg>> {
     let attrs = vec![
         attr("action", "sudo_timeout"),
         attr("request_id", request.sequence.unwrap_or(0).to_string()),
     ];
-    ICA.set_timeout(deps.storage)?;
     let seq_id = request
         .sequence
         .ok_or_else(|| StdError::generic_err("sequence not found"))?;
+    let channel_id = request
+        .source_channel
+        .clone()
+        .ok_or_else(|| StdError::generic_err("source_channel not found"))?;
+    let config = CONFIG.load(deps.storage)?;
+    let mut msgs = vec![];
+    if ICA.timeout(deps.storage, &channel_id)? {
+        if let Some(recovery) = &config.ica_recovery {
+            msgs.extend(ICA.recover(
+                deps.storage,
+                &deps.querier,
+                &env.contract.address,
+                config.connection_id,
+                ICA_ID,
+                recovery,
+            )?);
+        }
+    }
     let tx_state = TX_STATE.load(deps.storage)?;
     let transaction = tx_state
         .transaction
@@ -517,7 +828,6 @@ fn sudo_timeout(
     if let Transaction::IBCTransfer { amount } = transaction {
         NON_STAKED_BALANCE.update(deps.storage, |balance| StdResult::Ok(balance - amount))?;
     }
-    let mut msgs = vec![];
     if let Some(reply_to) = tx_state.reply_to {
         msgs.push(CosmosMsg::Wasm(WasmMsg::Execute {
             contract_addr: reply_to,
@@ -541,7 +851,7 @@ fn sudo_error(
     _env: Env,
     request: RequestPacket,
     details: String,
-) -> ContractResult<Response> {
+) -> ContractResult<Response<NeutronMsg>> {
     let attrs = vec![
         attr("action", "sudo_error"),
         attr("request_id", request.sequence.unwrap_or(0).to_string()),
@@ -595,6 +905,7 @@ pub fn migrate(
     if storage_version < version {
         cw2::set_contract_version(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
     }
+    ICA.init_last_address(deps.storage)?;
 
     Ok(Response::new())
 }
@@ -604,9 +915,20 @@ pub fn reply(deps: DepsMut, _env: Env, msg: Reply) -> StdResult<Response> {
     match ReplyMsg::from_reply_id(msg.id) {
         ReplyMsg::SudoPayload => submit_tx_reply(deps, msg),
         ReplyMsg::IbcTransfer => submit_ibc_transfer_reply(deps, msg),
+        ReplyMsg::BalanceQuery => balance_query_reply(deps, msg),
     }
 }
 
+fn balance_query_reply(deps: DepsMut, msg: Reply) -> StdResult<Response> {
+    let query_id = get_query_id(msg.result)?;
+    BALANCE_QUERY_ID.save(deps.storage, &query_id)?;
+    Ok(response(
+        "reply-balance-query",
+        CONTRACT_NAME,
+        [attr("query_id", query_id.to_string())],
+    ))
+}
+
 fn submit_tx_reply(deps: DepsMut, msg: Reply) -> StdResult<Response> {
     let resp: MsgSubmitTxResponse = serde_json_wasm::from_slice(
         msg.result
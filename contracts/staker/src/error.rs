@@ -40,6 +40,15 @@ pub enum ContractError {
     #[error("Invalid State: {reason}")]
     InvalidState { reason: String },
 
+    #[error("Auto staking is disabled")]
+    AutoStakeDisabled {},
+
+    #[error("Tick is too early, next one is allowed at {next_tick}")]
+    TickTooEarly { next_tick: u64 },
+
+    #[error("Unknown query id: {query_id}")]
+    UnknownQueryId { query_id: u64 },
+
     #[error("Unknown sudo response")]
     UnknownResponse {},
 
@@ -1,17 +1,27 @@
-use crate::contract::{execute, instantiate};
+use crate::contract::{execute, instantiate, query, sudo};
 use crate::error::ContractError;
 use cosmwasm_std::{
-    coins,
+    coins, from_json,
     testing::{mock_env, mock_info},
-    to_json_binary, Addr, Coin, CosmosMsg, Event, Response, SubMsg, Uint128,
+    to_json_binary, Addr, Binary, Coin, CosmosMsg, Event, Response, SubMsg, Uint128,
 };
 use drop_helpers::testing::mock_dependencies;
-use drop_staking_base::state::staker::{
-    Config, ConfigOptional, TxState, CONFIG, ICA, NON_STAKED_BALANCE, TX_STATE,
+use drop_staking_base::{
+    msg::staker::ReconciliationResponse,
+    state::staker::{
+        AutoStakeConfig, BalanceQueryConfig, Config, ConfigOptional, IcaBalance, ReplyMsg,
+        Transaction, TxState, TxStateStatus, BALANCE_QUERY_CONFIG, BALANCE_QUERY_ID, CONFIG, ICA,
+        ICA_BALANCE, LAST_TICK, LAST_TX_REMOTE_HEIGHT, NON_STAKED_BALANCE, TX_STATE,
+    },
 };
 use neutron_sdk::{
-    bindings::msg::{IbcFee, NeutronMsg},
+    bindings::{
+        msg::{IbcFee, NeutronMsg},
+        query::QueryRegisteredQueryResultResponse,
+    },
+    interchain_queries::types::{InterchainQueryResult, StorageValue},
     query::min_ibc_fee::MinIbcFeeResponse,
+    sudo::msg::SudoMsg,
 };
 
 fn get_default_config() -> Config {
@@ -26,6 +36,10 @@ fn get_default_config() -> Config {
         puppeteer_ica: Some("puppeteer_ica".to_string()),
         min_ibc_transfer: Uint128::from(10000u128),
         min_staking_amount: Uint128::from(10000u128),
+        ica_recovery: None,
+        transfer_memo: None,
+        auto_stake: None,
+        max_memo_length: 256,
     }
 }
 
@@ -91,6 +105,10 @@ fn test_update_config() {
         puppeteer_ica: Some("puppeteer_ica".to_string()),
         min_ibc_transfer: Some(Uint128::from(110000u128)),
         min_staking_amount: Some(Uint128::from(110000u128)),
+        ica_recovery: None,
+        transfer_memo: Some("memo".to_string()),
+        auto_stake: None,
+        max_memo_length: Some(128),
     };
     let res = execute(
         deps.as_mut(),
@@ -107,7 +125,7 @@ fn test_update_config() {
             "crates.io:drop-neutron-contracts__drop-staker-update_config"
         ).add_attributes(vec![
             ("action","update_config"),
-            ("new_config", "ConfigOptional { timeout: Some(20), allowed_senders: Some([\"new_core\"]), puppeteer_ica: Some(\"puppeteer_ica\"), min_ibc_transfer: Some(Uint128(110000)), min_staking_amount: Some(Uint128(110000)) }")
+            ("new_config", "ConfigOptional { timeout: Some(20), allowed_senders: Some([\"new_core\"]), puppeteer_ica: Some(\"puppeteer_ica\"), min_ibc_transfer: Some(Uint128(110000)), min_staking_amount: Some(Uint128(110000)), ica_recovery: None, transfer_memo: Some(\"memo\"), auto_stake: None, max_memo_length: Some(128) }")
         ]))
     );
     let config = CONFIG.load(deps.as_ref().storage).unwrap();
@@ -124,6 +142,10 @@ fn test_update_config() {
             puppeteer_ica: Some("puppeteer_ica".to_string()),
             min_ibc_transfer: Uint128::from(110000u128),
             min_staking_amount: Uint128::from(110000u128),
+            ica_recovery: None,
+            transfer_memo: Some("memo".to_string()),
+            auto_stake: None,
+            max_memo_length: 128,
         }
     );
 }
@@ -228,6 +250,640 @@ fn test_register_ica() {
     );
 }
 
+#[test]
+fn test_ibc_transfer_memo() {
+    let mut deps = mock_dependencies(&[Coin::new(10001, "base_denom")]);
+    deps.querier.add_custom_query_response(|_| {
+        to_json_binary(&MinIbcFeeResponse {
+            min_fee: IbcFee {
+                recv_fee: vec![],
+                ack_fee: coins(100, "local_denom"),
+                timeout_fee: coins(200, "local_denom"),
+            },
+        })
+        .unwrap()
+    });
+    CONFIG
+        .save(
+            deps.as_mut().storage,
+            &Config {
+                transfer_memo: Some("memo".to_string()),
+                ..get_default_config()
+            },
+        )
+        .unwrap();
+    TX_STATE
+        .save(deps.as_mut().storage, &TxState::default())
+        .unwrap();
+    NON_STAKED_BALANCE
+        .save(deps.as_mut().storage, &Uint128::zero())
+        .unwrap();
+    ICA.set_address(deps.as_mut().storage, "ica_address", "port", "channel")
+        .unwrap();
+    let res = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("nobody", &[]),
+        drop_staking_base::msg::staker::ExecuteMsg::IBCTransfer {},
+    )
+    .unwrap();
+    match &res.messages[0].msg {
+        CosmosMsg::Custom(NeutronMsg::IbcTransfer { memo, .. }) => assert_eq!(memo, "memo"),
+        msg => panic!("unexpected message: {msg:?}"),
+    }
+}
+
+fn get_auto_stake_config() -> Config {
+    Config {
+        auto_stake: Some(AutoStakeConfig {
+            strategy_contract: "strategy_contract".to_string(),
+            min_tick_interval: 100,
+        }),
+        ..get_default_config()
+    }
+}
+
+#[test]
+fn test_tick_disabled() {
+    let mut deps = mock_dependencies(&[]);
+    CONFIG
+        .save(deps.as_mut().storage, &get_default_config())
+        .unwrap();
+    let res = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("nobody", &[]),
+        drop_staking_base::msg::staker::ExecuteMsg::Tick {},
+    );
+    assert_eq!(res, Err(ContractError::AutoStakeDisabled {}));
+}
+
+#[test]
+fn test_tick_too_early() {
+    let mut deps = mock_dependencies(&[]);
+    CONFIG
+        .save(deps.as_mut().storage, &get_auto_stake_config())
+        .unwrap();
+    let env = mock_env();
+    LAST_TICK
+        .save(deps.as_mut().storage, &(env.block.time.seconds() - 50))
+        .unwrap();
+    let res = execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info("nobody", &[]),
+        drop_staking_base::msg::staker::ExecuteMsg::Tick {},
+    );
+    assert_eq!(
+        res,
+        Err(ContractError::TickTooEarly {
+            next_tick: env.block.time.seconds() + 50
+        })
+    );
+}
+
+#[test]
+fn test_tick_not_idle() {
+    let mut deps = mock_dependencies(&[]);
+    CONFIG
+        .save(deps.as_mut().storage, &get_auto_stake_config())
+        .unwrap();
+    TX_STATE
+        .save(
+            deps.as_mut().storage,
+            &TxState {
+                status: TxStateStatus::WaitingForAck,
+                ..TxState::default()
+            },
+        )
+        .unwrap();
+    let res = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("nobody", &[]),
+        drop_staking_base::msg::staker::ExecuteMsg::Tick {},
+    );
+    assert_eq!(
+        res,
+        Err(ContractError::InvalidState {
+            reason: "tx_state is not idle".to_string()
+        })
+    );
+}
+
+#[test]
+fn test_tick_nothing_to_do() {
+    let mut deps = mock_dependencies(&[Coin::new(100, "base_denom")]);
+    CONFIG
+        .save(deps.as_mut().storage, &get_auto_stake_config())
+        .unwrap();
+    TX_STATE
+        .save(deps.as_mut().storage, &TxState::default())
+        .unwrap();
+    NON_STAKED_BALANCE
+        .save(deps.as_mut().storage, &Uint128::from(100u128))
+        .unwrap();
+    let env = mock_env();
+    let res = execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info("nobody", &[]),
+        drop_staking_base::msg::staker::ExecuteMsg::Tick {},
+    )
+    .unwrap();
+    assert_eq!(
+        res,
+        Response::new().add_event(
+            Event::new("crates.io:drop-neutron-contracts__drop-staker-tick")
+                .add_attributes(vec![("action", "tick"), ("result", "nothing_to_do")])
+        )
+    );
+    assert_eq!(
+        LAST_TICK.load(deps.as_ref().storage).unwrap(),
+        env.block.time.seconds()
+    );
+}
+
+#[test]
+fn test_tick_ibc_transfer() {
+    let mut deps = mock_dependencies(&[Coin::new(10001, "base_denom")]);
+    // the tick fee check and the tx itself both query the fee
+    for _ in 0..2 {
+        deps.querier.add_custom_query_response(|_| {
+            to_json_binary(&MinIbcFeeResponse {
+                min_fee: IbcFee {
+                    recv_fee: vec![],
+                    ack_fee: coins(100, "local_denom"),
+                    timeout_fee: coins(200, "local_denom"),
+                },
+            })
+            .unwrap()
+        });
+    }
+    CONFIG
+        .save(deps.as_mut().storage, &get_auto_stake_config())
+        .unwrap();
+    TX_STATE
+        .save(deps.as_mut().storage, &TxState::default())
+        .unwrap();
+    NON_STAKED_BALANCE
+        .save(deps.as_mut().storage, &Uint128::zero())
+        .unwrap();
+    ICA.set_address(deps.as_mut().storage, "ica_address", "port", "channel")
+        .unwrap();
+    let res = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("nobody", &[]),
+        drop_staking_base::msg::staker::ExecuteMsg::Tick {},
+    )
+    .unwrap();
+    assert_eq!(
+        res.events[0].ty,
+        "crates.io:drop-neutron-contracts__drop-staker-ibc_transfer"
+    );
+    assert_eq!(
+        NON_STAKED_BALANCE.load(deps.as_ref().storage).unwrap(),
+        Uint128::from(10001u128)
+    );
+}
+
+#[test]
+fn test_tick_stake() {
+    let mut deps = mock_dependencies(&[]);
+    // the tick fee check and the tx itself both query the fee
+    for _ in 0..2 {
+        deps.querier.add_custom_query_response(|_| {
+            to_json_binary(&MinIbcFeeResponse {
+                min_fee: IbcFee {
+                    recv_fee: vec![],
+                    ack_fee: coins(100, "local_denom"),
+                    timeout_fee: coins(200, "local_denom"),
+                },
+            })
+            .unwrap()
+        });
+    }
+    deps.querier
+        .add_wasm_query_response("strategy_contract", |_| {
+            to_json_binary(&vec![
+                ("valoper1".to_string(), Uint128::from(6000u128)),
+                ("valoper2".to_string(), Uint128::from(4000u128)),
+            ])
+            .unwrap()
+        });
+    CONFIG
+        .save(deps.as_mut().storage, &get_auto_stake_config())
+        .unwrap();
+    TX_STATE
+        .save(deps.as_mut().storage, &TxState::default())
+        .unwrap();
+    NON_STAKED_BALANCE
+        .save(deps.as_mut().storage, &Uint128::from(10000u128))
+        .unwrap();
+    ICA.set_address(deps.as_mut().storage, "ica_address", "port", "channel")
+        .unwrap();
+    let res = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("nobody", &[]),
+        drop_staking_base::msg::staker::ExecuteMsg::Tick {},
+    )
+    .unwrap();
+    assert_eq!(
+        res.events,
+        vec![
+            Event::new("crates.io:drop-neutron-contracts__drop-staker-stake").add_attributes(vec![
+                ("action", "stake"),
+                ("connection_id", "connection"),
+                ("ica_id", "drop_STAKER"),
+                ("amount_to_stake", "10000"),
+            ])
+        ]
+    );
+    let tx_state = TX_STATE.load(deps.as_ref().storage).unwrap();
+    assert_eq!(tx_state.status, TxStateStatus::InProgress);
+    assert_eq!(tx_state.reply_to, None);
+}
+
+#[test]
+fn test_tick_stake_partial_allocation() {
+    let mut deps = mock_dependencies(&[]);
+    // the tick fee check and the tx itself both query the fee
+    for _ in 0..2 {
+        deps.querier.add_custom_query_response(|_| {
+            to_json_binary(&MinIbcFeeResponse {
+                min_fee: IbcFee {
+                    recv_fee: vec![],
+                    ack_fee: coins(100, "local_denom"),
+                    timeout_fee: coins(200, "local_denom"),
+                },
+            })
+            .unwrap()
+        });
+    }
+    deps.querier
+        .add_wasm_query_response("strategy_contract", |_| {
+            // validators are capped, only a part of the balance is allocated
+            to_json_binary(&vec![
+                ("valoper1".to_string(), Uint128::from(6000u128)),
+                ("valoper2".to_string(), Uint128::from(4000u128)),
+            ])
+            .unwrap()
+        });
+    CONFIG
+        .save(deps.as_mut().storage, &get_auto_stake_config())
+        .unwrap();
+    TX_STATE
+        .save(deps.as_mut().storage, &TxState::default())
+        .unwrap();
+    NON_STAKED_BALANCE
+        .save(deps.as_mut().storage, &Uint128::from(15000u128))
+        .unwrap();
+    ICA.set_address(deps.as_mut().storage, "ica_address", "port", "channel")
+        .unwrap();
+    let res = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("nobody", &[]),
+        drop_staking_base::msg::staker::ExecuteMsg::Tick {},
+    )
+    .unwrap();
+    assert_eq!(
+        res.events,
+        vec![
+            Event::new("crates.io:drop-neutron-contracts__drop-staker-stake").add_attributes(vec![
+                ("action", "stake"),
+                ("connection_id", "connection"),
+                ("ica_id", "drop_STAKER"),
+                ("amount_to_stake", "10000"),
+            ])
+        ]
+    );
+    let tx_state = TX_STATE.load(deps.as_ref().storage).unwrap();
+    assert_eq!(tx_state.status, TxStateStatus::InProgress);
+    assert_eq!(
+        tx_state.transaction,
+        Some(Transaction::Stake {
+            amount: Uint128::from(10000u128)
+        })
+    );
+}
+
+#[test]
+fn test_tick_fee_required() {
+    let mut deps = mock_dependencies(&[Coin::new(10001, "base_denom")]);
+    // the tick fee check and the tx itself both query the fee
+    for _ in 0..3 {
+        deps.querier.add_custom_query_response(|_| {
+            to_json_binary(&MinIbcFeeResponse {
+                min_fee: IbcFee {
+                    recv_fee: vec![],
+                    ack_fee: coins(100, "untrn"),
+                    timeout_fee: coins(200, "untrn"),
+                },
+            })
+            .unwrap()
+        });
+    }
+    CONFIG
+        .save(deps.as_mut().storage, &get_auto_stake_config())
+        .unwrap();
+    TX_STATE
+        .save(deps.as_mut().storage, &TxState::default())
+        .unwrap();
+    NON_STAKED_BALANCE
+        .save(deps.as_mut().storage, &Uint128::zero())
+        .unwrap();
+    ICA.set_address(deps.as_mut().storage, "ica_address", "port", "channel")
+        .unwrap();
+    let res = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("nobody", &coins(299, "untrn")),
+        drop_staking_base::msg::staker::ExecuteMsg::Tick {},
+    );
+    assert_eq!(
+        res,
+        Err(ContractError::InvalidFunds {
+            reason: "tick requires 300untrn to cover the IBC fee".to_string()
+        })
+    );
+    let res = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("nobody", &coins(300, "untrn")),
+        drop_staking_base::msg::staker::ExecuteMsg::Tick {},
+    )
+    .unwrap();
+    assert_eq!(
+        res.events[0].ty,
+        "crates.io:drop-neutron-contracts__drop-staker-ibc_transfer"
+    );
+}
+
+#[test]
+fn test_tick_nothing_to_do_returns_funds() {
+    let mut deps = mock_dependencies(&[]);
+    CONFIG
+        .save(deps.as_mut().storage, &get_auto_stake_config())
+        .unwrap();
+    TX_STATE
+        .save(deps.as_mut().storage, &TxState::default())
+        .unwrap();
+    NON_STAKED_BALANCE
+        .save(deps.as_mut().storage, &Uint128::zero())
+        .unwrap();
+    let res = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("nobody", &coins(300, "untrn")),
+        drop_staking_base::msg::staker::ExecuteMsg::Tick {},
+    )
+    .unwrap();
+    assert_eq!(
+        res.messages,
+        vec![SubMsg::new(cosmwasm_std::BankMsg::Send {
+            to_address: "nobody".to_string(),
+            amount: coins(300, "untrn"),
+        })]
+    );
+}
+
+#[test]
+fn test_register_balance_query() {
+    let mut deps = mock_dependencies(&[]);
+    CONFIG
+        .save(deps.as_mut().storage, &get_default_config())
+        .unwrap();
+    let deps_mut = deps.as_mut();
+    cw_ownable::initialize_owner(deps_mut.storage, deps_mut.api, Some("owner")).unwrap();
+    ICA.set_address(deps.as_mut().storage, "ica_address", "port", "channel")
+        .unwrap();
+    let msg = drop_staking_base::msg::staker::ExecuteMsg::RegisterBalanceQuery {
+        update_period: 100,
+        sdk_version: "0.47.0".to_string(),
+    };
+    let res = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("nobody", &[]),
+        msg.clone(),
+    );
+    assert_eq!(
+        res,
+        Err(ContractError::OwnershipError(
+            cw_ownable::OwnershipError::NotOwner
+        ))
+    );
+    let res = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("owner", &[]),
+        msg.clone(),
+    )
+    .unwrap();
+    assert_eq!(res.messages.len(), 1);
+    assert_eq!(res.messages[0].id, ReplyMsg::BalanceQuery.to_reply_id());
+    assert!(matches!(
+        res.messages[0].msg,
+        CosmosMsg::Custom(NeutronMsg::RegisterInterchainQuery {
+            update_period: 100,
+            ..
+        })
+    ));
+    assert_eq!(
+        BALANCE_QUERY_CONFIG.load(deps.as_ref().storage).unwrap(),
+        BalanceQueryConfig {
+            update_period: 100,
+            sdk_version: "0.47.0".to_string(),
+        }
+    );
+    // already registered, only the period is updated
+    BALANCE_QUERY_ID.save(deps.as_mut().storage, &1).unwrap();
+    let res = execute(deps.as_mut(), mock_env(), mock_info("owner", &[]), msg).unwrap();
+    assert_eq!(
+        res.messages,
+        vec![SubMsg::new(CosmosMsg::Custom(
+            NeutronMsg::update_interchain_query(1, None, Some(100), None).unwrap()
+        ))]
+    );
+}
+
+#[test]
+fn test_sudo_kv_query_result_ica_balance() {
+    let mut deps = mock_dependencies(&[]);
+    CONFIG
+        .save(
+            deps.as_mut().storage,
+            &Config {
+                remote_denom: "stake".to_string(),
+                ..get_default_config()
+            },
+        )
+        .unwrap();
+    BALANCE_QUERY_CONFIG
+        .save(
+            deps.as_mut().storage,
+            &BalanceQueryConfig {
+                update_period: 100,
+                sdk_version: "0.47.0".to_string(),
+            },
+        )
+        .unwrap();
+    BALANCE_QUERY_ID.save(deps.as_mut().storage, &1).unwrap();
+    deps.querier.add_query_response(
+        1,
+        to_json_binary(&QueryRegisteredQueryResultResponse {
+            result: InterchainQueryResult {
+                kv_results: vec![StorageValue {
+                    storage_prefix: "bank".to_string(),
+                    key: Binary::from_base64(
+                        "AiCfJEiz8RudHBDrScLR8pIMUlCHdutdClI4tEAyIUuXZnN0YWtl",
+                    )
+                    .unwrap(),
+                    value: Binary::from("29558778".as_bytes()),
+                }],
+                height: 1000,
+                revision: 2,
+            },
+        })
+        .unwrap(),
+    );
+    let env = mock_env();
+    let res = sudo(
+        deps.as_mut(),
+        env.clone(),
+        SudoMsg::KVQueryResult { query_id: 2 },
+    );
+    assert_eq!(res, Err(ContractError::UnknownQueryId { query_id: 2 }));
+    let res = sudo(
+        deps.as_mut(),
+        env.clone(),
+        SudoMsg::KVQueryResult { query_id: 1 },
+    )
+    .unwrap();
+    assert_eq!(
+        res,
+        Response::new().add_event(
+            Event::new("crates.io:drop-neutron-contracts__drop-staker-sudo-kv-query-result")
+                .add_attributes(vec![
+                    ("action", "sudo_kv_query_result"),
+                    ("ica_balance", "29558778"),
+                    ("remote_height", "1000"),
+                ])
+        )
+    );
+    assert_eq!(
+        ICA_BALANCE.load(deps.as_ref().storage).unwrap(),
+        IcaBalance {
+            amount: Uint128::from(29558778u128),
+            remote_height: 1000,
+            local_height: env.block.height,
+        }
+    );
+    // the same snapshot again is ignored
+    let res = sudo(deps.as_mut(), env, SudoMsg::KVQueryResult { query_id: 1 }).unwrap();
+    assert_eq!(
+        res.events[0].attributes[1],
+        cosmwasm_std::attr("result", "outdated")
+    );
+}
+
+#[test]
+fn test_reconcile() {
+    let mut deps = mock_dependencies(&[]);
+    let deps_mut = deps.as_mut();
+    cw_ownable::initialize_owner(deps_mut.storage, deps_mut.api, Some("owner")).unwrap();
+    TX_STATE
+        .save(deps.as_mut().storage, &TxState::default())
+        .unwrap();
+    NON_STAKED_BALANCE
+        .save(deps.as_mut().storage, &Uint128::from(1000u128))
+        .unwrap();
+    let ica_balance = IcaBalance {
+        amount: Uint128::from(700u128),
+        remote_height: 1000,
+        local_height: 100,
+    };
+    ICA_BALANCE
+        .save(deps.as_mut().storage, &ica_balance)
+        .unwrap();
+    // the last tx was finalized after the snapshot
+    LAST_TX_REMOTE_HEIGHT
+        .save(deps.as_mut().storage, &1000)
+        .unwrap();
+    let reconciliation: ReconciliationResponse = from_json(
+        query(
+            deps.as_ref().into_empty(),
+            mock_env(),
+            drop_staking_base::msg::staker::QueryMsg::Reconciliation {},
+        )
+        .unwrap(),
+    )
+    .unwrap();
+    assert_eq!(
+        reconciliation,
+        ReconciliationResponse {
+            non_staked_balance: Uint128::from(1000u128),
+            ica_balance: Some(ica_balance.clone()),
+            reconcilable: false,
+        }
+    );
+    let res = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("owner", &[]),
+        drop_staking_base::msg::staker::ExecuteMsg::Reconcile {},
+    );
+    assert_eq!(
+        res,
+        Err(ContractError::InvalidState {
+            reason: "ica balance is outdated or tx_state is not idle".to_string()
+        })
+    );
+
+    LAST_TX_REMOTE_HEIGHT
+        .save(deps.as_mut().storage, &999)
+        .unwrap();
+    let res = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("nobody", &[]),
+        drop_staking_base::msg::staker::ExecuteMsg::Reconcile {},
+    );
+    assert_eq!(
+        res,
+        Err(ContractError::OwnershipError(
+            cw_ownable::OwnershipError::NotOwner
+        ))
+    );
+    let res = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("owner", &[]),
+        drop_staking_base::msg::staker::ExecuteMsg::Reconcile {},
+    )
+    .unwrap();
+    assert_eq!(
+        res,
+        Response::new().add_event(
+            Event::new("crates.io:drop-neutron-contracts__drop-staker-reconcile").add_attributes(
+                vec![
+                    ("action", "reconcile"),
+                    ("old_non_staked_balance", "1000"),
+                    ("new_non_staked_balance", "700"),
+                    ("remote_height", "1000"),
+                ]
+            )
+        )
+    );
+    assert_eq!(
+        NON_STAKED_BALANCE.load(deps.as_ref().storage).unwrap(),
+        Uint128::from(700u128)
+    );
+}
+
 #[test]
 fn test_ibc_transfer() {
     let mut deps = mock_dependencies(&[]);
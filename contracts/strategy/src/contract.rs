@@ -1,12 +1,13 @@
 use crate::error::{ContractError, ContractResult};
-use cosmwasm_std::{attr, ensure_eq, to_json_binary, Attribute, Deps, Uint128};
+use cosmwasm_std::{attr, ensure, ensure_eq, to_json_binary, Attribute, Decimal, Deps, Uint128};
 use cosmwasm_std::{Binary, DepsMut, Env, MessageInfo, Response};
 use drop_helpers::answer::response;
 use drop_staking_base::msg::strategy::{
     Config, ConfigOptional, ExecuteMsg, InstantiateMsg, MigrateMsg, QueryMsg,
 };
 use drop_staking_base::state::strategy::{
-    DENOM, DISTRIBUTION_ADDRESS, PUPPETEER_ADDRESS, VALIDATOR_SET_ADDRESS,
+    DENOM, DISTRIBUTION_ADDRESS, MAX_STAKE_SHARE, MAX_VOTING_POWER_SHARE, PUPPETEER_ADDRESS,
+    VALIDATORS_STATS_ADDRESS, VALIDATOR_SET_ADDRESS,
 };
 use neutron_sdk::{
     bindings::{msg::NeutronMsg, query::NeutronQuery},
@@ -16,6 +17,8 @@ use std::collections::HashMap;
 
 const CONTRACT_NAME: &str = concat!("crates.io:drop-staking__", env!("CARGO_PKG_NAME"));
 const CONTRACT_VERSION: &str = env!("CARGO_PKG_VERSION");
+/// Maximum number of simultaneous redelegation entries per validators pair on the remote chain
+const MAX_REDELEGATION_ENTRIES: usize = 7;
 
 #[cfg_attr(not(feature = "library"), cosmwasm_std::entry_point)]
 pub fn instantiate(
@@ -23,7 +26,7 @@ pub fn instantiate(
     _env: Env,
     _info: MessageInfo,
     msg: InstantiateMsg,
-) -> NeutronResult<Response> {
+) -> ContractResult<Response> {
     cw2::set_contract_version(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
     cw_ownable::initialize_owner(deps.storage, deps.api, Some(&msg.owner))?;
 
@@ -38,17 +41,68 @@ pub fn instantiate(
 
     DENOM.save(deps.storage, &msg.denom)?;
 
-    Ok(response(
-        "instantiate",
-        CONTRACT_NAME,
-        [
-            attr("owner", msg.owner),
-            attr("puppeteer_address", msg.puppeteer_address),
-            attr("validator_set_address", msg.validator_set_address),
-            attr("distribution_address", msg.distribution_address),
-            attr("denom", msg.denom),
-        ],
-    ))
+    let mut attrs = vec![
+        attr("owner", msg.owner),
+        attr("puppeteer_address", msg.puppeteer_address),
+        attr("validator_set_address", msg.validator_set_address),
+        attr("distribution_address", msg.distribution_address),
+        attr("denom", msg.denom),
+    ];
+    save_caps(
+        deps,
+        msg.validators_stats_address,
+        msg.max_stake_share,
+        msg.max_voting_power_share,
+        &mut attrs,
+    )?;
+
+    Ok(response("instantiate", CONTRACT_NAME, attrs))
+}
+
+fn save_caps(
+    deps: DepsMut,
+    validators_stats_address: Option<String>,
+    max_stake_share: Option<Decimal>,
+    max_voting_power_share: Option<Decimal>,
+    attrs: &mut Vec<Attribute>,
+) -> ContractResult<()> {
+    if let Some(validators_stats_address) = validators_stats_address {
+        let validators_stats_address = deps.api.addr_validate(&validators_stats_address)?;
+        VALIDATORS_STATS_ADDRESS.save(deps.storage, &validators_stats_address)?;
+        attrs.push(attr("validators_stats_address", validators_stats_address))
+    }
+
+    for (item, key, share) in [
+        (MAX_STAKE_SHARE, "max_stake_share", max_stake_share),
+        (
+            MAX_VOTING_POWER_SHARE,
+            "max_voting_power_share",
+            max_voting_power_share,
+        ),
+    ] {
+        if let Some(share) = share {
+            ensure!(
+                share <= Decimal::one(),
+                ContractError::InvalidShare {
+                    name: key.to_string()
+                }
+            );
+            if share.is_zero() {
+                item.remove(deps.storage);
+            } else {
+                item.save(deps.storage, &share)?;
+            }
+            attrs.push(attr(key, share.to_string()))
+        }
+    }
+
+    ensure!(
+        MAX_VOTING_POWER_SHARE.may_load(deps.storage)?.is_none()
+            || VALIDATORS_STATS_ADDRESS.may_load(deps.storage)?.is_some(),
+        ContractError::ValidatorsStatsAddressNotSet {}
+    );
+
+    Ok(())
 }
 
 #[cfg_attr(not(feature = "library"), cosmwasm_std::entry_point)]
@@ -57,6 +111,10 @@ pub fn query(deps: Deps, env: Env, msg: QueryMsg) -> ContractResult<Binary> {
         QueryMsg::Config {} => query_config(deps, env),
         QueryMsg::CalcDeposit { deposit } => query_calc_deposit(deps, deposit),
         QueryMsg::CalcWithdraw { withdraw } => query_calc_withdraw(deps, withdraw),
+        QueryMsg::CalcRebalance {
+            max_moves,
+            min_amount,
+        } => query_calc_rebalance(deps, max_moves, min_amount),
         QueryMsg::Ownership {} => Ok(to_json_binary(&cw_ownable::get_ownership(deps.storage)?)?),
     }
 }
@@ -66,12 +124,18 @@ fn query_config(deps: Deps, _env: Env) -> ContractResult<Binary> {
     let validator_set_address = VALIDATOR_SET_ADDRESS.load(deps.storage)?.into_string();
     let distribution_address = DISTRIBUTION_ADDRESS.load(deps.storage)?.into_string();
     let denom = DENOM.load(deps.storage)?;
+    let validators_stats_address = VALIDATORS_STATS_ADDRESS
+        .may_load(deps.storage)?
+        .map(|address| address.into_string());
 
     Ok(to_json_binary(&Config {
         puppeteer_address,
         validator_set_address,
         distribution_address,
         denom,
+        validators_stats_address,
+        max_stake_share: MAX_STAKE_SHARE.may_load(deps.storage)?,
+        max_voting_power_share: MAX_VOTING_POWER_SHARE.may_load(deps.storage)?,
     })?)
 }
 
@@ -88,10 +152,10 @@ pub fn query_calc_deposit(deps: Deps, deposit: Uint128) -> ContractResult<Binary
         },
     )?;
 
+    // the distribution never breaks the caps, the rest of the deposit stays undelegated
     let total_deposit_changes: Uint128 = deposit_changes.iter().map(|(_, amount)| amount).sum();
-    ensure_eq!(
-        total_deposit_changes,
-        deposit,
+    ensure!(
+        total_deposit_changes <= deposit,
         ContractError::WrongDepositAndCalculation {}
     );
 
@@ -121,6 +185,112 @@ pub fn query_calc_withdraw(deps: Deps, withdraw: Uint128) -> ContractResult<Bina
     Ok(to_json_binary(&deposit_changes)?)
 }
 
+pub fn query_calc_rebalance(
+    deps: Deps,
+    max_moves: u32,
+    min_amount: Uint128,
+) -> ContractResult<Binary> {
+    let distribution_address = DISTRIBUTION_ADDRESS.load(deps.storage)?.into_string();
+    let puppeteer_address = PUPPETEER_ADDRESS.load(deps.storage)?.into_string();
+
+    let delegations = prepare_delegation_data(deps)?;
+    let current_stakes: Vec<(String, Uint128)> = delegations
+        .delegations
+        .iter()
+        .map(|d| (d.valoper_address.clone(), d.stake))
+        .collect();
+
+    let ideal_stakes: Vec<(String, Uint128)> = deps.querier.query_wasm_smart(
+        distribution_address,
+        &drop_staking_base::msg::distribution::QueryMsg::CalcIdeal { delegations },
+    )?;
+
+    let redelegations: Vec<drop_staking_base::msg::puppeteer::RedelegationEntries> =
+        deps.querier.query_wasm_smart(
+            puppeteer_address,
+            &drop_puppeteer_base::msg::QueryMsg::Extension {
+                msg: drop_staking_base::msg::puppeteer::QueryExtMsg::Redelegations {},
+            },
+        )?;
+
+    Ok(to_json_binary(&calc_rebalance(
+        &current_stakes,
+        &ideal_stakes,
+        &redelegations,
+        max_moves,
+        min_amount,
+    ))?)
+}
+
+/// Greedily matches the most overdelegated validators with the most underdelegated ones.
+/// Pairs which reached the redelegation entries limit are skipped, as well as sources
+/// with incoming redelegations in progress, since transitive redelegations are not allowed.
+pub fn calc_rebalance(
+    current_stakes: &[(String, Uint128)],
+    ideal_stakes: &[(String, Uint128)],
+    redelegations: &[drop_staking_base::msg::puppeteer::RedelegationEntries],
+    max_moves: u32,
+    min_amount: Uint128,
+) -> Vec<(String, String, Uint128)> {
+    let ideal_stakes: HashMap<_, _> = ideal_stakes.iter().cloned().collect();
+    let mut surpluses: Vec<(String, Uint128)> = Vec::new();
+    let mut deficits: Vec<(String, Uint128)> = Vec::new();
+    for (valoper_address, stake) in current_stakes {
+        let ideal_stake = ideal_stakes
+            .get(valoper_address)
+            .copied()
+            .unwrap_or_default();
+        if *stake > ideal_stake {
+            surpluses.push((valoper_address.clone(), *stake - ideal_stake));
+        } else if *stake < ideal_stake {
+            deficits.push((valoper_address.clone(), ideal_stake - *stake));
+        }
+    }
+    surpluses.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(&b.0)));
+    deficits.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(&b.0)));
+
+    let mut pair_entries: HashMap<(&str, &str), usize> = HashMap::new();
+    for entries in redelegations {
+        *pair_entries
+            .entry((
+                entries.validator_from.as_str(),
+                entries.validator_to.as_str(),
+            ))
+            .or_default() += entries.completion_times.len();
+    }
+
+    let mut moves: Vec<(String, String, Uint128)> = Vec::new();
+    for (validator_from, surplus) in surpluses.iter_mut() {
+        if redelegations
+            .iter()
+            .any(|entries| entries.validator_to == *validator_from)
+        {
+            continue;
+        }
+        for (validator_to, deficit) in deficits.iter_mut() {
+            if moves.len() >= max_moves as usize {
+                return moves;
+            }
+            let entries = pair_entries
+                .get(&(validator_from.as_str(), validator_to.as_str()))
+                .copied()
+                .unwrap_or_default();
+            if entries >= MAX_REDELEGATION_ENTRIES {
+                continue;
+            }
+            let amount = (*surplus).min(*deficit);
+            if amount.is_zero() || amount < min_amount {
+                continue;
+            }
+            *surplus -= amount;
+            *deficit -= amount;
+            moves.push((validator_from.clone(), validator_to.clone(), amount));
+        }
+    }
+
+    moves
+}
+
 fn prepare_delegation_data(
     deps: Deps,
 ) -> NeutronResult<drop_staking_base::msg::distribution::Delegations> {
@@ -144,6 +314,28 @@ fn prepare_delegation_data(
     let mut delegations: Vec<drop_staking_base::msg::distribution::Delegation> = Vec::new();
     let mut total_delegations: Uint128 = Uint128::zero();
     let mut total_weight: u64 = 0;
+    // the stake of a validator is capped by a share of its voting power, if it's known
+    let voting_power_caps: HashMap<String, Uint128> = match MAX_VOTING_POWER_SHARE
+        .may_load(deps.storage)?
+    {
+        Some(max_voting_power_share) => {
+            let validators_stats: Vec<drop_staking_base::state::validatorsstats::ValidatorState> =
+                deps.querier.query_wasm_smart(
+                    VALIDATORS_STATS_ADDRESS.load(deps.storage)?,
+                    &drop_staking_base::msg::validatorsstats::QueryMsg::State {},
+                )?;
+            validators_stats
+                .into_iter()
+                .filter_map(|state| {
+                    state
+                        .tokens
+                        .map(|tokens| (state.valoper_address, tokens * max_voting_power_share))
+                })
+                .collect()
+        }
+        None => HashMap::new(),
+    };
+
     let delegation_validator_map: HashMap<_, _> = account_delegations
         .delegations
         .delegations
@@ -162,6 +354,7 @@ fn prepare_delegation_data(
             valoper_address: validator.valoper_address.clone(),
             stake: validator_denom_delegation,
             weight: validator.weight,
+            cap: voting_power_caps.get(&validator.valoper_address).copied(),
         };
 
         total_delegations += validator_denom_delegation;
@@ -173,6 +366,7 @@ fn prepare_delegation_data(
         total: total_delegations,
         total_weight,
         delegations,
+        max_stake_share: MAX_STAKE_SHARE.may_load(deps.storage)?,
     })
 }
 
@@ -228,6 +422,14 @@ fn exec_config_update(
         attrs.push(attr("denom", denom))
     }
 
+    save_caps(
+        deps,
+        new_config.validators_stats_address,
+        new_config.max_stake_share,
+        new_config.max_voting_power_share,
+        &mut attrs,
+    )?;
+
     Ok(response("config_update", CONTRACT_NAME, attrs))
 }
 
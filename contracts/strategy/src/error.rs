@@ -14,12 +14,18 @@ pub enum ContractError {
     #[error("{0}")]
     OwnershipError(#[from] OwnershipError),
 
-    #[error("Incoming deposit and calculated deposit are not equal")]
+    #[error("Calculated deposit exceeds the incoming deposit")]
     WrongDepositAndCalculation {},
 
     #[error("Incoming withdraw and calculated withdraw are not equal")]
     WrongWithdrawAndCalculation {},
 
+    #[error("{name} must not exceed one")]
+    InvalidShare { name: String },
+
+    #[error("Validators stats address is required by the voting power cap")]
+    ValidatorsStatsAddressNotSet {},
+
     #[error("Semver parsing error: {0}")]
     SemVer(String),
 }
@@ -1,4 +1,4 @@
-use crate::contract::instantiate;
+use crate::contract::{calc_rebalance, instantiate};
 
 use cosmwasm_schema::cw_serde;
 use cosmwasm_std::testing::{mock_dependencies, mock_env, mock_info};
@@ -12,6 +12,7 @@ use drop_puppeteer_base::msg::QueryMsg as PuppeteerQueryMsg;
 use drop_puppeteer_base::state::{Delegations, DropDelegation};
 use drop_staking_base::error::distribution::ContractError as DistributionContractError;
 use drop_staking_base::error::validatorset::ContractError as ValidatorSetContractError;
+use drop_staking_base::msg::puppeteer::RedelegationEntries;
 use drop_staking_base::msg::strategy::QueryMsg;
 use drop_staking_base::msg::validatorset::QueryMsg as ValidatorSetQueryMsg;
 use drop_staking_base::msg::{
@@ -76,6 +77,8 @@ fn puppeteer_query(
         PuppeteerQueryMsg::Config {} => todo!(),
         PuppeteerQueryMsg::Ica {} => todo!(),
         PuppeteerQueryMsg::TxState {} => todo!(),
+        PuppeteerQueryMsg::TxQueue {} => todo!(),
+        PuppeteerQueryMsg::TransactionHistory { .. } => todo!(),
         PuppeteerQueryMsg::Transactions {} => todo!(),
         PuppeteerQueryMsg::KVQueryIds {} => todo!(),
         PuppeteerQueryMsg::Extension { msg } => match msg {
@@ -103,6 +106,10 @@ fn puppeteer_query(
                 };
                 Ok(to_json_binary(&delegations)?)
             }
+            drop_staking_base::msg::puppeteer::QueryExtMsg::Redelegations {} => {
+                let redelegations: Vec<RedelegationEntries> = vec![];
+                Ok(to_json_binary(&redelegations)?)
+            }
             _ => todo!(),
         },
     }
@@ -185,6 +192,58 @@ fn instantiate_validator_set_contract(app: &mut App) -> Addr {
     )
 }
 
+fn validators_stats_query(
+    _deps: Deps,
+    _env: Env,
+    msg: drop_staking_base::msg::validatorsstats::QueryMsg,
+) -> StdResult<Binary> {
+    match msg {
+        drop_staking_base::msg::validatorsstats::QueryMsg::State {} => {
+            let states: Vec<drop_staking_base::state::validatorsstats::ValidatorState> = [
+                ("valoper0", Some(1000u128)),
+                ("valoper1", Some(2000u128)),
+                ("valoper2", None),
+            ]
+            .into_iter()
+            .map(|(valoper_address, tokens)| {
+                drop_staking_base::state::validatorsstats::ValidatorState {
+                    valoper_address: valoper_address.to_string(),
+                    valcons_address: "valcons".to_string(),
+                    last_processed_remote_height: None,
+                    last_processed_local_height: None,
+                    last_validated_height: None,
+                    last_commission_in_range: None,
+                    uptime: Decimal::zero(),
+                    tombstone: false,
+                    prev_jailed_state: false,
+                    jailed_number: None,
+                    tokens_per_share: None,
+                    tokens: tokens.map(Uint128::from),
+                }
+            })
+            .collect();
+            Ok(to_json_binary(&states)?)
+        }
+        _ => todo!(),
+    }
+}
+
+fn validators_stats_contract() -> Box<dyn Contract<Empty>> {
+    let contract: ContractWrapper<
+        EmptyMsg,
+        EmptyMsg,
+        drop_staking_base::msg::validatorsstats::QueryMsg,
+        cosmwasm_std::StdError,
+        cosmwasm_std::StdError,
+        cosmwasm_std::StdError,
+    > = ContractWrapper::new(
+        |_, _, _, _: EmptyMsg| Ok(Response::new()),
+        |_, _, _, _: EmptyMsg| Ok(Response::new()),
+        validators_stats_query,
+    );
+    Box::new(contract)
+}
+
 fn strategy_contract() -> Box<dyn Contract<Empty>> {
     let contract = ContractWrapper::new(
         crate::contract::execute,
@@ -219,6 +278,9 @@ fn test_initialization() {
         puppeteer_address: PUPPETEER_CONTRACT_ADDR.to_string(),
         validator_set_address: VALIDATOR_SET_CONTRACT_ADDR.to_string(),
         denom: "uatom".to_string(),
+        validators_stats_address: None,
+        max_stake_share: None,
+        max_voting_power_share: None,
     };
 
     let info = mock_info(CORE_CONTRACT_ADDR, &[]);
@@ -266,6 +328,9 @@ fn test_config_query() {
             puppeteer_address: puppeteer_contract.to_string(),
             validator_set_address: validator_set_contract.to_string(),
             denom: "uatom".to_string(),
+            validators_stats_address: None,
+            max_stake_share: None,
+            max_voting_power_share: None,
         },
     );
 
@@ -281,6 +346,9 @@ fn test_config_query() {
             puppeteer_address: puppeteer_contract.to_string(),
             validator_set_address: validator_set_contract.to_string(),
             denom: "uatom".to_string(),
+            validators_stats_address: None,
+            max_stake_share: None,
+            max_voting_power_share: None,
         }
     );
 }
@@ -303,6 +371,9 @@ fn test_ideal_deposit_calculation() {
             puppeteer_address: puppeteer_contract.to_string(),
             validator_set_address: validator_set_contract.to_string(),
             denom: "uatom".to_string(),
+            validators_stats_address: None,
+            max_stake_share: None,
+            max_voting_power_share: None,
         },
     );
 
@@ -326,6 +397,80 @@ fn test_ideal_deposit_calculation() {
     );
 }
 
+#[test]
+fn test_capped_deposit_calculation() {
+    let mut app = mock_app();
+    let validator_set_contract = instantiate_validator_set_contract(&mut app);
+    let puppeteer_contract = instantiate_puppeteer_contract(&mut app);
+    let distribution_contract = instantiate_distribution_contract(&mut app);
+    let validators_stats_contract = instantiate_contract(
+        &mut app,
+        validators_stats_contract,
+        "drop validators stats contract".to_string(),
+    );
+
+    let strategy_id = app.store_code(strategy_contract());
+
+    let err = app
+        .instantiate_contract(
+            strategy_id,
+            Addr::unchecked(CORE_CONTRACT_ADDR),
+            &InstantiateMsg {
+                owner: CORE_CONTRACT_ADDR.to_string(),
+                distribution_address: distribution_contract.to_string(),
+                puppeteer_address: puppeteer_contract.to_string(),
+                validator_set_address: validator_set_contract.to_string(),
+                denom: "uatom".to_string(),
+                validators_stats_address: None,
+                max_stake_share: None,
+                max_voting_power_share: Some(Decimal::percent(10)),
+            },
+            &[],
+            "strategy contract",
+            None,
+        )
+        .unwrap_err();
+    assert_eq!(
+        err.root_cause().to_string(),
+        "Validators stats address is required by the voting power cap"
+    );
+
+    let strategy_contract = instantiate_strategy_contract(
+        &mut app,
+        strategy_id,
+        InstantiateMsg {
+            owner: CORE_CONTRACT_ADDR.to_string(),
+            distribution_address: distribution_contract.to_string(),
+            puppeteer_address: puppeteer_contract.to_string(),
+            validator_set_address: validator_set_contract.to_string(),
+            denom: "uatom".to_string(),
+            validators_stats_address: Some(validators_stats_contract.to_string()),
+            max_stake_share: Some(Decimal::percent(30)),
+            max_voting_power_share: Some(Decimal::percent(10)),
+        },
+    );
+
+    // valoper0 is at 10% of its voting power, the others get 30% of the total at most
+    // and the rest of the deposit stays undelegated
+    let deposit: Vec<(String, Uint128)> = app
+        .wrap()
+        .query_wasm_smart(
+            strategy_contract,
+            &QueryMsg::CalcDeposit {
+                deposit: 100u128.into(),
+            },
+        )
+        .unwrap();
+
+    assert_eq!(
+        deposit,
+        vec![
+            ("valoper1".to_string(), Uint128::from(20u128)),
+            ("valoper2".to_string(), Uint128::from(20u128))
+        ]
+    );
+}
+
 #[test]
 fn test_ideal_withdraw_calculation() {
     let mut app = mock_app();
@@ -344,6 +489,9 @@ fn test_ideal_withdraw_calculation() {
             puppeteer_address: puppeteer_contract.to_string(),
             validator_set_address: validator_set_contract.to_string(),
             denom: "uatom".to_string(),
+            validators_stats_address: None,
+            max_stake_share: None,
+            max_voting_power_share: None,
         },
     );
 
@@ -366,3 +514,149 @@ fn test_ideal_withdraw_calculation() {
         ]
     );
 }
+
+#[test]
+fn test_rebalance_calculation() {
+    let mut app = mock_app();
+    let validator_set_contract = instantiate_validator_set_contract(&mut app);
+    let puppeteer_contract = instantiate_puppeteer_contract(&mut app);
+    let distribution_contract = instantiate_distribution_contract(&mut app);
+
+    let strategy_id = app.store_code(strategy_contract());
+
+    let strategy_contract = instantiate_strategy_contract(
+        &mut app,
+        strategy_id,
+        InstantiateMsg {
+            owner: CORE_CONTRACT_ADDR.to_string(),
+            distribution_address: distribution_contract.to_string(),
+            puppeteer_address: puppeteer_contract.to_string(),
+            validator_set_address: validator_set_contract.to_string(),
+            denom: "uatom".to_string(),
+            validators_stats_address: None,
+            max_stake_share: None,
+            max_voting_power_share: None,
+        },
+    );
+
+    let moves: Vec<(String, String, Uint128)> = app
+        .wrap()
+        .query_wasm_smart(
+            strategy_contract,
+            &QueryMsg::CalcRebalance {
+                max_moves: 10,
+                min_amount: Uint128::zero(),
+            },
+        )
+        .unwrap();
+
+    assert_eq!(moves, vec![]);
+}
+
+#[test]
+fn test_calc_rebalance() {
+    let current_stakes = vec![
+        ("valoper0".to_string(), Uint128::from(300u128)),
+        ("valoper1".to_string(), Uint128::from(0u128)),
+        ("valoper2".to_string(), Uint128::from(100u128)),
+        ("valoper3".to_string(), Uint128::from(0u128)),
+    ];
+    let ideal_stakes = vec![
+        ("valoper0".to_string(), Uint128::from(100u128)),
+        ("valoper1".to_string(), Uint128::from(120u128)),
+        ("valoper2".to_string(), Uint128::from(100u128)),
+        ("valoper3".to_string(), Uint128::from(80u128)),
+    ];
+
+    let moves = calc_rebalance(&current_stakes, &ideal_stakes, &[], 10, Uint128::zero());
+    assert_eq!(
+        moves,
+        vec![
+            (
+                "valoper0".to_string(),
+                "valoper1".to_string(),
+                Uint128::from(120u128)
+            ),
+            (
+                "valoper0".to_string(),
+                "valoper3".to_string(),
+                Uint128::from(80u128)
+            ),
+        ]
+    );
+
+    let moves = calc_rebalance(&current_stakes, &ideal_stakes, &[], 1, Uint128::zero());
+    assert_eq!(
+        moves,
+        vec![(
+            "valoper0".to_string(),
+            "valoper1".to_string(),
+            Uint128::from(120u128)
+        )]
+    );
+
+    let moves = calc_rebalance(
+        &current_stakes,
+        &ideal_stakes,
+        &[],
+        10,
+        Uint128::from(100u128),
+    );
+    assert_eq!(
+        moves,
+        vec![(
+            "valoper0".to_string(),
+            "valoper1".to_string(),
+            Uint128::from(120u128)
+        )]
+    );
+}
+
+#[test]
+fn test_calc_rebalance_redelegation_limits() {
+    let current_stakes = vec![
+        ("valoper0".to_string(), Uint128::from(300u128)),
+        ("valoper1".to_string(), Uint128::from(0u128)),
+        ("valoper2".to_string(), Uint128::from(0u128)),
+    ];
+    let ideal_stakes = vec![
+        ("valoper0".to_string(), Uint128::from(100u128)),
+        ("valoper1".to_string(), Uint128::from(100u128)),
+        ("valoper2".to_string(), Uint128::from(100u128)),
+    ];
+    let full_pair = RedelegationEntries {
+        validator_from: "valoper0".to_string(),
+        validator_to: "valoper1".to_string(),
+        completion_times: vec![1, 2, 3, 4, 5, 6, 7],
+    };
+
+    let moves = calc_rebalance(
+        &current_stakes,
+        &ideal_stakes,
+        &[full_pair],
+        10,
+        Uint128::zero(),
+    );
+    assert_eq!(
+        moves,
+        vec![(
+            "valoper0".to_string(),
+            "valoper2".to_string(),
+            Uint128::from(100u128)
+        )]
+    );
+
+    let incoming = RedelegationEntries {
+        validator_from: "valoper2".to_string(),
+        validator_to: "valoper0".to_string(),
+        completion_times: vec![1],
+    };
+    let moves = calc_rebalance(
+        &current_stakes,
+        &ideal_stakes,
+        &[incoming],
+        10,
+        Uint128::zero(),
+    );
+    assert_eq!(moves, vec![]);
+}
@@ -1,12 +1,22 @@
 use bech32::{encode, Bech32, Hrp};
-use cosmwasm_std::{to_json_binary, Decimal, Deps, Order, Reply, StdError, SubMsg};
+use cosmwasm_std::{
+    attr, to_json_binary, CosmosMsg, Decimal, Decimal256, Deps, Order, Reply, StdError, Storage,
+    SubMsg, SubMsgResult, Uint128, WasmMsg,
+};
 use cosmwasm_std::{Binary, DepsMut, Env, MessageInfo, Response, StdResult};
+use cw_storage_plus::Bound;
+use drop_helpers::answer::response;
 use drop_helpers::query_id::get_query_id;
+use drop_staking_base::msg::validatorset::{
+    ExecuteMsg as ValidatorSetExecuteMsg, ValidatorInfoUpdate,
+};
 use drop_staking_base::msg::validatorsstats::{ExecuteMsg, InstantiateMsg, MigrateMsg, QueryMsg};
 use drop_staking_base::state::validatorsstats::{
-    Config, KVQueryIds, MissedBlocks, ValidatorMissedBlocksForPeriod, ValidatorState, CONFIG,
-    MISSED_BLOCKS, SIGNING_INFO_QUERY_ID, SIGNING_INFO_REPLY_ID, STATE_MAP, VALCONS_TO_VALOPER,
-    VALIDATOR_PROFILE_QUERY_ID, VALIDATOR_PROFILE_REPLY_ID,
+    slash_events_map, Config, KVQueryIds, MissedBlocks, SlashEvent, ValidatorState, CONFIG,
+    LEGACY_MISSED_BLOCKS_KEY, MISSED_BLOCKS, MISSED_BLOCKS_WINDOW, PUSH_CURSOR,
+    SIGNING_INFO_QUERY_ID, SIGNING_INFO_REPLY_ID, SLASH_EVENT_ID, STATE_MAP,
+    UPDATE_VALIDATORS_INFO_REPLY_ID, VALCONS_TO_VALOPER, VALIDATOR_PROFILE_QUERY_ID,
+    VALIDATOR_PROFILE_REPLY_ID,
 };
 use neutron_sdk::bindings::query::QueryRegisteredQueryResultResponse;
 use neutron_sdk::interchain_queries::queries::get_raw_interchain_query_result;
@@ -22,9 +32,13 @@ use neutron_sdk::{
     NeutronResult,
 };
 use sha2::{Digest, Sha256};
+use std::str::FromStr;
 
 const CONTRACT_NAME: &str = concat!("crates.io:drop-staking__", env!("CARGO_PKG_NAME"));
 const CONTRACT_VERSION: &str = env!("CARGO_PKG_VERSION");
+const DECIMAL_PLACES: u32 = 18;
+pub const SLASH_EVENTS_DEFAULT_LIMIT: u32 = 100;
+pub const PUSH_PAGE_SIZE: usize = 50;
 
 #[cfg_attr(not(feature = "library"), cosmwasm_std::entry_point)]
 pub fn instantiate(
@@ -36,6 +50,10 @@ pub fn instantiate(
     cw2::set_contract_version(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
 
     let owner = deps.api.addr_validate(&msg.owner)?;
+    let validators_set_address = msg
+        .validators_set_address
+        .map(|address| deps.api.addr_validate(&address))
+        .transpose()?;
 
     let config = &Config {
         connection_id: msg.connection_id,
@@ -44,6 +62,7 @@ pub fn instantiate(
         info_update_period: msg.info_update_period,
         avg_block_time: msg.avg_block_time,
         owner,
+        validators_set_address,
     };
 
     cw_ownable::initialize_owner(deps.storage, deps.api, Some(&msg.owner))?;
@@ -59,9 +78,39 @@ pub fn query(deps: Deps<NeutronQuery>, env: Env, msg: QueryMsg) -> StdResult<Bin
         QueryMsg::State {} => query_state(deps, env),
         QueryMsg::Config {} => query_config(deps, env),
         QueryMsg::KVQueryIds {} => query_kv_query_ids(deps, env),
+        QueryMsg::SlashEvents {
+            valoper,
+            start_after,
+            limit,
+        } => query_slash_events(deps, valoper, start_after, limit),
     }
 }
 
+fn query_slash_events(
+    deps: Deps<NeutronQuery>,
+    valoper: Option<String>,
+    start_after: Option<u64>,
+    limit: Option<u32>,
+) -> StdResult<Binary> {
+    let limit = limit.unwrap_or(SLASH_EVENTS_DEFAULT_LIMIT) as usize;
+    let min = start_after.map(Bound::exclusive);
+    let events: Vec<(u64, SlashEvent)> = match valoper {
+        Some(valoper) => slash_events_map()
+            .idx
+            .valoper
+            .prefix(valoper)
+            .range(deps.storage, min, None, Order::Ascending)
+            .take(limit)
+            .collect::<StdResult<_>>()?,
+        None => slash_events_map()
+            .range(deps.storage, min, None, Order::Ascending)
+            .take(limit)
+            .collect::<StdResult<_>>()?,
+    };
+
+    to_json_binary(&events)
+}
+
 fn query_kv_query_ids(deps: Deps<NeutronQuery>, _env: Env) -> StdResult<Binary> {
     to_json_binary(&KVQueryIds {
         signing_info_id: SIGNING_INFO_QUERY_ID
@@ -98,7 +147,87 @@ pub fn execute(
     // TODO: Add block time change support
     match msg {
         ExecuteMsg::RegisterStatsQueries { validators } => register_stats_queries(deps, validators),
+        ExecuteMsg::Push {} => execute_push(deps),
+    }
+}
+
+fn execute_push(deps: DepsMut<NeutronQuery>) -> NeutronResult<Response<NeutronMsg>> {
+    let config = CONFIG.load(deps.storage)?;
+    let validators_set_address = config
+        .validators_set_address
+        .ok_or_else(|| StdError::generic_err("validators set address is not configured"))?;
+
+    let mut start_after = PUSH_CURSOR.may_load(deps.storage)?;
+    let mut validators = push_page(deps.storage, start_after.clone())?;
+    if validators.is_empty() && start_after.is_some() {
+        start_after = None;
+        validators = push_page(deps.storage, None)?;
+    }
+    match validators.last() {
+        Some((last, _)) if validators.len() == PUSH_PAGE_SIZE => {
+            PUSH_CURSOR.save(deps.storage, last)?
+        }
+        _ => PUSH_CURSOR.remove(deps.storage),
     }
+    let validators: Vec<ValidatorState> =
+        validators.into_iter().map(|(_key, value)| value).collect();
+
+    Ok(response(
+        "execute-push",
+        CONTRACT_NAME,
+        [
+            attr("validators_set_address", validators_set_address.to_string()),
+            attr("start_after", start_after.unwrap_or_default()),
+            attr("count", validators.len().to_string()),
+        ],
+    )
+    .add_submessage(update_validators_info_msg(
+        validators_set_address.to_string(),
+        &validators,
+    )?))
+}
+
+fn push_page(
+    storage: &dyn Storage,
+    start_after: Option<String>,
+) -> StdResult<Vec<(String, ValidatorState)>> {
+    STATE_MAP
+        .range(
+            storage,
+            start_after.map(Bound::exclusive),
+            None,
+            Order::Ascending,
+        )
+        .take(PUSH_PAGE_SIZE)
+        .collect()
+}
+
+/// Pushes validators info to the validators set. Sent as a reply-on-error
+/// submessage so a failing validators set does not revert the stats update.
+fn update_validators_info_msg(
+    validators_set_address: String,
+    validators: &[ValidatorState],
+) -> StdResult<SubMsg<NeutronMsg>> {
+    let msg = CosmosMsg::Wasm(WasmMsg::Execute {
+        contract_addr: validators_set_address,
+        msg: to_json_binary(&ValidatorSetExecuteMsg::UpdateValidatorsInfo {
+            validators: validators
+                .iter()
+                .map(|validator| ValidatorInfoUpdate {
+                    valoper_address: validator.valoper_address.clone(),
+                    last_processed_remote_height: validator.last_processed_remote_height,
+                    last_processed_local_height: validator.last_processed_local_height,
+                    last_validated_height: validator.last_validated_height,
+                    last_commission_in_range: validator.last_commission_in_range,
+                    uptime: validator.uptime,
+                    tombstone: validator.tombstone,
+                    jailed_number: validator.jailed_number,
+                })
+                .collect(),
+        })?,
+        funds: vec![],
+    });
+    Ok(SubMsg::reply_on_error(msg, UPDATE_VALIDATORS_INFO_REPLY_ID))
 }
 
 fn register_stats_queries(
@@ -187,6 +316,8 @@ fn sudo_validator_info(
         return register_signing_infos_query(deps, data.validators);
     }
 
+    let mut attrs = vec![];
+
     for validator in data.validators.iter() {
         let mut validator_state = get_validator_state(&deps, validator.operator_address.clone())?;
 
@@ -209,6 +340,36 @@ fn sudo_validator_info(
             validator_state.last_commission_in_range
         };
 
+        if let Some(tokens_per_share) =
+            calculate_tokens_per_share(&validator.tokens, &validator.delegator_shares)?
+        {
+            if let Some(prev_tokens_per_share) = validator_state.tokens_per_share {
+                if tokens_per_share < prev_tokens_per_share {
+                    let slash_event = SlashEvent {
+                        valoper_address: validator.operator_address.clone(),
+                        remote_height: interchain_query_result.result.height,
+                        local_height: env.block.height,
+                        timestamp: env.block.time.seconds(),
+                        prev_tokens_per_share,
+                        tokens_per_share,
+                        slash_fraction: calculate_slash_fraction(
+                            prev_tokens_per_share,
+                            tokens_per_share,
+                        )?,
+                    };
+                    let slash_event_id = SLASH_EVENT_ID
+                        .may_load(deps.storage)?
+                        .map_or(0, |id| id + 1);
+                    slash_events_map().save(deps.storage, slash_event_id, &slash_event)?;
+                    SLASH_EVENT_ID.save(deps.storage, &slash_event_id)?;
+                    attrs.push(attr("slash_event_id", slash_event_id.to_string()));
+                    attrs.push(attr("slashed_validator", &validator.operator_address));
+                }
+            }
+            validator_state.tokens_per_share = Some(tokens_per_share);
+        }
+        validator_state.tokens = Some(Uint128::from_str(&validator.tokens)?);
+
         validator_state.jailed_number = if !validator_state.prev_jailed_state && validator.jailed {
             validator_state.prev_jailed_state = true;
             Some(validator_state.jailed_number.unwrap_or(0) + 1)
@@ -226,7 +387,33 @@ fn sudo_validator_info(
         )?;
     }
 
-    Ok(Response::new())
+    Ok(Response::new().add_attributes(attrs))
+}
+
+/// Returns validator tokens per delegator share, `None` if the validator has no shares.
+/// `delegator_shares` is an sdk.Dec serialized with 18 decimal places
+fn calculate_tokens_per_share(
+    tokens: &str,
+    delegator_shares: &str,
+) -> StdResult<Option<Decimal256>> {
+    let delegator_shares =
+        Decimal256::from_atomics(Uint128::from_str(delegator_shares)?, DECIMAL_PLACES)
+            .map_err(|e| StdError::generic_err(e.to_string()))?;
+    if delegator_shares.is_zero() {
+        return Ok(None);
+    }
+    let tokens = Decimal256::from_atomics(Uint128::from_str(tokens)?, 0)
+        .map_err(|e| StdError::generic_err(e.to_string()))?;
+
+    Ok(Some(tokens / delegator_shares))
+}
+
+fn calculate_slash_fraction(
+    prev_tokens_per_share: Decimal256,
+    tokens_per_share: Decimal256,
+) -> StdResult<Decimal> {
+    let slash_fraction = (prev_tokens_per_share - tokens_per_share) / prev_tokens_per_share;
+    Decimal::try_from(slash_fraction).map_err(|e| StdError::generic_err(e.to_string()))
 }
 
 // TODO: move min/max commission to config
@@ -243,6 +430,8 @@ fn sudo_signing_info(
     deps.api
         .debug(&format!("WASMDEBUG: signing_info_sudo data: {data:?}",));
 
+    let mut updated_validators: Vec<ValidatorState> = Vec::new();
+
     for info in data.signing_infos.iter() {
         let valoper_address = VALCONS_TO_VALOPER.may_load(deps.storage, info.address.clone())?;
 
@@ -254,21 +443,6 @@ fn sudo_signing_info(
             continue;
         }
 
-        // all_missed_blocks contains all missed blocks for all validators for the specific period
-        let mut all_missed_blocks = MISSED_BLOCKS.may_load(deps.storage)?.unwrap_or_default();
-        if !all_missed_blocks.is_empty()
-            && all_missed_blocks[0].timestamp <= env.block.time.seconds() - 60 * 60 * 24 * 30
-        // TODO: move timeout to config
-        {
-            all_missed_blocks.remove(0);
-        }
-
-        let mut missed_blocks = MissedBlocks {
-            remote_height: interchain_query_result.result.height,
-            timestamp: env.block.time.seconds(),
-            validators: Vec::new(),
-        };
-
         if let Some(address) = valoper_address {
             let mut validator_state = get_validator_state(&deps, address.clone())?;
 
@@ -279,60 +453,98 @@ fn sudo_signing_info(
                 validator_state.tombstone
             };
 
-            let missed_blocks_percent = calucalate_missed_blocks_percent(
-                &all_missed_blocks,
-                &mut missed_blocks,
-                address.clone(),
-                info.missed_blocks_counter as u64,
-            );
+            prune_missed_blocks(
+                deps.storage,
+                &address,
+                env.block
+                    .time
+                    .seconds()
+                    .saturating_sub(MISSED_BLOCKS_WINDOW),
+            )?;
+
+            let missed_blocks = MissedBlocks {
+                remote_height: interchain_query_result.result.height,
+                missed_blocks: info.missed_blocks_counter as u64,
+            };
+
+            let missed_blocks_percent =
+                calucalate_missed_blocks_percent(deps.storage, &address, &missed_blocks)?;
+
+            MISSED_BLOCKS.save(
+                deps.storage,
+                (address.clone(), env.block.time.seconds()),
+                &missed_blocks,
+            )?;
 
-            validator_state.uptime = Decimal::one() - missed_blocks_percent;
+            validator_state.uptime = Decimal::one().saturating_sub(missed_blocks_percent);
 
             STATE_MAP.save(deps.storage, address.clone(), &validator_state)?;
+            updated_validators.push(validator_state);
         }
+    }
 
-        all_missed_blocks.push(missed_blocks);
-
-        MISSED_BLOCKS.save(deps.storage, &all_missed_blocks)?;
+    let config = CONFIG.load(deps.storage)?;
+    let mut msgs: Vec<SubMsg<NeutronMsg>> = Vec::new();
+    if let Some(validators_set_address) = config.validators_set_address {
+        if !updated_validators.is_empty() {
+            msgs.push(update_validators_info_msg(
+                validators_set_address.to_string(),
+                &updated_validators,
+            )?);
+        }
     }
 
-    Ok(Response::new())
+    Ok(Response::new().add_submessages(msgs))
 }
 
-// TODO: Implement tests
-fn calucalate_missed_blocks_percent(
-    all_missed_blocks: &Vec<MissedBlocks>,
-    missed_blocks: &mut MissedBlocks,
-    address: String,
-    missed_blocks_counter: u64,
-) -> Decimal {
-    let validator_missed_blocks = ValidatorMissedBlocksForPeriod {
-        address: address.clone(),
-        missed_blocks: missed_blocks_counter,
-    };
-
-    missed_blocks.validators.push(validator_missed_blocks);
+pub(crate) fn prune_missed_blocks(
+    storage: &mut dyn Storage,
+    address: &str,
+    older_than: u64,
+) -> StdResult<()> {
+    let stale_timestamps = MISSED_BLOCKS
+        .prefix(address.to_string())
+        .keys(
+            storage,
+            None,
+            Some(Bound::exclusive(older_than)),
+            Order::Ascending,
+        )
+        .collect::<StdResult<Vec<u64>>>()?;
+
+    for timestamp in stale_timestamps {
+        MISSED_BLOCKS.remove(storage, (address.to_string(), timestamp));
+    }
 
-    let total_blocks_diff = missed_blocks.remote_height
-        - (if !all_missed_blocks.is_empty() {
-            all_missed_blocks[0].remote_height
-        } else {
-            missed_blocks.remote_height
-        });
+    Ok(())
+}
 
-    let sum_missed_blocks: u64 = all_missed_blocks
-        .iter()
-        .flat_map(|x| &x.validators)
-        .filter(|x| x.address == address)
-        .map(|inner| inner.missed_blocks)
-        .sum();
+pub(crate) fn calucalate_missed_blocks_percent(
+    storage: &dyn Storage,
+    address: &str,
+    missed_blocks: &MissedBlocks,
+) -> StdResult<Decimal> {
+    let mut first_remote_height: Option<u64> = None;
+    let mut sum_missed_blocks = missed_blocks.missed_blocks;
+
+    for item in
+        MISSED_BLOCKS
+            .prefix(address.to_string())
+            .range(storage, None, None, Order::Ascending)
+    {
+        let (_timestamp, sample) = item?;
+        first_remote_height.get_or_insert(sample.remote_height);
+        sum_missed_blocks += sample.missed_blocks;
+    }
 
-    let sum_missed_blocks = sum_missed_blocks + missed_blocks_counter;
+    let total_blocks_diff = missed_blocks
+        .remote_height
+        .saturating_sub(first_remote_height.unwrap_or(missed_blocks.remote_height));
 
     if total_blocks_diff == 0 {
-        Decimal::zero()
+        Ok(Decimal::zero())
     } else {
-        Decimal::from_ratio(sum_missed_blocks, total_blocks_diff)
+        Ok(Decimal::from_ratio(sum_missed_blocks, total_blocks_diff))
     }
 }
 
@@ -384,6 +596,8 @@ fn get_validator_state(
             tombstone: false,
             prev_jailed_state: false,
             jailed_number: Some(0),
+            tokens_per_share: None,
+            tokens: None,
         });
 
     Ok(validator_state)
@@ -419,6 +633,7 @@ pub fn reply(deps: DepsMut, env: Env, msg: Reply) -> StdResult<Response> {
     match msg.id {
         VALIDATOR_PROFILE_REPLY_ID => validator_info_reply(deps, env, msg),
         SIGNING_INFO_REPLY_ID => signing_info_reply(deps, env, msg),
+        UPDATE_VALIDATORS_INFO_REPLY_ID => update_validators_info_reply(deps, env, msg),
         _ => Err(StdError::generic_err(format!(
             "unsupported reply message id {}",
             msg.id
@@ -448,6 +663,23 @@ fn signing_info_reply(deps: DepsMut, _env: Env, msg: Reply) -> StdResult<Respons
     Ok(Response::new())
 }
 
+fn update_validators_info_reply(deps: DepsMut, _env: Env, msg: Reply) -> StdResult<Response> {
+    deps.api.debug(&format!(
+        "WASMDEBUG: update_validators_info_reply call: {msg:?}",
+    ));
+
+    let error = match msg.result {
+        SubMsgResult::Err(error) => error,
+        SubMsgResult::Ok(_) => String::new(),
+    };
+
+    Ok(response(
+        "reply-update-validators-info",
+        CONTRACT_NAME,
+        [attr("status", "failed"), attr("error", error)],
+    ))
+}
+
 #[cfg_attr(not(feature = "library"), cosmwasm_std::entry_point)]
 pub fn migrate(
     deps: DepsMut<NeutronQuery>,
@@ -466,7 +698,7 @@ pub fn migrate(
         cw2::set_contract_version(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
     }
 
+    deps.storage.remove(LEGACY_MISSED_BLOCKS_KEY);
+
     Ok(Response::new())
 }
-
-// TODO: add tests
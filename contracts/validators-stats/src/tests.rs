@@ -0,0 +1,473 @@
+use cosmos_sdk_proto::cosmos::staking::v1beta1::{
+    Commission, CommissionRates, Validator as CosmosValidator,
+};
+use cosmwasm_std::{
+    attr, from_json,
+    testing::{mock_env, mock_info},
+    to_json_binary, Addr, Binary, CosmosMsg, Decimal, Decimal256, Event, Order, Reply, ReplyOn,
+    Response, StdResult, SubMsgResult, Uint128, WasmMsg,
+};
+use drop_helpers::testing::mock_dependencies;
+use drop_staking_base::{
+    msg::validatorset::{ExecuteMsg as ValidatorSetExecuteMsg, ValidatorInfoUpdate},
+    state::validatorsstats::{
+        slash_events_map, Config, MissedBlocks, SlashEvent, ValidatorState, CONFIG, MISSED_BLOCKS,
+        PUSH_CURSOR, SIGNING_INFO_QUERY_ID, STATE_MAP, UPDATE_VALIDATORS_INFO_REPLY_ID,
+        VALIDATOR_PROFILE_QUERY_ID,
+    },
+};
+use neutron_sdk::{
+    bindings::{
+        query::QueryRegisteredQueryResultResponse,
+        types::{InterchainQueryResult, StorageValue},
+    },
+    sudo::msg::SudoMsg,
+};
+use prost::Message;
+
+fn get_default_config(validators_set_address: Option<&str>) -> Config {
+    Config {
+        connection_id: "connection-0".to_string(),
+        port_id: "transfer".to_string(),
+        profile_update_period: 100,
+        info_update_period: 100,
+        avg_block_time: 5,
+        owner: Addr::unchecked("owner"),
+        validators_set_address: validators_set_address.map(Addr::unchecked),
+    }
+}
+
+fn get_validator_state(valoper_address: &str) -> ValidatorState {
+    ValidatorState {
+        valoper_address: valoper_address.to_string(),
+        valcons_address: "valcons".to_string(),
+        last_processed_remote_height: Some(10),
+        last_processed_local_height: Some(20),
+        last_validated_height: Some(20),
+        last_commission_in_range: Some(20),
+        uptime: Decimal::percent(99),
+        tombstone: false,
+        prev_jailed_state: false,
+        jailed_number: Some(1),
+        tokens_per_share: None,
+        tokens: None,
+    }
+}
+
+fn save_missed_blocks(
+    storage: &mut dyn cosmwasm_std::Storage,
+    address: &str,
+    timestamp: u64,
+    remote_height: u64,
+    missed_blocks: u64,
+) {
+    MISSED_BLOCKS
+        .save(
+            storage,
+            (address.to_string(), timestamp),
+            &MissedBlocks {
+                remote_height,
+                missed_blocks,
+            },
+        )
+        .unwrap();
+}
+
+#[test]
+fn prune_missed_blocks() {
+    let mut deps = mock_dependencies(&[]);
+    save_missed_blocks(deps.as_mut().storage, "valoper1", 100, 1000, 1);
+    save_missed_blocks(deps.as_mut().storage, "valoper1", 200, 1100, 2);
+    save_missed_blocks(deps.as_mut().storage, "valoper1", 300, 1200, 3);
+    save_missed_blocks(deps.as_mut().storage, "valoper2", 100, 1000, 4);
+
+    crate::contract::prune_missed_blocks(deps.as_mut().storage, "valoper1", 300).unwrap();
+
+    let keys = MISSED_BLOCKS
+        .keys(
+            deps.as_ref().storage,
+            None,
+            None,
+            cosmwasm_std::Order::Ascending,
+        )
+        .collect::<cosmwasm_std::StdResult<Vec<_>>>()
+        .unwrap();
+    assert_eq!(
+        keys,
+        vec![("valoper1".to_string(), 300), ("valoper2".to_string(), 100)]
+    );
+}
+
+#[test]
+fn calculate_missed_blocks_percent() {
+    let mut deps = mock_dependencies(&[]);
+    save_missed_blocks(deps.as_mut().storage, "valoper1", 100, 1000, 10);
+    save_missed_blocks(deps.as_mut().storage, "valoper1", 200, 1100, 20);
+    save_missed_blocks(deps.as_mut().storage, "valoper2", 50, 500, 400);
+
+    let percent = crate::contract::calucalate_missed_blocks_percent(
+        deps.as_ref().storage,
+        "valoper1",
+        &MissedBlocks {
+            remote_height: 1200,
+            missed_blocks: 30,
+        },
+    )
+    .unwrap();
+    assert_eq!(percent, Decimal::from_ratio(60u64, 200u64));
+}
+
+#[test]
+fn calculate_missed_blocks_percent_no_history() {
+    let deps = mock_dependencies(&[]);
+
+    let percent = crate::contract::calucalate_missed_blocks_percent(
+        deps.as_ref().storage,
+        "valoper1",
+        &MissedBlocks {
+            remote_height: 1200,
+            missed_blocks: 30,
+        },
+    )
+    .unwrap();
+    assert_eq!(percent, Decimal::zero());
+}
+
+#[test]
+fn push_without_validators_set() {
+    let mut deps = mock_dependencies(&[]);
+    CONFIG
+        .save(deps.as_mut().storage, &get_default_config(None))
+        .unwrap();
+
+    let error = crate::contract::execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("anyone", &[]),
+        drop_staking_base::msg::validatorsstats::ExecuteMsg::Push {},
+    )
+    .unwrap_err();
+    assert_eq!(
+        error,
+        neutron_sdk::NeutronError::Std(cosmwasm_std::StdError::generic_err(
+            "validators set address is not configured"
+        ))
+    );
+}
+
+#[test]
+fn push() {
+    let mut deps = mock_dependencies(&[]);
+    CONFIG
+        .save(
+            deps.as_mut().storage,
+            &get_default_config(Some("validators_set")),
+        )
+        .unwrap();
+    STATE_MAP
+        .save(
+            deps.as_mut().storage,
+            "valoper1".to_string(),
+            &get_validator_state("valoper1"),
+        )
+        .unwrap();
+
+    let response = crate::contract::execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("anyone", &[]),
+        drop_staking_base::msg::validatorsstats::ExecuteMsg::Push {},
+    )
+    .unwrap();
+
+    assert_eq!(response.messages.len(), 1);
+    assert_eq!(response.messages[0].id, UPDATE_VALIDATORS_INFO_REPLY_ID);
+    assert_eq!(response.messages[0].reply_on, ReplyOn::Error);
+    assert_eq!(
+        response.messages[0].msg,
+        CosmosMsg::Wasm(WasmMsg::Execute {
+            contract_addr: "validators_set".to_string(),
+            msg: to_json_binary(&ValidatorSetExecuteMsg::UpdateValidatorsInfo {
+                validators: vec![ValidatorInfoUpdate {
+                    valoper_address: "valoper1".to_string(),
+                    last_processed_remote_height: Some(10),
+                    last_processed_local_height: Some(20),
+                    last_validated_height: Some(20),
+                    last_commission_in_range: Some(20),
+                    uptime: Decimal::percent(99),
+                    tombstone: false,
+                    jailed_number: Some(1),
+                }],
+            })
+            .unwrap(),
+            funds: vec![],
+        })
+    );
+    assert_eq!(
+        response.events[0].attributes,
+        vec![
+            attr("validators_set_address", "validators_set"),
+            attr("start_after", ""),
+            attr("count", "1"),
+        ]
+    );
+    assert_eq!(PUSH_CURSOR.may_load(deps.as_ref().storage).unwrap(), None);
+}
+
+#[test]
+fn push_pages() {
+    let mut deps = mock_dependencies(&[]);
+    CONFIG
+        .save(
+            deps.as_mut().storage,
+            &get_default_config(Some("validators_set")),
+        )
+        .unwrap();
+    for i in 0..=crate::contract::PUSH_PAGE_SIZE {
+        let valoper_address = format!("valoper{:03}", i);
+        STATE_MAP
+            .save(
+                deps.as_mut().storage,
+                valoper_address.clone(),
+                &get_validator_state(&valoper_address),
+            )
+            .unwrap();
+    }
+    let mut push = || {
+        crate::contract::execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("anyone", &[]),
+            drop_staking_base::msg::validatorsstats::ExecuteMsg::Push {},
+        )
+        .unwrap()
+        .events[0]
+            .attributes[1..]
+            .to_vec()
+    };
+
+    assert_eq!(push(), vec![attr("start_after", ""), attr("count", "50")]);
+    assert_eq!(
+        push(),
+        vec![attr("start_after", "valoper049"), attr("count", "1")]
+    );
+    assert_eq!(push(), vec![attr("start_after", ""), attr("count", "50")]);
+    assert_eq!(
+        PUSH_CURSOR.load(deps.as_ref().storage).unwrap(),
+        "valoper049"
+    );
+}
+
+#[test]
+fn update_validators_info_reply_error() {
+    let mut deps = mock_dependencies(&[]);
+
+    let response = crate::contract::reply(
+        deps.as_mut().into_empty(),
+        mock_env(),
+        Reply {
+            id: UPDATE_VALIDATORS_INFO_REPLY_ID,
+            result: SubMsgResult::Err("validators set failure".to_string()),
+        },
+    )
+    .unwrap();
+    assert_eq!(
+        response,
+        Response::new().add_event(
+            Event::new(
+                "crates.io:drop-staking__drop-validators-stats-reply-update-validators-info"
+            )
+            .add_attributes(vec![
+                attr("status", "failed"),
+                attr("error", "validators set failure"),
+            ])
+        )
+    );
+}
+
+fn get_slash_event(valoper_address: &str, remote_height: u64) -> SlashEvent {
+    SlashEvent {
+        valoper_address: valoper_address.to_string(),
+        remote_height,
+        local_height: remote_height + 1,
+        timestamp: 1000,
+        prev_tokens_per_share: Decimal256::one(),
+        tokens_per_share: Decimal256::percent(95),
+        slash_fraction: Decimal::percent(5),
+    }
+}
+
+#[test]
+fn query_slash_events() {
+    let mut deps = mock_dependencies(&[]);
+    slash_events_map()
+        .save(deps.as_mut().storage, 0, &get_slash_event("valoper1", 10))
+        .unwrap();
+    slash_events_map()
+        .save(deps.as_mut().storage, 1, &get_slash_event("valoper2", 20))
+        .unwrap();
+    slash_events_map()
+        .save(deps.as_mut().storage, 2, &get_slash_event("valoper1", 30))
+        .unwrap();
+
+    let response = crate::contract::query(
+        deps.as_ref(),
+        mock_env(),
+        drop_staking_base::msg::validatorsstats::QueryMsg::SlashEvents {
+            valoper: None,
+            start_after: Some(0),
+            limit: None,
+        },
+    )
+    .unwrap();
+    assert_eq!(
+        from_json::<Vec<(u64, SlashEvent)>>(&response).unwrap(),
+        vec![
+            (1, get_slash_event("valoper2", 20)),
+            (2, get_slash_event("valoper1", 30))
+        ]
+    );
+
+    let response = crate::contract::query(
+        deps.as_ref(),
+        mock_env(),
+        drop_staking_base::msg::validatorsstats::QueryMsg::SlashEvents {
+            valoper: Some("valoper1".to_string()),
+            start_after: None,
+            limit: Some(1),
+        },
+    )
+    .unwrap();
+    assert_eq!(
+        from_json::<Vec<(u64, SlashEvent)>>(&response).unwrap(),
+        vec![(0, get_slash_event("valoper1", 10))]
+    );
+}
+
+fn build_validator(valoper_address: &str, tokens: &str, delegator_shares: &str) -> StorageValue {
+    StorageValue {
+        storage_prefix: "staking".to_string(),
+        key: Binary::from(valoper_address.as_bytes()),
+        value: Binary::from(
+            CosmosValidator {
+                operator_address: valoper_address.to_string(),
+                consensus_pubkey: None,
+                jailed: false,
+                status: 3,
+                tokens: tokens.to_string(),
+                delegator_shares: delegator_shares.to_string(),
+                description: None,
+                unbonding_height: 0,
+                unbonding_time: None,
+                commission: Some(Commission {
+                    commission_rates: Some(CommissionRates {
+                        rate: "50000000000000000".to_string(),
+                        max_rate: "100000000000000000".to_string(),
+                        max_change_rate: "10000000000000000".to_string(),
+                    }),
+                    update_time: None,
+                }),
+                min_self_delegation: "1".to_string(),
+            }
+            .encode_to_vec(),
+        ),
+    }
+}
+
+#[test]
+fn detect_slashing() {
+    let mut deps = mock_dependencies(&[]);
+    CONFIG
+        .save(deps.as_mut().storage, &get_default_config(None))
+        .unwrap();
+    VALIDATOR_PROFILE_QUERY_ID
+        .save(deps.as_mut().storage, &1)
+        .unwrap();
+    SIGNING_INFO_QUERY_ID
+        .save(deps.as_mut().storage, &2)
+        .unwrap();
+    for valoper_address in ["valoper1", "valoper2"] {
+        STATE_MAP
+            .save(
+                deps.as_mut().storage,
+                valoper_address.to_string(),
+                &ValidatorState {
+                    tokens_per_share: Some(Decimal256::one()),
+                    ..get_validator_state(valoper_address)
+                },
+            )
+            .unwrap();
+    }
+    deps.querier.add_query_response(
+        1,
+        to_json_binary(&QueryRegisteredQueryResultResponse {
+            result: InterchainQueryResult {
+                kv_results: vec![
+                    // 950 tokens for 1000 shares: slashed by 5%
+                    build_validator("valoper1", "950", "1000000000000000000000"),
+                    // 1010 tokens for 1000 shares: rewards, no slashing
+                    build_validator("valoper2", "1010", "1000000000000000000000"),
+                ],
+                height: 100,
+                revision: 0,
+            },
+        })
+        .unwrap(),
+    );
+
+    let env = mock_env();
+    let response = crate::contract::sudo(
+        deps.as_mut(),
+        env.clone(),
+        SudoMsg::KVQueryResult { query_id: 1 },
+    )
+    .unwrap();
+    assert_eq!(
+        response.attributes,
+        vec![
+            attr("slash_event_id", "0"),
+            attr("slashed_validator", "valoper1"),
+        ]
+    );
+
+    let slash_events = slash_events_map()
+        .range(deps.as_ref().storage, None, None, Order::Ascending)
+        .collect::<StdResult<Vec<_>>>()
+        .unwrap();
+    assert_eq!(
+        slash_events,
+        vec![(
+            0,
+            SlashEvent {
+                valoper_address: "valoper1".to_string(),
+                remote_height: 100,
+                local_height: env.block.height,
+                timestamp: env.block.time.seconds(),
+                prev_tokens_per_share: Decimal256::one(),
+                tokens_per_share: Decimal256::percent(95),
+                slash_fraction: Decimal::percent(5),
+            }
+        )]
+    );
+    assert_eq!(
+        STATE_MAP
+            .load(deps.as_ref().storage, "valoper1".to_string())
+            .unwrap()
+            .tokens_per_share,
+        Some(Decimal256::percent(95))
+    );
+    assert_eq!(
+        STATE_MAP
+            .load(deps.as_ref().storage, "valoper2".to_string())
+            .unwrap()
+            .tokens_per_share,
+        Some(Decimal256::percent(101))
+    );
+    assert_eq!(
+        STATE_MAP
+            .load(deps.as_ref().storage, "valoper2".to_string())
+            .unwrap()
+            .tokens,
+        Some(Uint128::from(1010u128))
+    );
+}
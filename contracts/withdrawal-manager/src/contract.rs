@@ -97,6 +97,7 @@ pub fn execute(
                 }
             }
         }
+        ExecuteMsg::ReceiveDelivery {} => execute_receive_delivery(deps, info),
         ExecuteMsg::Pause {} => exec_pause(deps, info),
         ExecuteMsg::Unpause {} => exec_unpause(deps, info),
     }
@@ -237,6 +238,37 @@ fn execute_receive_nft_withdraw(
     Ok(response("execute-receive_nft", CONTRACT_NAME, attrs).add_messages(messages))
 }
 
+fn execute_receive_delivery(
+    deps: DepsMut<NeutronQuery>,
+    info: MessageInfo,
+) -> ContractResult<Response<NeutronMsg>> {
+    let config = CONFIG.load(deps.storage)?;
+    let amount = info
+        .funds
+        .iter()
+        .find(|coin| coin.denom == config.base_denom)
+        .map(|coin| coin.amount)
+        .filter(|amount| !amount.is_zero())
+        .ok_or(ContractError::NoDeliveredFunds {
+            denom: config.base_denom.clone(),
+        })?;
+    let attrs = vec![
+        attr("action", "receive_delivery"),
+        attr("amount", amount.to_string()),
+    ];
+    Ok(
+        response("execute-receive_delivery", CONTRACT_NAME, attrs).add_message(CosmosMsg::Wasm(
+            WasmMsg::Execute {
+                contract_addr: config.core_contract.to_string(),
+                msg: to_json_binary(
+                    &drop_staking_base::msg::core::ExecuteMsg::DeliveryReceived { amount },
+                )?,
+                funds: vec![],
+            },
+        )),
+    )
+}
+
 #[cfg_attr(not(feature = "library"), cosmwasm_std::entry_point)]
 pub fn migrate(
     deps: DepsMut<NeutronQuery>,
@@ -30,6 +30,9 @@ pub enum ContractError {
     #[error("Slashing effect is not set")]
     BatchSlashingEffectIsEmpty {},
 
+    #[error("No {denom} funds delivered")]
+    NoDeliveredFunds { denom: String },
+
     #[error("{0}")]
     OwnershipError(#[from] OwnershipError),
 
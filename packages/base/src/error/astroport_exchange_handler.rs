@@ -1,4 +1,4 @@
-use cosmwasm_std::{StdError, Uint128};
+use cosmwasm_std::{CheckedMultiplyFractionError, OverflowError, StdError, Uint128};
 use cw_ownable::OwnershipError;
 use neutron_sdk::NeutronError;
 use thiserror::Error;
@@ -14,6 +14,12 @@ pub enum ContractError {
     #[error("{0}")]
     OwnershipError(#[from] OwnershipError),
 
+    #[error("{0}")]
+    OverflowError(#[from] OverflowError),
+
+    #[error("{0}")]
+    CheckedMultiplyFractionError(#[from] CheckedMultiplyFractionError),
+
     #[error("unauthorized")]
     Unauthorized,
 
@@ -33,6 +39,21 @@ pub enum ContractError {
     #[error("Asset price query is failed {details}")]
     AssetPriceQueryFailed { details: String },
 
+    #[error("Price of {denom} is stale, updated at: {updated_at}")]
+    StalePrice { denom: String, updated_at: u64 },
+
+    #[error("TWAP is disabled")]
+    TwapDisabled,
+
+    #[error("No TWAP observation older than {window} seconds")]
+    TwapNotReady { window: u64 },
+
+    #[error("Pair has no cumulative price for {denom}")]
+    TwapPriceNotFound { denom: String },
+
+    #[error("Minimal return of {denom} swap is zero")]
+    ZeroMinReturn { denom: String },
+
     #[error("Semver parsing error: {0}")]
     SemVer(String),
 }
@@ -136,6 +136,9 @@ pub enum ContractError {
     #[error("Fee must be in range [0.0, 1.0]")]
     InvalidFee {},
 
+    #[error("Batch {batch_id} is not awaiting the pump delivery")]
+    BatchNotAwaitingDelivery { batch_id: u128 },
+
     #[error("Semver parsing error: {0}")]
     SemVer(String),
 }
@@ -0,0 +1,50 @@
+use cosmwasm_std::{CheckedMultiplyFractionError, OverflowError, StdError};
+use cw_ownable::OwnershipError;
+use thiserror::Error;
+
+#[derive(Error, Debug, PartialEq)]
+pub enum ContractError {
+    #[error("{0}")]
+    Std(#[from] StdError),
+
+    #[error("{0}")]
+    OwnershipError(#[from] OwnershipError),
+
+    #[error("{0}")]
+    OverflowError(#[from] OverflowError),
+
+    #[error("{0}")]
+    CheckedMultiplyFractionError(#[from] CheckedMultiplyFractionError),
+
+    #[error("Max spread is zero")]
+    ZeroMaxSpread,
+
+    #[error("Max spread is too big. Max: 0.1")]
+    MaxSpreadTooBig,
+
+    #[error("Route for {denom} already exists")]
+    RouteAlreadyExists { denom: String },
+
+    #[error("No swap route from {from} to {to}")]
+    RouteNotFound { from: String, to: String },
+
+    #[error("Minimal output of {denom} swap is zero")]
+    ZeroMinOutput { denom: String },
+
+    #[error("Nothing to exchange")]
+    NothingToExchange,
+
+    #[error("Asset price query is failed {details}")]
+    AssetPriceQueryFailed { details: String },
+
+    #[error("Semver parsing error: {0}")]
+    SemVer(String),
+}
+
+impl From<semver::Error> for ContractError {
+    fn from(err: semver::Error) -> Self {
+        Self::SemVer(err.to_string())
+    }
+}
+
+pub type ContractResult<T> = Result<T, ContractError>;
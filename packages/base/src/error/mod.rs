@@ -1,6 +1,7 @@
 pub mod astroport_exchange_handler;
 pub mod core;
 pub mod distribution;
+pub mod exchange_handler;
 pub mod price_provider;
 pub mod rewards_manager;
 pub mod splitter;
@@ -10,9 +10,18 @@ pub enum ContractError {
     #[error("{0}")]
     OwnershipError(#[from] OwnershipError),
 
+    #[error("unauthorized")]
+    Unauthorized,
+
     #[error("Denom not found {details}")]
     DenomNotFound { details: String },
 
+    #[error("Price of {denom} is stale, updated at: {updated_at}")]
+    StalePrice { denom: String, updated_at: u64 },
+
+    #[error("Feeder {address} already exists")]
+    FeederAlreadyExists { address: String },
+
     #[error("Semver parsing error: {0}")]
     SemVer(String),
 }
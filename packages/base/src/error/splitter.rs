@@ -22,6 +22,15 @@ pub enum ContractError {
     #[error("Share must not be zero")]
     ZeroShare {},
 
+    #[error("Denom {denom} is duplicated")]
+    DuplicatedDenom { denom: String },
+
+    #[error("Receiver {receiver} of the payload is not configured")]
+    UnknownReceiver { receiver: String },
+
+    #[error("Payload of {receiver} is not a valid JSON object: {details}")]
+    InvalidPayload { receiver: String, details: String },
+
     #[error("Semver parsing error: {0}")]
     SemVer(String),
 }
@@ -3,6 +3,8 @@ use cosmwasm_schema::{cw_serde, QueryResponses};
 use cosmwasm_std::{Decimal, Uint128};
 use cw_ownable::{cw_ownable_execute, cw_ownable_query};
 
+use crate::state::astroport_exchange_handler::TwapObservation;
+
 #[cw_ownable_execute]
 #[cw_serde]
 pub enum ExecuteMsg {
@@ -15,11 +17,19 @@ pub enum ExecuteMsg {
         from_denom: Option<String>,
         min_rewards: Option<Uint128>,
         max_spread: Option<Decimal>,
+        /// Zero disables the TWAP bound
+        twap_window: Option<u64>,
+        /// Zero disables the price staleness check
+        price_max_age: Option<u64>,
     },
     UpdateSwapOperations {
         operations: Option<Vec<SwapOperation>>,
     },
+    /// Skips the swap and only records an observation while the TWAP is not ready
     Exchange {},
+    /// Records the pair cumulative price if the last observation is older than `twap_window`.
+    /// Keepers must call it at least once per window, otherwise exchanges keep waiting for the TWAP
+    ObserveTwap {},
 }
 
 #[cw_ownable_query]
@@ -28,6 +38,8 @@ pub enum ExecuteMsg {
 pub enum QueryMsg {
     #[returns(ConfigResponse)]
     Config {},
+    #[returns(Vec<TwapObservation>)]
+    TwapObservations {},
 }
 
 #[cw_serde]
@@ -40,6 +52,8 @@ pub struct ConfigResponse {
     pub from_denom: String,
     pub min_rewards: Uint128,
     pub swap_operations: Option<Vec<SwapOperation>>,
+    pub twap_window: Option<u64>,
+    pub price_max_age: Option<u64>,
 }
 
 #[cw_serde]
@@ -53,6 +67,8 @@ pub struct InstantiateMsg {
     pub from_denom: String,
     pub min_rewards: Uint128,
     pub max_spread: Decimal,
+    pub twap_window: Option<u64>,
+    pub price_max_age: Option<u64>,
 }
 
 #[cw_serde]
@@ -1,11 +1,15 @@
 use crate::{
     error::core::ContractResult,
-    msg::staker::ResponseHookMsg as StakerResponseHookMsg,
-    state::core::{Config, ConfigOptional},
+    msg::{
+        pump::ResponseHookMsg as PumpResponseHookMsg,
+        staker::ResponseHookMsg as StakerResponseHookMsg,
+    },
+    state::core::{Config, ConfigOptional, StakingPath},
 };
 use cosmwasm_schema::{cw_serde, QueryResponses};
 use cosmwasm_std::{Deps, Uint128, Uint64};
 use cw_ownable::cw_ownable_execute;
+use drop_helpers::ica::IcaRecoveredHookMsg;
 #[allow(unused_imports)]
 use drop_helpers::pause::PauseInfoResponse;
 use drop_macros::{pausable, pausable_query};
@@ -20,6 +24,7 @@ pub struct InstantiateMsg {
     pub withdrawal_voucher_contract: String,
     pub withdrawal_manager_contract: String,
     pub validators_set_contract: String,
+    pub validators_stats_contract: Option<String>,
     pub base_denom: String,
     pub remote_denom: String,
     pub lsm_min_bond_amount: Uint128,
@@ -36,6 +41,8 @@ pub struct InstantiateMsg {
     pub emergency_address: Option<String>,
     pub min_stake_amount: Uint128,
     pub icq_update_delay: u64, // blocks
+    pub rebalance_min_amount: Option<Uint128>,
+    pub staking_path: Option<StakingPath>,
 }
 
 impl InstantiateMsg {
@@ -62,6 +69,10 @@ impl InstantiateMsg {
             lsm_redeem_maximum_interval: self.lsm_redeem_max_interval,
             lsm_min_bond_amount: self.lsm_min_bond_amount,
             validators_set_contract: deps.api.addr_validate(&self.validators_set_contract)?,
+            validators_stats_contract: self
+                .validators_stats_contract
+                .map(|address| deps.api.addr_validate(&address))
+                .transpose()?,
             bond_limit: match self.bond_limit {
                 None => None,
                 Some(limit) if limit.is_zero() => None,
@@ -71,6 +82,13 @@ impl InstantiateMsg {
             emergency_address: self.emergency_address,
             min_stake_amount: self.min_stake_amount,
             icq_update_delay: self.icq_update_delay,
+            rebalance_min_amount: match self.rebalance_min_amount {
+                None => None,
+                Some(amount) if amount.is_zero() => None,
+                Some(amount) => Some(amount),
+            },
+            staking_path: self.staking_path.unwrap_or_default(),
+            pump_contract: None,
         })
     }
 }
@@ -146,7 +164,17 @@ pub enum ExecuteMsg {
     Tick {},
     PuppeteerHook(Box<PuppeteerResponseHookMsg>),
     StakerHook(Box<StakerResponseHookMsg>),
+    PumpHook(Box<PumpResponseHookMsg>),
+    /// Sent by the withdrawal manager once unbonded funds delivered by the pump arrived
+    DeliveryReceived {
+        amount: Uint128,
+    },
+    IcaRecoveredHook(IcaRecoveredHookMsg),
     ResetBondedAmount {},
+    /// Marks batches stuck awaiting the pump delivery as withdrawn
+    MarkBatchesWithdrawn {
+        batch_ids: Vec<u128>,
+    },
     ProcessEmergencyBatch {
         batch_id: u128,
         unbonded_amount: Uint128,
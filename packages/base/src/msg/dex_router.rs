@@ -0,0 +1,56 @@
+use cosmwasm_schema::{cw_serde, QueryResponses};
+use cosmwasm_std::{Coin, Uint128};
+
+use crate::state::dex_router::Pool;
+
+/// Osmosis-style swap router interface
+#[cw_serde]
+pub struct SwapAmountInRoute {
+    pub pool_id: u64,
+    pub token_out_denom: String,
+}
+
+#[cw_serde]
+pub struct InstantiateMsg {}
+
+#[cw_serde]
+pub enum ExecuteMsg {
+    /// Creates a constant product pool from the two attached coins
+    CreatePool {},
+    SetRoute {
+        input_denom: String,
+        output_denom: String,
+        pool_route: Vec<SwapAmountInRoute>,
+    },
+    Swap {
+        output_denom: String,
+        min_output_amount: Uint128,
+        route: Option<Vec<SwapAmountInRoute>>,
+        to: Option<String>,
+    },
+}
+
+#[cw_serde]
+#[derive(QueryResponses)]
+pub enum QueryMsg {
+    #[returns(GetRouteResponse)]
+    GetRoute {
+        input_denom: String,
+        output_denom: String,
+    },
+    #[returns(Uint128)]
+    SimulateSwap {
+        input_coin: Coin,
+        route: Vec<SwapAmountInRoute>,
+    },
+    #[returns(Pool)]
+    Pool { pool_id: u64 },
+}
+
+#[cw_serde]
+pub struct GetRouteResponse {
+    pub pool_route: Vec<SwapAmountInRoute>,
+}
+
+#[cw_serde]
+pub struct MigrateMsg {}
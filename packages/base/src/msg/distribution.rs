@@ -1,17 +1,21 @@
 use cosmwasm_schema::{cw_serde, QueryResponses};
-use cosmwasm_std::Uint128;
+use cosmwasm_std::{Decimal, Uint128};
 
 #[cw_serde]
 pub struct Delegations {
     pub total: Uint128,
     pub total_weight: u64,
     pub delegations: Vec<Delegation>,
+    /// Maximum share of the resulting total stake a single validator can hold
+    pub max_stake_share: Option<Decimal>,
 }
 #[cw_serde]
 pub struct Delegation {
     pub valoper_address: String,
     pub stake: Uint128,
     pub weight: u64,
+    /// Maximum stake the validator can hold
+    pub cap: Option<Uint128>,
 }
 
 #[cw_serde]
@@ -36,6 +40,8 @@ pub enum QueryMsg {
         withdraw: Uint128,
         delegations: Delegations,
     },
+    #[returns(Vec<(String, Uint128)>)]
+    CalcIdeal { delegations: Delegations },
 }
 
 #[cw_serde]
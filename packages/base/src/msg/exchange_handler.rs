@@ -0,0 +1,36 @@
+use cosmwasm_schema::{cw_serde, QueryResponses};
+use cosmwasm_std::Decimal;
+use cw_ownable::{cw_ownable_execute, cw_ownable_query};
+
+use crate::state::exchange_handler::{Config, ConfigOptional, DexRoute};
+
+#[cw_ownable_execute]
+#[cw_serde]
+pub enum ExecuteMsg {
+    UpdateConfig { new_config: ConfigOptional },
+    AddRoute { route: DexRoute },
+    RemoveRoute { denom: String },
+    Exchange {},
+}
+
+#[cw_ownable_query]
+#[cw_serde]
+#[derive(QueryResponses)]
+pub enum QueryMsg {
+    #[returns(Config)]
+    Config {},
+    #[returns(Vec<DexRoute>)]
+    Routes {},
+}
+
+#[cw_serde]
+pub struct InstantiateMsg {
+    pub owner: String,
+    pub core_contract: String,
+    pub price_provider_contract: String,
+    pub to_denom: String,
+    pub max_spread: Decimal,
+}
+
+#[cw_serde]
+pub struct MigrateMsg {}
@@ -1,6 +1,8 @@
 pub mod astroport_exchange_handler;
 pub mod core;
+pub mod dex_router;
 pub mod distribution;
+pub mod exchange_handler;
 pub mod hook_tester;
 pub mod price_provider;
 pub mod proposal_votes;
@@ -1,25 +1,76 @@
 use cosmwasm_schema::{cw_serde, QueryResponses};
-use cosmwasm_std::Decimal;
+use cosmwasm_std::{Addr, Decimal};
 use cw_ownable::{cw_ownable_execute, cw_ownable_query};
 
+use crate::state::price_provider::Config;
+
 #[cw_serde]
 pub struct InstantiateMsg {
     pub owner: Option<String>,
+    /// Defaults to the owner
+    #[serde(default)]
+    pub feeders: Vec<String>,
+    #[serde(default)]
+    pub max_age: Option<u64>,
 }
 
 #[cw_ownable_execute]
 #[cw_serde]
 pub enum ExecuteMsg {
-    RemoveDenom { denom: String },
-    SetPrice { denom: String, price: Decimal },
+    RemoveDenom {
+        denom: String,
+    },
+    /// `price` is the amount of the base asset per unit of `denom`
+    SetPrice {
+        denom: String,
+        price: Decimal,
+    },
+    AddFeeder {
+        address: String,
+    },
+    RemoveFeeder {
+        address: String,
+    },
+    UpdateConfig {
+        new_config: Config,
+    },
 }
 
 #[cw_ownable_query]
 #[cw_serde]
 #[derive(QueryResponses)]
 pub enum QueryMsg {
+    /// Median of the fresh feeder prices, errors if there is none. Prices are quoted as
+    /// the amount of the base asset per unit of `denom`, so swapping `x` of `denom`
+    /// returns `x * price`. The Astroport handler pair path without TWAP is the exception:
+    /// it passes the price through as the belief price, i.e. `denom` offered per ask unit
     #[returns(Decimal)]
     Price { denom: String },
+    #[returns(PriceInfoResponse)]
+    PriceInfo { denom: String },
+    /// Includes stale prices, skips denoms without any
+    #[returns(Vec<PriceInfoResponse>)]
+    Prices { denoms: Vec<String> },
+    #[returns(Vec<Addr>)]
+    Feeders {},
+    #[returns(Config)]
+    Config {},
+}
+
+#[cw_serde]
+pub enum PriceSource {
+    Feeder { address: Addr },
+    Median { feeders: Vec<Addr> },
+}
+
+#[cw_serde]
+pub struct PriceInfoResponse {
+    pub denom: String,
+    pub price: Decimal,
+    /// Update time of the oldest price aggregated
+    pub updated_at: u64,
+    pub source: PriceSource,
+    pub stale: bool,
 }
 
 #[cw_serde]
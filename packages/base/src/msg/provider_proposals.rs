@@ -1,6 +1,7 @@
 use crate::state::provider_proposals::ConfigOptional;
 use cosmwasm_schema::{cw_serde, QueryResponses};
 use cosmwasm_std::Decimal;
+use drop_puppeteer_base::msg::{ResponseHookMsg as PuppeteerResponseHookMsg, WeightedVoteOption};
 use neutron_sdk::interchain_queries::v045::types::ProposalVote;
 
 #[cw_serde]
@@ -17,8 +18,18 @@ pub struct InstantiateMsg {
 
 #[cw_serde]
 pub enum ExecuteMsg {
-    UpdateConfig { new_config: ConfigOptional },
-    UpdateProposalVotes { votes: Vec<ProposalVote> },
+    UpdateConfig {
+        new_config: ConfigOptional,
+    },
+    UpdateProposalVotes {
+        votes: Vec<ProposalVote>,
+    },
+    /// Casts the protocol vote, allowed for the operator of the `Operator` voting policy only
+    CastVote {
+        proposal_id: u64,
+        options: Vec<WeightedVoteOption>,
+    },
+    PuppeteerHook(Box<PuppeteerResponseHookMsg>),
 }
 
 #[cw_serde]
@@ -32,6 +43,8 @@ pub enum QueryMsg {
     GetProposals {},
     #[returns(crate::state::provider_proposals::Metrics)]
     Metrics {},
+    #[returns(Option<crate::state::provider_proposals::ProtocolVote>)]
+    ProtocolVote { proposal_id: u64 },
 }
 
 #[cw_serde]
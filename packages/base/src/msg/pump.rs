@@ -1,6 +1,7 @@
-use crate::state::pump::PumpTimeout;
+use crate::state::pump::{PumpTimeout, Route};
 use cosmwasm_schema::{cw_serde, QueryResponses};
 use cosmwasm_std::Coin;
+use neutron_sdk::sudo::msg::RequestPacket;
 
 #[cw_ownable::cw_ownable_query]
 #[cw_serde]
@@ -10,6 +11,8 @@ pub enum QueryMsg {
     Config {},
     #[returns(drop_helpers::ica::IcaState)]
     Ica {},
+    #[returns(Vec<Route>)]
+    Routes {},
 }
 
 #[cw_serde]
@@ -31,6 +34,12 @@ pub struct UpdateConfigMsg {
     pub refundee: Option<String>,
     pub timeout: Option<PumpTimeout>,
     pub local_denom: Option<String>,
+    pub ica_recovery: Option<drop_helpers::ica::IcaRecoveryConfig>,
+    pub dest_memo: Option<String>,
+    /// replaces the whole routing table
+    pub routes: Option<Vec<Route>>,
+    pub reply_to: Option<String>,
+    pub max_memo_length: Option<u64>,
 }
 
 #[cw_ownable::cw_ownable_execute]
@@ -56,3 +65,34 @@ pub struct InstantiateMsg {
 
 #[cw_serde]
 pub struct MigrateMsg {}
+
+#[cw_serde]
+pub enum ResponseHookMsg {
+    Success(ResponseHookSuccessMsg),
+    Error(ResponseHookErrorMsg),
+}
+
+/// The ICA executed the transfer of the coin to the receiver. The funds are still in flight
+/// and may be refunded to the ICA if the transfer packet times out
+#[cw_serde]
+pub struct ResponseHookSuccessMsg {
+    pub request_id: u64,
+    pub request: RequestPacket,
+    pub coin: Coin,
+    pub receiver: String,
+    pub local_height: u64,
+}
+
+#[cw_serde]
+pub struct ResponseHookErrorMsg {
+    pub request_id: u64,
+    pub request: RequestPacket,
+    pub coin: Coin,
+    pub receiver: String,
+    pub details: String,
+}
+
+#[cw_serde]
+pub enum ReceiverExecuteMsg {
+    PumpHook(ResponseHookMsg),
+}
@@ -4,10 +4,13 @@ use cw_ownable::{cw_ownable_execute, cw_ownable_query};
 use drop_helpers::version::version_to_u32;
 use prost::Message;
 
-use crate::state::puppeteer::ConfigOptional;
+use crate::state::puppeteer::{ConfigOptional, QueryPeriods};
 use cosmos_sdk_proto::cosmos::base::v1beta1::Coin as CosmosCoin;
 use drop_puppeteer_base::{
-    msg::{ExecuteMsg as BaseExecuteMsg, IBCTransferReason, TransferReadyBatchesMsg},
+    msg::{
+        ExecuteMsg as BaseExecuteMsg, IBCTransferReason, TransferReadyBatchesMsg, VoteOption,
+        WeightedVoteOption,
+    },
     r#trait::PuppeteerReconstruct,
     state::{Delegations, RedeemShareItem},
 };
@@ -50,6 +53,10 @@ pub enum ExecuteMsg {
         delegate_grantee: String,
         rewards_withdraw_address: String,
     },
+    Delegate {
+        items: Vec<(String, Uint128)>,
+        reply_to: String,
+    },
     Undelegate {
         items: Vec<(String, Uint128)>,
         batch_id: u128,
@@ -72,10 +79,14 @@ pub enum ExecuteMsg {
     },
     IBCTransfer {
         reason: IBCTransferReason,
+        /// e.g. packet-forward-middleware or IBC hooks instructions
+        memo: Option<String>,
         reply_to: String,
     },
     Transfer {
         items: Vec<(String, cosmwasm_std::Coin)>,
+        /// memo of the interchain tx
+        memo: Option<String>,
         reply_to: String,
     },
     ClaimRewardsAndOptionalyTransfer {
@@ -83,9 +94,31 @@ pub enum ExecuteMsg {
         transfer: Option<TransferReadyBatchesMsg>,
         reply_to: String,
     },
+    Vote {
+        proposal_id: u64,
+        option: VoteOption,
+        reply_to: String,
+    },
+    /// Weights of the options must sum up to 1
+    WeightedVote {
+        proposal_id: u64,
+        options: Vec<WeightedVoteOption>,
+        reply_to: String,
+    },
     UpdateConfig {
         new_config: ConfigOptional,
     },
+    UpdateQueryPeriods {
+        periods: QueryPeriods,
+    },
+    RemoveQueries {
+        query_ids: Vec<u64>,
+    },
+    /// Removes queries not tracked by the contract anymore
+    /// and unbonding delegations queries of validators not in the list
+    ResyncQueries {
+        validators: Vec<String>,
+    },
 }
 
 impl ExecuteMsg {
@@ -112,6 +145,23 @@ pub struct DelegationsResponse {
     pub timestamp: Timestamp,
 }
 
+#[cw_serde]
+pub struct PendingDelegationsSnapshot {
+    pub remote_height: u64,
+    pub collected_chunks: Vec<u16>,
+    pub missing_chunks: Vec<u16>,
+}
+
+#[cw_serde]
+pub struct DelegationsStatusResponse {
+    /// (chunk_id, remote height of its last result)
+    pub chunk_heights: Vec<(u16, u64)>,
+    pub last_complete_remote_height: Option<u64>,
+    /// seconds passed since the last complete snapshot was collected
+    pub last_complete_age: Option<u64>,
+    pub pending: Vec<PendingDelegationsSnapshot>,
+}
+
 #[cw_serde]
 pub struct BalancesResponse {
     pub balances: Balances,
@@ -132,6 +182,18 @@ pub enum QueryExtMsg {
     NonNativeRewardsBalances {},
     #[returns(Vec<drop_puppeteer_base::state::UnbondingDelegation>)]
     UnbondingDelegations {},
+    #[returns(Vec<RedelegationEntries>)]
+    Redelegations {},
+    #[returns(DelegationsStatusResponse)]
+    DelegationsStatus {},
+}
+
+#[cw_serde]
+pub struct RedelegationEntries {
+    pub validator_from: String,
+    pub validator_to: String,
+    /// Completion times (in seconds) of the entries which are not matured yet
+    pub completion_times: Vec<u64>,
 }
 
 #[cw_serde]
@@ -1,4 +1,5 @@
 use cosmwasm_schema::{cw_serde, QueryResponses};
+use cosmwasm_std::Uint128;
 use cw_ownable::{cw_ownable_execute, cw_ownable_query};
 #[allow(unused_imports)]
 use drop_helpers::pause::PauseInfoResponse;
@@ -10,9 +11,16 @@ use crate::state::rewards_manager::HandlerConfig;
 #[pausable]
 #[cw_serde]
 pub enum ExecuteMsg {
-    AddHandler { config: HandlerConfig },
-    RemoveHandler { denom: String },
-    ExchangeRewards { denoms: Vec<String> },
+    AddHandler {
+        config: HandlerConfig,
+    },
+    RemoveHandler {
+        denom: String,
+    },
+    /// Exchanges rewards of the given denoms, or of all registered handlers if omitted
+    ExchangeRewards {
+        denoms: Option<Vec<String>>,
+    },
 }
 
 #[cw_ownable_query]
@@ -22,6 +30,18 @@ pub enum ExecuteMsg {
 pub enum QueryMsg {
     #[returns(Vec<HandlerConfig>)]
     Handlers {},
+    #[returns(Vec<PendingRewardsResponseItem>)]
+    PendingRewards {},
+}
+
+#[cw_serde]
+pub struct PendingRewardsResponseItem {
+    pub denom: String,
+    pub balance: Uint128,
+    pub min_rewards: Uint128,
+    pub last_exchange: Option<u64>,
+    pub next_exchange: u64,
+    pub exchangeable: bool,
 }
 
 #[cw_serde]
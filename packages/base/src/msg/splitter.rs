@@ -1,5 +1,6 @@
 use crate::state::splitter::Config;
 use cosmwasm_schema::{cw_serde, QueryResponses};
+use cosmwasm_std::Uint128;
 
 #[cw_serde]
 pub struct InstantiateMsg {
@@ -19,6 +20,17 @@ pub enum ExecuteMsg {
 pub enum QueryMsg {
     #[returns(Config)]
     Config {},
+    /// Amounts the next distribution would send
+    #[returns(Vec<DenomDistribution>)]
+    Preview {},
+}
+
+#[cw_serde]
+pub struct DenomDistribution {
+    pub denom: String,
+    pub amounts: Vec<(String, Uint128)>,
+    /// Rounding remainder sent to the dust receiver or carried over
+    pub dust: Uint128,
 }
 
 #[cw_serde]
@@ -1,4 +1,4 @@
-use crate::state::staker::{ConfigOptional, Transaction};
+use crate::state::staker::{ConfigOptional, IcaBalance, Transaction};
 use cosmwasm_schema::{cw_serde, QueryResponses};
 use cosmwasm_std::Uint128;
 use neutron_sdk::sudo::msg::RequestPacket;
@@ -17,6 +17,16 @@ pub enum QueryMsg {
     Ica {},
     #[returns(crate::state::staker::TxState)]
     TxState {},
+    #[returns(ReconciliationResponse)]
+    Reconciliation {},
+}
+
+#[cw_serde]
+pub struct ReconciliationResponse {
+    pub non_staked_balance: Uint128,
+    pub ica_balance: Option<IcaBalance>,
+    /// ica_balance is fresher than the last transaction and can be reconciled against
+    pub reconcilable: bool,
 }
 
 #[cw_serde]
@@ -33,9 +43,24 @@ pub struct OpenAckVersion {
 #[cw_serde]
 pub enum ExecuteMsg {
     RegisterICA {},
-    Stake { items: Vec<(String, Uint128)> },
+    Stake {
+        items: Vec<(String, Uint128)>,
+    },
     IBCTransfer {},
-    UpdateConfig { new_config: Box<ConfigOptional> },
+    /// Transfers or stakes pending funds when auto staking is enabled, permissionless.
+    /// The caller attaches the IBC fee in untrn. While a tick's tx is in flight the core
+    /// skips staking through the staker.
+    Tick {},
+    /// Registers (or updates the period of) the ICQ on the ICA balance
+    RegisterBalanceQuery {
+        update_period: u64,
+        sdk_version: String,
+    },
+    /// Sets NON_STAKED_BALANCE to the ICA balance reported by the ICQ
+    Reconcile {},
+    UpdateConfig {
+        new_config: Box<ConfigOptional>,
+    },
 }
 
 #[cw_serde]
@@ -1,5 +1,5 @@
 use cosmwasm_schema::{cw_serde, QueryResponses};
-use cosmwasm_std::Uint128;
+use cosmwasm_std::{Decimal, Uint128};
 use cw_ownable::{cw_ownable_execute, cw_ownable_query};
 use optfield::optfield;
 
@@ -15,10 +15,15 @@ pub enum ExecuteMsg {
 pub enum QueryMsg {
     #[returns(Config)]
     Config {},
+    /// The part of the deposit exceeding the caps of every validator is left out
     #[returns(Vec<(String, Uint128)>)]
     CalcDeposit { deposit: Uint128 },
     #[returns(Vec<(String, Uint128)>)]
     CalcWithdraw { withdraw: Uint128 },
+    /// Returns (validator_from, validator_to, amount) redelegations which bring
+    /// current delegations closer to the ideal ones
+    #[returns(Vec<(String, String, Uint128)>)]
+    CalcRebalance { max_moves: u32, min_amount: Uint128 },
 }
 
 #[optfield(pub ConfigOptional, attrs)]
@@ -28,6 +33,12 @@ pub struct Config {
     pub validator_set_address: String,
     pub distribution_address: String,
     pub denom: String,
+    /// Required by the voting power cap
+    pub validators_stats_address: Option<String>,
+    /// Maximum share of the total stake a single validator can hold, zero disables it
+    pub max_stake_share: Option<Decimal>,
+    /// Maximum share of its own voting power a validator can get, zero disables it
+    pub max_voting_power_share: Option<Decimal>,
 }
 
 #[cw_serde]
@@ -37,6 +48,9 @@ pub struct InstantiateMsg {
     pub validator_set_address: String,
     pub distribution_address: String,
     pub denom: String,
+    pub validators_stats_address: Option<String>,
+    pub max_stake_share: Option<Decimal>,
+    pub max_voting_power_share: Option<Decimal>,
 }
 
 #[cw_serde]
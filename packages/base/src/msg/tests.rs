@@ -194,3 +194,113 @@ fn test_reconstruct_balance_and_delegations_with_delegations() {
         }
     }
 }
+
+fn balances_and_delegations_fixture(sdk_version: &str) -> Vec<StorageValue> {
+    let key = create_account_denom_balance_key(
+        decode_and_convert("cosmos1hdga6p84cpc6gulk9ruxy5w0vpfx9dv83ku59r").unwrap(),
+        "uatom",
+    )
+    .unwrap();
+    let (balance, denom) = if sdk_version.starts_with("0.45.") {
+        let coin = cosmos_sdk_proto::cosmos::base::v1beta1::Coin {
+            denom: "uatom".to_string(),
+            amount: "1000".to_string(),
+        };
+        (
+            Binary::from(coin.encode_to_vec()),
+            to_json_binary(&"uatom".to_string()).unwrap(),
+        )
+    } else {
+        let params = cosmos_sdk_proto::cosmos::staking::v1beta1::Params {
+            bond_denom: "uatom".to_string(),
+            max_validators: 180,
+            ..Default::default()
+        };
+        (
+            Binary::from("1000".as_bytes()),
+            Binary::from(params.encode_to_vec()),
+        )
+    };
+    let delegation = cosmos_sdk_proto::cosmos::staking::v1beta1::Delegation {
+        delegator_address: "delegator".to_string(),
+        validator_address: "validator".to_string(),
+        shares: "2000000000000000000000".to_string(),
+    };
+    let validator = cosmos_sdk_proto::cosmos::staking::v1beta1::Validator {
+        operator_address: "validator".to_string(),
+        consensus_pubkey: None,
+        jailed: false,
+        status: 3,
+        tokens: "1000".to_string(),
+        delegator_shares: "2000000000000000000000".to_string(),
+        description: None,
+        unbonding_height: 0,
+        unbonding_time: None,
+        commission: None,
+        min_self_delegation: "1".to_string(),
+    };
+    vec![
+        StorageValue {
+            storage_prefix: "bank".to_string(),
+            key: Binary::from(key),
+            value: balance,
+        },
+        StorageValue {
+            storage_prefix: "staking".to_string(),
+            key: Binary::from("params".as_bytes()),
+            value: denom,
+        },
+        StorageValue {
+            storage_prefix: "staking".to_string(),
+            key: Binary::from("delegation".as_bytes()),
+            value: Binary::from(delegation.encode_to_vec()),
+        },
+        StorageValue {
+            storage_prefix: "staking".to_string(),
+            key: Binary::from("validator".as_bytes()),
+            value: Binary::from(validator.encode_to_vec()),
+        },
+    ]
+}
+
+#[test]
+fn test_reconstruct_balance_and_delegations_sdk_versions() {
+    for sdk_version in ["0.45.16", "0.47.10", "0.50.9"] {
+        let result: BalancesAndDelegations = PuppeteerReconstruct::reconstruct(
+            &balances_and_delegations_fixture(sdk_version),
+            sdk_version,
+        )
+        .unwrap();
+        assert_eq!(
+            result.balances.coins,
+            vec![Coin::new(1000, "uatom")],
+            "sdk {sdk_version}"
+        );
+        assert_eq!(
+            result.delegations.delegations,
+            vec![DropDelegation {
+                delegator: Addr::unchecked("delegator"),
+                validator: "validator".to_string(),
+                amount: Coin::new(1000, "uatom"),
+                share_ratio: Decimal256::from_ratio(1u128, 2u128),
+            }],
+            "sdk {sdk_version}"
+        );
+    }
+}
+
+#[test]
+fn test_reconstruct_multi_balances_sdk_0_50() {
+    let key = create_account_denom_balance_key(
+        decode_and_convert("cosmos1hdga6p84cpc6gulk9ruxy5w0vpfx9dv83ku59r").unwrap(),
+        "uatom",
+    )
+    .unwrap();
+    let storage_values = vec![StorageValue {
+        storage_prefix: "bank".to_string(),
+        key: Binary::from(key),
+        value: Binary::from("1000".as_bytes()),
+    }];
+    let result = MultiBalances::reconstruct(&storage_values, "0.50.9").unwrap();
+    assert_eq!(result.coins, vec![Coin::new(1000, "uatom")]);
+}
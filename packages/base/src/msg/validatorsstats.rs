@@ -8,11 +8,17 @@ pub struct InstantiateMsg {
     pub info_update_period: u64,
     pub avg_block_time: u64,
     pub owner: String,
+    pub validators_set_address: Option<String>,
 }
 
 #[cw_serde]
 pub enum ExecuteMsg {
-    RegisterStatsQueries { validators: Vec<String> },
+    RegisterStatsQueries {
+        validators: Vec<String>,
+    },
+    /// Pushes the next page of validator states to the validators set,
+    /// wrapping around to the first validator after the last page
+    Push {},
 }
 
 #[cw_serde]
@@ -24,6 +30,12 @@ pub enum QueryMsg {
     KVQueryIds {},
     #[returns(Vec<crate::state::validatorsstats::ValidatorState>)]
     State {},
+    #[returns(Vec<(u64, crate::state::validatorsstats::SlashEvent)>)]
+    SlashEvents {
+        valoper: Option<String>,
+        start_after: Option<u64>,
+        limit: Option<u32>,
+    },
 }
 
 #[cw_serde]
@@ -32,6 +32,10 @@ pub enum ExecuteMsg {
         base_denom: Option<String>,
     },
     ReceiveNft(Cw721ReceiveMsg),
+    /// Called by IBC hooks with the unbonded funds delivered by the pump, so the core
+    /// withdraws batches only once the funds arrived. The pump transfer memo has to be
+    /// `{"wasm":{"contract":"<withdrawal manager>","msg":{"receive_delivery":{}}}}`
+    ReceiveDelivery {},
 }
 
 #[cw_serde]
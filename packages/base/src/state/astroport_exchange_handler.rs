@@ -13,7 +13,22 @@ pub struct Config {
     pub from_denom: String,
     pub min_rewards: Uint128,
     pub max_spread: Decimal,
+    /// Minimal period (in seconds) the pair TWAP bounding the swap is taken over
+    #[serde(default)]
+    pub twap_window: Option<u64>,
+    /// Maximal age (in seconds) of the price provider price allowed to swap
+    #[serde(default)]
+    pub price_max_age: Option<u64>,
 }
 pub const CONFIG: Item<Config> = Item::new("config");
 
 pub const SWAP_OPERATIONS: Item<Vec<SwapOperation>> = Item::new("swap_operations");
+
+#[cw_serde]
+pub struct TwapObservation {
+    pub timestamp: u64,
+    pub cumulative_price: Uint128,
+}
+
+/// At most two pair cumulative price observations at least `twap_window` apart
+pub const TWAP_OBSERVATIONS: Item<Vec<TwapObservation>> = Item::new("twap_observations");
@@ -14,6 +14,7 @@ pub struct ConfigOptional {
     pub withdrawal_voucher_contract: Option<String>,
     pub withdrawal_manager_contract: Option<String>,
     pub validators_set_contract: Option<String>,
+    pub validators_stats_contract: Option<String>,
     pub base_denom: Option<String>,
     pub remote_denom: Option<String>,
     pub idle_min_interval: Option<u64>,
@@ -29,6 +30,20 @@ pub struct ConfigOptional {
     pub rewards_receiver: Option<String>,
     pub emergency_address: Option<String>,
     pub min_stake_amount: Option<Uint128>,
+    pub rebalance_min_amount: Option<Uint128>,
+    pub staking_path: Option<StakingPath>,
+    pub pump_contract: Option<String>,
+}
+
+/// Which ICA delegates the bonded funds
+#[cw_serde]
+#[derive(Default, Copy)]
+pub enum StakingPath {
+    /// funds are sent to the staker contract which delegates them via authz
+    #[default]
+    Staker,
+    /// funds are transferred to the puppeteer ICA and delegated by the puppeteer itself
+    Puppeteer,
 }
 
 #[cw_serde]
@@ -40,6 +55,7 @@ pub struct Config {
     pub withdrawal_voucher_contract: Addr,
     pub withdrawal_manager_contract: Addr,
     pub validators_set_contract: Addr,
+    pub validators_stats_contract: Option<Addr>,
     pub base_denom: String,
     pub remote_denom: String,
     pub idle_min_interval: u64,        //seconds
@@ -54,7 +70,13 @@ pub struct Config {
     pub bond_limit: Option<Uint128>,
     pub emergency_address: Option<String>,
     pub min_stake_amount: Uint128,
-    pub icq_update_delay: u64, // blocks
+    pub icq_update_delay: u64,                 // blocks
+    pub rebalance_min_amount: Option<Uint128>, // rebalancing is disabled if not set
+    #[serde(default)]
+    pub staking_path: StakingPath,
+    /// when set, batches are withdrawn only after the pump confirms the delivery
+    #[serde(default)]
+    pub pump_contract: Option<Addr>,
 }
 
 pub const CONFIG: Item<Config> = Item::new("config");
@@ -140,6 +162,8 @@ pub enum ContractState {
     Claiming,
     Unbonding,
     StakingBond,
+    Rebalancing,
+    StakingTransfer,
 }
 
 const TRANSITIONS: &[Transition<ContractState>] = &[
@@ -199,6 +223,26 @@ const TRANSITIONS: &[Transition<ContractState>] = &[
         from: ContractState::Claiming,
         to: ContractState::Idle,
     },
+    Transition {
+        from: ContractState::Idle,
+        to: ContractState::Rebalancing,
+    },
+    Transition {
+        from: ContractState::Rebalancing,
+        to: ContractState::Idle,
+    },
+    Transition {
+        from: ContractState::Idle,
+        to: ContractState::StakingTransfer,
+    },
+    Transition {
+        from: ContractState::Claiming,
+        to: ContractState::StakingTransfer,
+    },
+    Transition {
+        from: ContractState::StakingTransfer,
+        to: ContractState::Idle,
+    },
 ];
 
 pub const FSM: Fsm<ContractState> = Fsm::new("machine_state", TRANSITIONS);
@@ -212,3 +256,12 @@ pub const BONDED_AMOUNT: Item<Uint128> = Item::new("bonded_amount"); // to be us
 pub const LAST_LSM_REDEEM: Item<u64> = Item::new("last_lsm_redeem");
 pub const EXCHANGE_RATE: Item<(Decimal, u64)> = Item::new("exchange_rate");
 pub const LD_DENOM: Item<String> = Item::new("ld_denom");
+pub const LAST_SLASH_EVENT_ID: Item<u64> = Item::new("last_slash_event_id");
+/// Bonded funds waiting in the core to be transferred to the puppeteer ICA (puppeteer staking path)
+pub const PENDING_STAKE_LOCAL: Item<Uint128> = Item::new("pending_stake_local");
+/// Funds on the puppeteer ICA waiting to be delegated (puppeteer staking path)
+pub const PENDING_STAKE_REMOTE: Item<Uint128> = Item::new("pending_stake_remote");
+/// Withdrawing batches transferred to the pump ICA, waiting for the pump delivery
+pub const PENDING_DELIVERY_BATCHES: Item<Vec<u128>> = Item::new("pending_delivery_batches");
+/// Delivered amount not yet attributed to a batch
+pub const PUMP_DELIVERED_AMOUNT: Item<Uint128> = Item::new("pump_delivered_amount");
@@ -0,0 +1,14 @@
+use cosmwasm_schema::cw_serde;
+use cosmwasm_std::Coin;
+use cw_storage_plus::{Item, Map};
+
+use crate::msg::dex_router::SwapAmountInRoute;
+
+#[cw_serde]
+pub struct Pool {
+    pub assets: [Coin; 2],
+}
+
+pub const POOL_COUNT: Item<u64> = Item::new("pool_count");
+pub const POOLS: Map<u64, Pool> = Map::new("pools");
+pub const ROUTES: Map<(&str, &str), Vec<SwapAmountInRoute>> = Map::new("routes");
@@ -0,0 +1,41 @@
+use astroport::router::SwapOperation;
+use cosmwasm_schema::cw_serde;
+use cosmwasm_std::{Decimal, Uint128};
+use cw_storage_plus::{Item, Map};
+use optfield::optfield;
+
+#[optfield(pub ConfigOptional, attrs)]
+#[cw_serde]
+pub struct Config {
+    pub core_contract: String,
+    pub price_provider_contract: String,
+    /// Denom every reward is exchanged into
+    pub to_denom: String,
+    pub max_spread: Decimal,
+}
+
+pub const CONFIG: Item<Config> = Item::new("config");
+
+#[cw_serde]
+pub enum Dex {
+    AstroportPair {
+        pair_contract: String,
+    },
+    AstroportRouter {
+        router_contract: String,
+        operations: Vec<SwapOperation>,
+    },
+    /// Osmosis-style router which resolves pool routes on its own
+    Router {
+        router_contract: String,
+    },
+}
+
+#[cw_serde]
+pub struct DexRoute {
+    pub denom: String,
+    pub dex: Dex,
+    pub min_rewards: Uint128,
+}
+
+pub const DEX_ROUTES: Map<&str, DexRoute> = Map::new("dex_routes");
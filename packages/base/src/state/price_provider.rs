@@ -1,4 +1,26 @@
-use cosmwasm_std::Decimal;
-use cw_storage_plus::Map;
+use cosmwasm_schema::cw_serde;
+use cosmwasm_std::{Addr, Decimal};
+use cw_storage_plus::{Item, Map};
 
+#[cw_serde]
+#[derive(Default)]
+pub struct Config {
+    /// Age (in seconds) after which a feeder price is ignored
+    pub max_age: Option<u64>,
+}
+
+pub const CONFIG: Item<Config> = Item::new("config");
+
+#[cw_serde]
+pub struct PriceFeed {
+    pub price: Decimal,
+    pub updated_at: u64,
+}
+
+pub const FEEDERS: Item<Vec<Addr>> = Item::new("feeders");
+/// Latest price per denom and feeder
+pub const FEEDS: Map<(&str, &Addr), PriceFeed> = Map::new("feeds");
+
+/// Owner set prices of the previous version, moved into `FEEDS` on migration
 pub const PRICES: Map<&String, Decimal> = Map::new("pairs");
+pub const PRICE_UPDATES: Map<&String, u64> = Map::new("price_updates");
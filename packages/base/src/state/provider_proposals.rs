@@ -2,6 +2,7 @@ use cosmwasm_schema::cw_serde;
 
 use cosmwasm_std::Decimal;
 use cw_storage_plus::{Item, Map};
+use drop_puppeteer_base::msg::WeightedVoteOption;
 use neutron_sdk::interchain_queries::v045::types::{Proposal, ProposalVote};
 use optfield::optfield;
 
@@ -24,6 +25,35 @@ pub struct Config {
     pub init_proposal: u64,
     pub proposals_prefetch: u64,
     pub veto_spam_threshold: Decimal,
+    /// Puppeteer of the protocol ICA used to vote with the delegated stake
+    #[serde(default)]
+    pub puppeteer_address: Option<String>,
+    #[serde(default)]
+    pub voting_policy: VotingPolicy,
+}
+
+#[cw_serde]
+#[derive(Default)]
+pub enum VotingPolicy {
+    /// Protocol stake doesn't take part in the host chain governance
+    #[default]
+    Disabled,
+    /// Abstains on every proposal once it enters the voting period
+    Abstain,
+    /// Votes the way the operator says, e.g. a contract collecting dAsset holders signalling
+    Operator { address: String },
+}
+
+#[cw_serde]
+pub enum ProtocolVoteStatus {
+    Pending,
+    Voted,
+}
+
+#[cw_serde]
+pub struct ProtocolVote {
+    pub options: Vec<WeightedVoteOption>,
+    pub status: ProtocolVoteStatus,
 }
 
 #[cw_serde]
@@ -32,6 +62,8 @@ pub struct Metrics {
 }
 
 pub const PROPOSALS_REPLY_ID: u64 = 1;
+/// Abstain vote replies are identified by this offset plus the proposal id
+pub const ABSTAIN_VOTE_REPLY_ID_OFFSET: u64 = 1 << 32;
 
 pub const QUERY_ID: Item<u64> = Item::new("query_id");
 
@@ -39,3 +71,4 @@ pub const CONFIG: Item<Config> = Item::new("config");
 pub const ACTIVE_PROPOSALS: Item<Vec<u64>> = Item::new("active_proposals");
 pub const PROPOSALS: Map<u64, Proposal> = Map::new("proposals");
 pub const PROPOSALS_VOTES: Map<u64, Vec<ProposalVote>> = Map::new("proposals_votes");
+pub const PROTOCOL_VOTES: Map<u64, ProtocolVote> = Map::new("protocol_votes");
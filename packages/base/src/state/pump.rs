@@ -1,7 +1,7 @@
 use cosmwasm_schema::cw_serde;
-use cosmwasm_std::Addr;
-use cw_storage_plus::Item;
-use drop_helpers::ica::Ica;
+use cosmwasm_std::{Addr, Coin};
+use cw_storage_plus::{Item, Map};
+use drop_helpers::ica::{Ica, IcaRecoveryConfig};
 
 #[cw_serde]
 pub struct PumpTimeout {
@@ -18,8 +18,42 @@ pub struct Config {
     pub refundee: Option<Addr>,
     pub timeout: PumpTimeout,
     pub local_denom: String,
+    pub ica_recovery: Option<IcaRecoveryConfig>,
+    /// memo of the transfers to the destination, e.g. IBC hooks call of the receiving contract
+    #[serde(default)]
+    pub dest_memo: Option<String>,
+    /// per denom destinations, denoms without a route go to dest_address
+    #[serde(default)]
+    pub routes: Vec<Route>,
+    /// contract notified about the outcome of every pushed transfer
+    #[serde(default)]
+    pub reply_to: Option<Addr>,
+    /// max length of memos passed along with transfers
+    #[serde(default = "drop_helpers::ibc_transfer::default_max_memo_length")]
+    pub max_memo_length: u64,
+}
+
+#[cw_serde]
+pub struct Route {
+    pub denom: String,
+    pub channel: String,
+    pub receiver: String,
+    /// e.g. packet forward middleware memo for multi-hop forwarding
+    pub memo: Option<String>,
 }
 
 pub const CONFIG: Item<Config> = Item::new("core");
 pub const ICA: Ica = Ica::new("ica");
 pub const ICA_ID: &str = "drop_PUMP";
+
+#[cw_serde]
+pub struct Delivery {
+    pub coin: Coin,
+    pub receiver: String,
+}
+
+pub const PUSH_REPLY_ID: u64 = 1;
+/// deliveries submitted in the current tx, waiting for their sequence ids in replies
+pub const DELIVERY_QUEUE: Item<Vec<Delivery>> = Item::new("delivery_queue");
+/// deliveries waiting for the ICA ack, by (channel, sequence)
+pub const DELIVERIES: Map<(&str, u64), Delivery> = Map::new("deliveries");
@@ -1,6 +1,7 @@
 use cosmwasm_schema::cw_serde;
 use cosmwasm_std::Addr;
-use cw_storage_plus::Item;
+use cw_storage_plus::{Item, Map};
+use drop_helpers::ica::IcaRecoveryConfig;
 use drop_puppeteer_base::state::{BalancesAndDelegationsState, BaseConfig};
 
 use crate::msg::puppeteer::MultiBalances;
@@ -15,6 +16,10 @@ pub struct ConfigOptional {
     pub transfer_channel_id: Option<String>,
     pub sdk_version: Option<String>,
     pub timeout: Option<u64>,
+    pub ica_recovery: Option<IcaRecoveryConfig>,
+    pub query_deposit_receiver: Option<String>,
+    pub delegations_max_height_skew: Option<u64>,
+    pub max_memo_length: Option<u64>,
 }
 
 #[cw_serde]
@@ -28,6 +33,38 @@ pub struct Config {
     pub sdk_version: String,
     pub timeout: u64, // timeout for interchain transactions in seconds
     pub delegations_queries_chunk_size: u32,
+    pub ica_recovery: Option<IcaRecoveryConfig>,
+    #[serde(default)]
+    pub query_periods: QueryPeriods,
+    /// receives ICQ deposits returned on queries removal, they stay on the contract if not set
+    #[serde(default)]
+    pub query_deposit_receiver: Option<Addr>,
+    /// max remote height difference between chunks of one delegations snapshot
+    #[serde(default)]
+    pub delegations_max_height_skew: u64,
+    /// max length of memos passed along with transfers
+    #[serde(default = "drop_helpers::ibc_transfer::default_max_memo_length")]
+    pub max_memo_length: u64,
+}
+
+/// Per query type ICQ update periods, `update_period` is used for the unset ones
+#[cw_serde]
+#[derive(Default)]
+pub struct QueryPeriods {
+    pub delegations_and_balance: Option<u64>,
+    pub unbonding_delegations: Option<u64>,
+    pub non_native_rewards_balances: Option<u64>,
+}
+
+impl Config {
+    pub fn query_period(&self, query_type: &KVQueryType) -> u64 {
+        match query_type {
+            KVQueryType::DelegationsAndBalance => self.query_periods.delegations_and_balance,
+            KVQueryType::UnbondingDelegations => self.query_periods.unbonding_delegations,
+            KVQueryType::NonNativeRewardsBalances => self.query_periods.non_native_rewards_balances,
+        }
+        .unwrap_or(self.update_period)
+    }
 }
 
 impl BaseConfig for Config {
@@ -38,6 +75,10 @@ impl BaseConfig for Config {
     fn update_period(&self) -> u64 {
         self.update_period
     }
+
+    fn ica_recovery(&self) -> Option<IcaRecoveryConfig> {
+        self.ica_recovery.clone()
+    }
 }
 
 #[cw_serde]
@@ -51,3 +92,7 @@ pub const CONFIG: Item<Config> = Item::new("config");
 
 pub const NON_NATIVE_REWARD_BALANCES: Item<BalancesAndDelegationsState<MultiBalances>> =
     Item::new("non_native_reward_balances");
+
+/// Completion times (in seconds) of the redelegation entries in progress
+/// for every (validator_from, validator_to) pair
+pub const REDELEGATIONS: Map<(String, String), Vec<u64>> = Map::new("redelegations");
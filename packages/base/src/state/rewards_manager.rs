@@ -7,6 +7,11 @@ pub struct HandlerConfig {
     pub address: String,
     pub denom: String,
     pub min_rewards: Uint128,
+    /// Minimal number of seconds between two exchanges of this denom
+    #[serde(default)]
+    pub cooldown: u64,
 }
 
 pub const REWARDS_HANDLERS: Map<String, HandlerConfig> = Map::new("rewards_handlers");
+/// Timestamp (in seconds) of the last exchange per denom
+pub const LAST_EXCHANGE: Map<String, u64> = Map::new("last_exchange");
@@ -1,5 +1,5 @@
 use cosmwasm_schema::cw_serde;
-use cosmwasm_std::Uint128;
+use cosmwasm_std::{Binary, Uint128};
 use cw_storage_plus::Item;
 
 #[cw_serde]
@@ -7,6 +7,18 @@ use cw_storage_plus::Item;
 pub struct Config {
     pub receivers: Vec<(String, Uint128)>,
     pub denom: String,
+    /// Denoms distributed along with `denom`
+    #[serde(default)]
+    pub extra_denoms: Vec<String>,
+    /// Receives the rounding remainder, which is carried over to the next distribution if unset
+    #[serde(default)]
+    pub dust_receiver: Option<String>,
+    /// Balance of a denom below which it is not distributed
+    #[serde(default)]
+    pub min_distribution: Uint128,
+    /// Wasm execute payloads the shares of the receivers are attached to instead of a plain send
+    #[serde(default)]
+    pub receiver_msgs: Vec<(String, Binary)>,
 }
 
 pub const CONFIG: Item<Config> = Item::new("config");
@@ -1,7 +1,7 @@
 use cosmwasm_schema::cw_serde;
 use cosmwasm_std::{Addr, Uint128};
 use cw_storage_plus::Item;
-use drop_helpers::ica::Ica;
+use drop_helpers::ica::{Ica, IcaRecoveryConfig};
 
 #[cw_serde]
 pub struct Config {
@@ -15,6 +15,23 @@ pub struct Config {
     pub puppeteer_ica: Option<String>,
     pub min_ibc_transfer: Uint128,
     pub min_staking_amount: Uint128,
+    pub ica_recovery: Option<IcaRecoveryConfig>,
+    /// memo of the IBC transfers to the ICA
+    #[serde(default)]
+    pub transfer_memo: Option<String>,
+    #[serde(default)]
+    pub auto_stake: Option<AutoStakeConfig>,
+    /// max length of memos passed along with transfers
+    #[serde(default = "drop_helpers::ibc_transfer::default_max_memo_length")]
+    pub max_memo_length: u64,
+}
+
+/// Lets anyone (e.g. Neutron cron) tick the staker to transfer and stake pending funds
+#[cw_serde]
+pub struct AutoStakeConfig {
+    pub strategy_contract: String,
+    /// min seconds between two ticks
+    pub min_tick_interval: u64,
 }
 
 #[cw_serde]
@@ -24,6 +41,10 @@ pub struct ConfigOptional {
     pub puppeteer_ica: Option<String>,
     pub min_ibc_transfer: Option<Uint128>,
     pub min_staking_amount: Option<Uint128>,
+    pub ica_recovery: Option<IcaRecoveryConfig>,
+    pub transfer_memo: Option<String>,
+    pub auto_stake: Option<AutoStakeConfig>,
+    pub max_memo_length: Option<u64>,
 }
 
 #[cw_serde]
@@ -40,6 +61,20 @@ pub enum Transaction {
     Stake { amount: Uint128 },
     IBCTransfer { amount: Uint128 },
 }
+#[cw_serde]
+pub struct BalanceQueryConfig {
+    pub update_period: u64,
+    pub sdk_version: String,
+}
+
+/// ICA balance of remote_denom reported by the balance ICQ
+#[cw_serde]
+pub struct IcaBalance {
+    pub amount: Uint128,
+    pub remote_height: u64,
+    pub local_height: u64,
+}
+
 #[cw_serde]
 #[derive(Default)]
 pub struct TxState {
@@ -54,17 +89,26 @@ pub const ICA: Ica = Ica::new("ica");
 pub const ICA_ID: &str = "drop_STAKER";
 pub const NON_STAKED_BALANCE: Item<Uint128> = Item::new("current_balance");
 pub const TX_STATE: Item<TxState> = Item::new("tx_state");
+pub const LAST_TICK: Item<u64> = Item::new("last_tick");
+pub const BALANCE_QUERY_CONFIG: Item<BalanceQueryConfig> = Item::new("balance_query_config");
+pub const BALANCE_QUERY_ID: Item<u64> = Item::new("balance_query_id");
+pub const ICA_BALANCE: Item<IcaBalance> = Item::new("ica_balance");
+/// remote client height at which the last transaction got its ack. Failed and timed out
+/// transactions don't change the ICA balance, so they don't move it
+pub const LAST_TX_REMOTE_HEIGHT: Item<u64> = Item::new("last_tx_remote_height");
 
 pub use reply_msg::ReplyMsg;
 mod reply_msg {
     const OFFSET: u64 = u16::BITS as u64;
     const SUDO_PAYLOAD: u64 = 1 << OFFSET;
     const IBC_TRANSFER: u64 = 2 << OFFSET;
+    const BALANCE_QUERY: u64 = 3 << OFFSET;
 
     #[cosmwasm_schema::cw_serde]
     pub enum ReplyMsg {
         SudoPayload,
         IbcTransfer,
+        BalanceQuery,
     }
 
     impl ReplyMsg {
@@ -72,6 +116,7 @@ mod reply_msg {
             match self {
                 ReplyMsg::SudoPayload => SUDO_PAYLOAD,
                 ReplyMsg::IbcTransfer => IBC_TRANSFER,
+                ReplyMsg::BalanceQuery => BALANCE_QUERY,
             }
         }
 
@@ -79,6 +124,7 @@ mod reply_msg {
             match reply_id {
                 SUDO_PAYLOAD => Self::SudoPayload,
                 IBC_TRANSFER => Self::IbcTransfer,
+                BALANCE_QUERY => Self::BalanceQuery,
                 _ => unreachable!(),
             }
         }
@@ -92,18 +138,23 @@ mod reply_msg {
         fn enum_variant_from_reply_id() {
             assert_eq!(ReplyMsg::from_reply_id(SUDO_PAYLOAD), ReplyMsg::SudoPayload);
             assert_eq!(ReplyMsg::from_reply_id(IBC_TRANSFER), ReplyMsg::IbcTransfer);
+            assert_eq!(
+                ReplyMsg::from_reply_id(BALANCE_QUERY),
+                ReplyMsg::BalanceQuery
+            );
         }
 
         #[test]
         fn enum_variant_to_reply_id() {
             assert_eq!(ReplyMsg::SudoPayload.to_reply_id(), SUDO_PAYLOAD);
             assert_eq!(ReplyMsg::IbcTransfer.to_reply_id(), IBC_TRANSFER);
+            assert_eq!(ReplyMsg::BalanceQuery.to_reply_id(), BALANCE_QUERY);
         }
 
         #[test]
         #[should_panic]
         fn invalid_reply_id() {
-            ReplyMsg::from_reply_id(IBC_TRANSFER + 1);
+            ReplyMsg::from_reply_id(BALANCE_QUERY + 1);
         }
     }
 }
@@ -1,7 +1,13 @@
-use cosmwasm_std::Addr;
+use cosmwasm_std::{Addr, Decimal};
 use cw_storage_plus::Item;
 
 pub const PUPPETEER_ADDRESS: Item<Addr> = Item::new("puppeteer");
 pub const VALIDATOR_SET_ADDRESS: Item<Addr> = Item::new("validator_set");
 pub const DISTRIBUTION_ADDRESS: Item<Addr> = Item::new("distribution");
 pub const DENOM: Item<String> = Item::new("denom");
+/// Validators stats contract providing the voting power of the validators
+pub const VALIDATORS_STATS_ADDRESS: Item<Addr> = Item::new("validators_stats");
+/// Maximum share of the total stake a single validator can hold
+pub const MAX_STAKE_SHARE: Item<Decimal> = Item::new("max_stake_share");
+/// Maximum share of its own voting power a validator can get from us
+pub const MAX_VOTING_POWER_SHARE: Item<Decimal> = Item::new("max_voting_power_share");
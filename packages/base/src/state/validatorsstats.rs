@@ -1,7 +1,7 @@
 use cosmwasm_schema::cw_serde;
 
-use cosmwasm_std::{Addr, Decimal};
-use cw_storage_plus::{Item, Map};
+use cosmwasm_std::{Addr, Decimal, Decimal256, Uint128};
+use cw_storage_plus::{Index, IndexList, IndexedMap, Item, Map, MultiIndex};
 
 #[cw_serde]
 pub struct Config {
@@ -11,6 +11,7 @@ pub struct Config {
     pub info_update_period: u64,
     pub avg_block_time: u64,
     pub owner: Addr,
+    pub validators_set_address: Option<Addr>,
 }
 
 #[cw_serde]
@@ -31,6 +32,10 @@ pub struct ValidatorState {
     pub tombstone: bool,
     pub prev_jailed_state: bool,
     pub jailed_number: Option<u64>,
+    pub tokens_per_share: Option<Decimal256>,
+    /// Voting power of the validator at the last processed remote height
+    #[serde(default)]
+    pub tokens: Option<Uint128>,
 }
 
 #[cw_serde]
@@ -39,24 +44,62 @@ pub struct State {
 }
 
 #[cw_serde]
-pub struct ValidatorMissedBlocksForPeriod {
-    pub address: String,
+pub struct MissedBlocks {
+    pub remote_height: u64,
     pub missed_blocks: u64,
 }
 
 #[cw_serde]
-pub struct MissedBlocks {
+pub struct SlashEvent {
+    pub valoper_address: String,
     pub remote_height: u64,
+    pub local_height: u64,
     pub timestamp: u64,
-    pub validators: Vec<ValidatorMissedBlocksForPeriod>,
+    pub prev_tokens_per_share: Decimal256,
+    pub tokens_per_share: Decimal256,
+    pub slash_fraction: Decimal,
+}
+
+pub struct SlashEventIndexes<'a> {
+    pub valoper: MultiIndex<'a, String, SlashEvent, u64>,
+}
+
+impl<'a> IndexList<SlashEvent> for SlashEventIndexes<'a> {
+    fn get_indexes(&'_ self) -> Box<dyn Iterator<Item = &'_ dyn Index<SlashEvent>> + '_> {
+        let v: Vec<&dyn Index<SlashEvent>> = vec![&self.valoper];
+        Box::new(v.into_iter())
+    }
+}
+
+pub fn slash_events_map<'a>() -> IndexedMap<'a, u64, SlashEvent, SlashEventIndexes<'a>> {
+    IndexedMap::new(
+        "slash_events",
+        SlashEventIndexes {
+            valoper: MultiIndex::new(
+                |_pk, e| e.valoper_address.clone(),
+                "slash_events",
+                "slash_events__valoper",
+            ),
+        },
+    )
 }
 
 pub const VALIDATOR_PROFILE_REPLY_ID: u64 = 1;
 pub const SIGNING_INFO_REPLY_ID: u64 = 2;
+pub const UPDATE_VALIDATORS_INFO_REPLY_ID: u64 = 3;
+
+// TODO: move window to config
+pub const MISSED_BLOCKS_WINDOW: u64 = 60 * 60 * 24 * 30;
 
 pub const CONFIG: Item<Config> = Item::new("config");
-pub const MISSED_BLOCKS: Item<Vec<MissedBlocks>> = Item::new("missed_blocks");
+/// Missed blocks samples keyed by (valoper address, local timestamp)
+pub const MISSED_BLOCKS: Map<(String, u64), MissedBlocks> = Map::new("validator_missed_blocks");
+/// Legacy storage key of the `Item<Vec<_>>` holding samples for all validators at once
+pub const LEGACY_MISSED_BLOCKS_KEY: &[u8] = b"missed_blocks";
 pub const STATE_MAP: Map<String, ValidatorState> = Map::new("state_map");
 pub const VALIDATOR_PROFILE_QUERY_ID: Item<u64> = Item::new("validator_profile_query_id");
 pub const SIGNING_INFO_QUERY_ID: Item<u64> = Item::new("signin_info_query_id");
+pub const SLASH_EVENT_ID: Item<u64> = Item::new("slash_event_id");
 pub const VALCONS_TO_VALOPER: Map<String, String> = Map::new("valcons_to_valoper");
+/// Last validator pushed to the validators set, the next push continues after it
+pub const PUSH_CURSOR: Item<String> = Item::new("push_cursor");
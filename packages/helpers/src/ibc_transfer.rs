@@ -0,0 +1,41 @@
+use cosmos_sdk_proto::{cosmos::base::v1beta1::Coin, ibc::core::client::v1::Height};
+use cosmwasm_std::{StdError, StdResult};
+
+pub const DEFAULT_MAX_MEMO_LENGTH: u64 = 256;
+
+pub fn default_max_memo_length() -> u64 {
+    DEFAULT_MAX_MEMO_LENGTH
+}
+
+pub fn validate_memo(memo: &str, max_length: u64) -> StdResult<()> {
+    if memo.len() as u64 > max_length {
+        return Err(StdError::generic_err(format!(
+            "Memo is too long: {} > {}",
+            memo.len(),
+            max_length
+        )));
+    }
+    Ok(())
+}
+
+/// ibc.applications.transfer.v1.MsgTransfer including the memo field (ibc-go v5+),
+/// the one from cosmos-sdk-proto doesn't have it
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct MsgTransfer {
+    #[prost(string, tag = "1")]
+    pub source_port: ::prost::alloc::string::String,
+    #[prost(string, tag = "2")]
+    pub source_channel: ::prost::alloc::string::String,
+    #[prost(message, optional, tag = "3")]
+    pub token: ::core::option::Option<Coin>,
+    #[prost(string, tag = "4")]
+    pub sender: ::prost::alloc::string::String,
+    #[prost(string, tag = "5")]
+    pub receiver: ::prost::alloc::string::String,
+    #[prost(message, optional, tag = "6")]
+    pub timeout_height: ::core::option::Option<Height>,
+    #[prost(uint64, tag = "7")]
+    pub timeout_timestamp: u64,
+    #[prost(string, tag = "8")]
+    pub memo: ::prost::alloc::string::String,
+}
@@ -1,6 +1,9 @@
 use cosmwasm_schema::cw_serde;
-use cosmwasm_std::{Coin, CosmosMsg, StdError, StdResult, Storage};
-use cw_storage_plus::Item;
+use cosmwasm_std::{
+    to_json_binary, Addr, Coin, CosmosMsg, CustomQuery, QuerierWrapper, StdError, StdResult,
+    Storage, WasmMsg,
+};
+use cw_storage_plus::{Item, Map};
 use neutron_sdk::bindings::msg::NeutronMsg;
 
 #[cw_serde]
@@ -17,15 +20,60 @@ pub enum IcaState {
     },
 }
 
-pub struct Ica<'a>(Item<'a, IcaState>);
+/// Settings of the automatic ICA re-registration after a channel timeout
+#[cw_serde]
+pub struct IcaRecoveryConfig {
+    /// fee for the re-registration, paid from the contract balance
+    pub register_fee: Coin,
+    /// contract notified with `IcaRecoveredHook` once the ICA is back
+    pub hook_receiver: Option<String>,
+}
+
+#[cw_serde]
+pub struct IcaRecoveredHookMsg {
+    pub ica_id: String,
+    pub ica_address: String,
+    pub port_id: String,
+    pub channel_id: String,
+}
+
+#[cw_serde]
+pub enum IcaRecoveryReceiverExecuteMsg {
+    IcaRecoveredHook(IcaRecoveredHookMsg),
+}
+
+impl IcaRecoveryConfig {
+    pub fn hook_msg<T>(&self, msg: IcaRecoveredHookMsg) -> StdResult<Option<CosmosMsg<T>>> {
+        self.hook_receiver
+            .as_ref()
+            .map(|receiver| {
+                Ok(CosmosMsg::Wasm(WasmMsg::Execute {
+                    contract_addr: receiver.to_string(),
+                    msg: to_json_binary(&IcaRecoveryReceiverExecuteMsg::IcaRecoveredHook(msg))?,
+                    funds: vec![],
+                }))
+            })
+            .transpose()
+    }
+}
+
+pub struct Ica<'a> {
+    storage_key: &'a str,
+    state: Item<'a, IcaState>,
+    last_address: Map<'a, &'a str, String>,
+}
 
 impl<'a> Ica<'a> {
     pub const fn new(storage_key: &'a str) -> Self {
-        Self(Item::new(storage_key))
+        Self {
+            storage_key,
+            state: Item::new(storage_key),
+            last_address: Map::new("ica_last_address"),
+        }
     }
 
     pub fn load(&self, store: &dyn Storage) -> StdResult<IcaState> {
-        self.0.may_load(store).map(Option::unwrap_or_default)
+        self.state.may_load(store).map(Option::unwrap_or_default)
     }
 
     pub fn register(
@@ -41,7 +89,7 @@ impl<'a> Ica<'a> {
             )),
             IcaState::Registered { .. } => Err(StdError::generic_err("ICA is already registered")),
             IcaState::Timeout | IcaState::None => {
-                self.0.save(store, &IcaState::InProgress)?;
+                self.state.save(store, &IcaState::InProgress)?;
                 Ok(NeutronMsg::register_interchain_account(
                     connection_id.into(),
                     ica_id.into(),
@@ -53,24 +101,89 @@ impl<'a> Ica<'a> {
     }
 
     pub fn set_timeout(&self, store: &mut dyn Storage) -> StdResult<()> {
-        self.0.save(store, &IcaState::Timeout)
+        self.state.save(store, &IcaState::Timeout)
+    }
+
+    /// Marks the ICA as timed out if the timed out packet was sent over the ICA channel.
+    /// Returns `true` if the state has been changed
+    pub fn timeout(&self, store: &mut dyn Storage, channel_id: &str) -> StdResult<bool> {
+        match self.load(store)? {
+            IcaState::Registered {
+                channel_id: ica_channel_id,
+                ..
+            } if ica_channel_id == channel_id => {
+                self.set_timeout(store)?;
+                Ok(true)
+            }
+            _ => Ok(false),
+        }
     }
 
+    /// Re-registers the timed out ICA on the same connection and with the same ICA id,
+    /// so the host chain reopens the channel for the same ICA address.
+    /// Returns `None` if the ICA is not timed out or the contract can't pay the fee
+    pub fn recover<C: CustomQuery>(
+        &self,
+        store: &mut dyn Storage,
+        querier: &QuerierWrapper<C>,
+        contract: &Addr,
+        connection_id: impl Into<String>,
+        ica_id: impl Into<String>,
+        recovery: &IcaRecoveryConfig,
+    ) -> StdResult<Option<CosmosMsg<NeutronMsg>>> {
+        if self.load(store)? != IcaState::Timeout {
+            return Ok(None);
+        }
+        let balance = querier.query_balance(contract, &recovery.register_fee.denom)?;
+        if balance.amount < recovery.register_fee.amount {
+            return Ok(None);
+        }
+        self.register(store, connection_id, ica_id, recovery.register_fee.clone())
+            .map(Some)
+    }
+
+    /// Saves the registered ICA. Returns `true` if the ICA has been registered before,
+    /// i.e. the channel has been recovered; the ICA address must stay the same in this case
     pub fn set_address(
         &self,
         store: &mut dyn Storage,
         address: impl Into<String>,
         port_id: impl Into<String>,
         channel_id: impl Into<String>,
-    ) -> StdResult<()> {
-        self.0.save(
+    ) -> StdResult<bool> {
+        let address = address.into();
+        let last_address = self.last_address.may_load(store, self.storage_key)?;
+        if let Some(last_address) = &last_address {
+            if *last_address != address {
+                return Err(StdError::generic_err(format!(
+                    "ICA address changed after re-registration: {} != {}",
+                    address, last_address
+                )));
+            }
+        }
+        self.last_address.save(store, self.storage_key, &address)?;
+        self.state.save(
             store,
             &IcaState::Registered {
-                ica_address: address.into(),
+                ica_address: address,
                 port_id: port_id.into(),
                 channel_id: channel_id.into(),
             },
-        )
+        )?;
+        Ok(last_address.is_some())
+    }
+
+    /// Remembers the address of an ICA registered before the recovery flow existed,
+    /// so its first recovery is recognized as such
+    pub fn init_last_address(&self, store: &mut dyn Storage) -> StdResult<()> {
+        if self.last_address.has(store, self.storage_key) {
+            return Ok(());
+        }
+        if let IcaState::Registered { ica_address, .. } = self.load(store)? {
+            self.last_address
+                .save(store, self.storage_key, &ica_address)?;
+        }
+        Ok(())
     }
 
     pub fn get_address(&self, store: &dyn Storage) -> StdResult<String> {
@@ -97,7 +97,8 @@ pub fn get_balance_and_delegations_keys(
         key: Binary(balance_key),
     });
 
-    // create KV key to get BondDenom from staking module params
+    // create KV key to get BondDenom from staking module params,
+    // x/params is gone since 0.50 and staking keeps its params itself since 0.47
     if version_to_u32(sdk_version)? < version_to_u32("0.47.0")? {
         keys.push(KVKey {
             path: PARAMS_STORE_KEY.to_string(),
@@ -2,6 +2,7 @@ pub mod answer;
 pub mod fsm;
 pub mod ibc_client_state;
 pub mod ibc_fee;
+pub mod ibc_transfer;
 pub mod ica;
 pub mod icq;
 pub mod interchain;
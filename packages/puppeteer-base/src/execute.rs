@@ -1,11 +1,14 @@
 use crate::{
     error::{ContractError, ContractResult},
     msg::{ExecuteMsg, Transaction},
-    state::{BaseConfig, PuppeteerBase, TxState, TxStateStatus, ICA_ID, LOCAL_DENOM},
+    state::{
+        BaseConfig, PuppeteerBase, TxResult, TxState, TxStateStatus, ICA_ID, LOCAL_DENOM,
+        TX_QUEUE_MAX_SIZE,
+    },
 };
 use cosmwasm_std::{
-    attr, ensure_eq, CosmosMsg, CustomQuery, Deps, DepsMut, Env, MessageInfo, Response, StdError,
-    StdResult, SubMsg,
+    attr, ensure, CosmosMsg, CustomQuery, Deps, DepsMut, Env, MessageInfo, Order, Response,
+    StdError, StdResult, Storage, SubMsg,
 };
 use drop_helpers::answer::response;
 use neutron_sdk::{
@@ -50,33 +53,86 @@ where
         Ok(Response::default())
     }
 
-    pub fn validate_tx_state<C: CustomQuery>(
-        &self,
-        deps: Deps<C>,
-        status: TxStateStatus,
-    ) -> NeutronResult<()> {
-        let tx_state = self.tx_state.load(deps.storage).unwrap_or_default();
-        ensure_eq!(
-            tx_state.status,
-            status,
+    pub fn validate_tx_queue_not_full<C: CustomQuery>(&self, deps: Deps<C>) -> NeutronResult<()> {
+        ensure!(
+            self.tx_in_progress.may_load(deps.storage)?.is_none(),
+            NeutronError::Std(StdError::generic_err("Transaction is already in progress"))
+        );
+        let queue_size = self
+            .tx_queue
+            .keys(deps.storage, None, None, Order::Ascending)
+            .count();
+        ensure!(
+            queue_size < TX_QUEUE_MAX_SIZE,
             NeutronError::Std(StdError::generic_err(format!(
-                "Transaction txState is not equal to expected: {}",
-                status
+                "Transaction queue is full: {}",
+                TX_QUEUE_MAX_SIZE
             )))
         );
         Ok(())
     }
 
-    pub fn validate_tx_idle_state<C: CustomQuery>(&self, deps: Deps<C>) -> NeutronResult<()> {
-        self.validate_tx_state(deps, TxStateStatus::Idle)
+    /// Removes the transaction from the queue once it's acknowledged, failed or timed out
+    pub fn take_tx(
+        &self,
+        storage: &mut dyn Storage,
+        channel_id: &str,
+        seq_id: u64,
+    ) -> StdResult<TxState> {
+        let key = (channel_id.to_string(), seq_id);
+        let tx_state = self
+            .tx_queue
+            .may_load(storage, key.clone())?
+            .ok_or_else(|| {
+                StdError::generic_err(format!(
+                    "Transaction {} on channel {} not found in queue",
+                    seq_id, channel_id
+                ))
+            })?;
+        self.tx_queue.remove(storage, key);
+        Ok(tx_state)
     }
 
-    pub fn validate_tx_waiting_state<C: CustomQuery>(&self, deps: Deps<C>) -> NeutronResult<()> {
-        self.validate_tx_state(deps, TxStateStatus::WaitingForAck)
+    /// Removes every transaction queued on the channel. An ordered ICA channel is closed
+    /// on timeout, so none of the remaining packets will ever be acknowledged
+    pub fn take_channel_txs(
+        &self,
+        storage: &mut dyn Storage,
+        channel_id: &str,
+    ) -> StdResult<Vec<(u64, TxState)>> {
+        let txs = self
+            .tx_queue
+            .prefix(channel_id.to_string())
+            .range(storage, None, None, Order::Ascending)
+            .collect::<StdResult<Vec<_>>>()?;
+        for (seq_id, _) in &txs {
+            self.tx_queue
+                .remove(storage, (channel_id.to_string(), *seq_id));
+        }
+        Ok(txs)
     }
 
-    pub fn validate_tx_inprogress_state<C: CustomQuery>(&self, deps: Deps<C>) -> NeutronResult<()> {
-        self.validate_tx_state(deps, TxStateStatus::InProgress)
+    /// Saves the outcome of the transaction to its history entry. Transactions submitted
+    /// before the history was introduced or already pruned have no entry and are skipped
+    pub fn record_tx_result(
+        &self,
+        storage: &mut dyn Storage,
+        env: &Env,
+        channel_id: &str,
+        seq_id: u64,
+        result: TxResult,
+    ) -> StdResult<()> {
+        if let Some((id, mut entry)) = self
+            .tx_history
+            .idx
+            .tx
+            .item(storage, (channel_id.to_string(), seq_id))?
+        {
+            entry.result = result;
+            entry.result_height = Some(env.block.height);
+            self.tx_history.save(storage, id, &entry)?;
+        }
+        Ok(())
     }
 
     pub fn msg_with_sudo_callback<C: Into<CosmosMsg<X>> + Serialize, X>(
@@ -88,8 +144,8 @@ where
         payload_id: u64,
     ) -> StdResult<SubMsg<X>> {
         deps.api
-            .debug("WASMDEBUG: msg_with_sudo_callback save tx_state InProgress");
-        self.tx_state.save(
+            .debug("WASMDEBUG: msg_with_sudo_callback save tx_in_progress");
+        self.tx_in_progress.save(
             deps.storage,
             &TxState {
                 status: TxStateStatus::InProgress,
@@ -2,12 +2,12 @@ use crate::{
     proto::{
         MsgBeginRedelegateResponse, MsgDelegateResponse, MsgExecResponse, MsgGrantResponse,
         MsgIBCTransfer, MsgRedeemTokensforSharesResponse, MsgSendResponse,
-        MsgTokenizeSharesResponse, MsgUndelegateResponse,
+        MsgTokenizeSharesResponse, MsgUndelegateResponse, MsgVoteResponse, MsgVoteWeightedResponse,
     },
     state::RedeemShareItem,
 };
 use cosmwasm_schema::{cw_serde, QueryResponses};
-use cosmwasm_std::{Empty, Uint128};
+use cosmwasm_std::{Decimal, Empty, Uint128};
 use neutron_sdk::sudo::msg::RequestPacket;
 use schemars::JsonSchema;
 
@@ -47,8 +47,19 @@ where
     KVQueryIds {},
     #[returns(cosmwasm_std::Binary)]
     Extension { msg: E },
+    /// The transaction being submitted or, if none, the oldest one awaiting its ack.
+    /// Idle when the queue is empty
     #[returns(crate::state::TxState)]
     TxState {},
+    #[returns(Vec<crate::state::TxState>)]
+    TxQueue {},
+    /// Retained transactions in ascending id order, oldest first, at most 100 per page
+    #[returns(Vec<crate::state::TxHistoryEntry>)]
+    TransactionHistory {
+        start_after: Option<u64>,
+        limit: Option<u32>,
+        kind: Option<TransactionKind>,
+    },
 }
 
 #[cw_serde]
@@ -98,11 +109,18 @@ pub enum ResponseAnswer {
     AuthzExecResponse(MsgExecResponse),
     IBCTransfer(MsgIBCTransfer),
     TransferResponse(MsgSendResponse),
+    VoteResponse(MsgVoteResponse),
+    VoteWeightedResponse(MsgVoteWeightedResponse),
     UnknownResponse {},
 }
 
 #[cw_serde]
 pub enum Transaction {
+    Delegate {
+        interchain_account_id: String,
+        items: Vec<(String, Uint128)>,
+        denom: String,
+    },
     Undelegate {
         interchain_account_id: String,
         items: Vec<(String, Uint128)>,
@@ -151,6 +169,81 @@ pub enum Transaction {
         delegate_grantee: String,
         rewards_withdraw_address: String,
     },
+    Vote {
+        interchain_account_id: String,
+        proposal_id: u64,
+        option: VoteOption,
+    },
+    WeightedVote {
+        interchain_account_id: String,
+        proposal_id: u64,
+        options: Vec<WeightedVoteOption>,
+    },
+}
+
+#[cw_serde]
+#[derive(Copy)]
+pub enum TransactionKind {
+    Undelegate = 0,
+    Redelegate = 1,
+    WithdrawReward = 2,
+    TokenizeShare = 3,
+    RedeemShares = 4,
+    ClaimRewardsAndOptionalyTransfer = 5,
+    IBCTransfer = 6,
+    Transfer = 7,
+    SetupProtocol = 8,
+    Delegate = 9,
+    Vote = 10,
+    WeightedVote = 11,
+}
+
+impl Transaction {
+    pub fn kind(&self) -> TransactionKind {
+        match self {
+            Transaction::Delegate { .. } => TransactionKind::Delegate,
+            Transaction::Undelegate { .. } => TransactionKind::Undelegate,
+            Transaction::Redelegate { .. } => TransactionKind::Redelegate,
+            Transaction::WithdrawReward { .. } => TransactionKind::WithdrawReward,
+            Transaction::TokenizeShare { .. } => TransactionKind::TokenizeShare,
+            Transaction::RedeemShares { .. } => TransactionKind::RedeemShares,
+            Transaction::ClaimRewardsAndOptionalyTransfer { .. } => {
+                TransactionKind::ClaimRewardsAndOptionalyTransfer
+            }
+            Transaction::IBCTransfer { .. } => TransactionKind::IBCTransfer,
+            Transaction::Transfer { .. } => TransactionKind::Transfer,
+            Transaction::SetupProtocol { .. } => TransactionKind::SetupProtocol,
+            Transaction::Vote { .. } => TransactionKind::Vote,
+            Transaction::WeightedVote { .. } => TransactionKind::WeightedVote,
+        }
+    }
+}
+
+#[cw_serde]
+#[derive(Copy)]
+pub enum VoteOption {
+    Yes,
+    Abstain,
+    No,
+    NoWithVeto,
+}
+
+impl VoteOption {
+    /// Value of the option in cosmos.gov.v1beta1.VoteOption
+    pub fn to_proto(&self) -> i32 {
+        match self {
+            VoteOption::Yes => 1,
+            VoteOption::Abstain => 2,
+            VoteOption::No => 3,
+            VoteOption::NoWithVeto => 4,
+        }
+    }
+}
+
+#[cw_serde]
+pub struct WeightedVoteOption {
+    pub option: VoteOption,
+    pub weight: Decimal,
 }
 
 #[cw_serde]
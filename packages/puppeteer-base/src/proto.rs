@@ -11,6 +11,10 @@ pub struct MsgIBCTransfer {}
 pub struct MsgSendResponse {}
 #[cw_serde]
 pub struct MsgGrantResponse {}
+#[cw_serde]
+pub struct MsgVoteResponse {}
+#[cw_serde]
+pub struct MsgVoteWeightedResponse {}
 
 #[cw_serde]
 pub struct MsgUndelegateResponse {
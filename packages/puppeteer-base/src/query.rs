@@ -1,12 +1,16 @@
-use cosmwasm_std::{to_json_binary, Binary, Deps, Env, StdError};
+use cosmwasm_std::{to_json_binary, Binary, Deps, Env, Order, StdError, StdResult};
+use cw_storage_plus::Bound;
 use neutron_sdk::bindings::query::NeutronQuery;
 use schemars::JsonSchema;
 use serde::{de::DeserializeOwned, Serialize};
 
 use crate::{
     error::{ContractError, ContractResult},
-    msg::QueryMsg,
-    state::{BaseConfig, PuppeteerBase, Transfer},
+    msg::{QueryMsg, TransactionKind},
+    state::{
+        BaseConfig, PuppeteerBase, Transfer, TxHistoryEntry, TxState, TX_HISTORY_DEFAULT_LIMIT,
+        TX_HISTORY_MAX_LIMIT,
+    },
 };
 
 impl<'a, T, U> PuppeteerBase<'a, T, U>
@@ -24,7 +28,13 @@ where
             QueryMsg::Config {} => self.query_config(deps),
             QueryMsg::Ica {} => self.query_ica(deps),
             QueryMsg::TxState {} => self.query_tx_state(deps),
+            QueryMsg::TxQueue {} => self.query_tx_queue(deps),
             QueryMsg::Transactions {} => self.query_transactions(deps),
+            QueryMsg::TransactionHistory {
+                start_after,
+                limit,
+                kind,
+            } => self.query_transaction_history(deps, start_after, limit, kind),
             QueryMsg::Extension { msg } => Err(ContractError::Std(StdError::generic_err(format!(
                 "Unsupported query message: {:?}",
                 msg
@@ -52,7 +62,56 @@ where
     }
 
     fn query_tx_state(&self, deps: Deps<NeutronQuery>) -> ContractResult<Binary> {
-        let tx_state = self.tx_state.may_load(deps.storage)?.unwrap_or_default();
+        let tx_state = match self.tx_in_progress.may_load(deps.storage)? {
+            Some(tx_state) => tx_state,
+            None => self
+                .tx_queue
+                .range(deps.storage, None, None, Order::Ascending)
+                .next()
+                .transpose()?
+                .map(|(_key, tx_state)| tx_state)
+                .unwrap_or_default(),
+        };
         Ok(to_json_binary(&tx_state)?)
     }
+
+    fn query_tx_queue(&self, deps: Deps<NeutronQuery>) -> ContractResult<Binary> {
+        let tx_queue = self
+            .tx_queue
+            .range(deps.storage, None, None, Order::Ascending)
+            .map(|res| res.map(|(_key, tx_state)| tx_state))
+            .collect::<StdResult<Vec<TxState>>>()?;
+        Ok(to_json_binary(&tx_queue)?)
+    }
+
+    fn query_transaction_history(
+        &self,
+        deps: Deps<NeutronQuery>,
+        start_after: Option<u64>,
+        limit: Option<u32>,
+        kind: Option<TransactionKind>,
+    ) -> ContractResult<Binary> {
+        let limit = limit
+            .unwrap_or(TX_HISTORY_DEFAULT_LIMIT)
+            .min(TX_HISTORY_MAX_LIMIT) as usize;
+        let min = start_after.map(Bound::exclusive);
+        let entries = match kind {
+            Some(kind) => self
+                .tx_history
+                .idx
+                .kind
+                .prefix(kind as u8)
+                .range(deps.storage, min, None, Order::Ascending)
+                .take(limit)
+                .map(|res| res.map(|(_key, entry)| entry))
+                .collect::<StdResult<Vec<TxHistoryEntry>>>()?,
+            None => self
+                .tx_history
+                .range(deps.storage, min, None, Order::Ascending)
+                .take(limit)
+                .map(|res| res.map(|(_key, entry)| entry))
+                .collect::<StdResult<Vec<TxHistoryEntry>>>()?,
+        };
+        Ok(to_json_binary(&entries)?)
+    }
 }
@@ -1,5 +1,7 @@
-use crate::state::{BaseConfig, PuppeteerBase, TxState, TxStateStatus};
-use cosmwasm_std::{attr, DepsMut, Reply, Response, StdError, StdResult};
+use crate::state::{
+    BaseConfig, PuppeteerBase, TxHistoryEntry, TxResult, TxStateStatus, TX_HISTORY_MAX_SIZE,
+};
+use cosmwasm_std::{attr, DepsMut, Env, Reply, Response, StdError, StdResult, Storage};
 use drop_helpers::{answer::response, query_id::get_query_id};
 use neutron_sdk::bindings::msg::{MsgIbcTransferResponse, MsgSubmitTxResponse};
 use serde::{de::DeserializeOwned, Serialize};
@@ -71,7 +73,7 @@ where
         Ok(Response::new())
     }
 
-    pub fn submit_tx_reply(&self, deps: DepsMut, msg: Reply) -> StdResult<Response> {
+    pub fn submit_tx_reply(&self, deps: DepsMut, env: Env, msg: Reply) -> StdResult<Response> {
         let resp: MsgSubmitTxResponse = serde_json_wasm::from_slice(
             msg.result
                 .into_result()
@@ -85,10 +87,7 @@ where
             .debug(format!("WASMDEBUG: prepare_sudo_payload received; resp: {resp:?}").as_str());
         let seq_id = resp.sequence_id;
         let channel_id = resp.channel;
-        let mut self_tx_state: TxState = self.tx_state.load(deps.storage)?;
-        self_tx_state.seq_id = Some(seq_id);
-        self_tx_state.status = TxStateStatus::WaitingForAck;
-        self.tx_state.save(deps.storage, &self_tx_state)?;
+        self.enqueue_tx_in_progress(deps.storage, &env, &channel_id, seq_id)?;
         let atts = vec![
             attr("channel_id", channel_id.to_string()),
             attr("seq_id", seq_id.to_string()),
@@ -96,7 +95,12 @@ where
         Ok(response("sudo-tx-payload-received", "puppeteer-base", atts))
     }
 
-    pub fn submit_ibc_transfer_reply(&self, deps: DepsMut, msg: Reply) -> StdResult<Response> {
+    pub fn submit_ibc_transfer_reply(
+        &self,
+        deps: DepsMut,
+        env: Env,
+        msg: Reply,
+    ) -> StdResult<Response> {
         let resp: MsgIbcTransferResponse = serde_json_wasm::from_slice(
             msg.result
                 .into_result()
@@ -110,10 +114,7 @@ where
             .debug(format!("WASMDEBUG: prepare_sudo_payload received; resp: {resp:?}").as_str());
         let seq_id = resp.sequence_id;
         let channel_id = resp.channel;
-        let mut self_tx_state: TxState = self.tx_state.load(deps.storage)?;
-        self_tx_state.seq_id = Some(seq_id);
-        self_tx_state.status = TxStateStatus::WaitingForAck;
-        self.tx_state.save(deps.storage, &self_tx_state)?;
+        self.enqueue_tx_in_progress(deps.storage, &env, &channel_id, seq_id)?;
         let atts = vec![
             attr("channel_id", channel_id.to_string()),
             attr("seq_id", seq_id.to_string()),
@@ -124,4 +125,40 @@ where
             atts,
         ))
     }
+
+    fn enqueue_tx_in_progress(
+        &self,
+        storage: &mut dyn Storage,
+        env: &Env,
+        channel_id: &str,
+        seq_id: u64,
+    ) -> StdResult<()> {
+        let mut tx_state = self.tx_in_progress.load(storage)?;
+        tx_state.seq_id = Some(seq_id);
+        tx_state.status = TxStateStatus::WaitingForAck;
+        if let Some(transaction) = &tx_state.transaction {
+            let id = self.tx_history_next_id.may_load(storage)?.unwrap_or(0);
+            self.tx_history.save(
+                storage,
+                id,
+                &TxHistoryEntry {
+                    id,
+                    channel_id: channel_id.to_string(),
+                    seq_id,
+                    transaction: transaction.clone(),
+                    submit_height: env.block.height,
+                    result: TxResult::Pending,
+                    result_height: None,
+                },
+            )?;
+            self.tx_history_next_id.save(storage, &(id + 1))?;
+            if let Some(pruned_id) = id.checked_sub(TX_HISTORY_MAX_SIZE) {
+                self.tx_history.remove(storage, pruned_id)?;
+            }
+        }
+        self.tx_queue
+            .save(storage, (channel_id.to_string(), seq_id), &tx_state)?;
+        self.tx_in_progress.remove(storage);
+        Ok(())
+    }
 }
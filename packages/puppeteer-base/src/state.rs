@@ -5,8 +5,11 @@ use cosmos_sdk_proto::cosmos::{
 use cosmwasm_std::{from_json, Addr, Decimal256, StdError, Timestamp, Uint128, Uint256};
 
 use cosmwasm_schema::cw_serde;
-use cw_storage_plus::{Index, IndexList, IndexedMap, Item, Map, UniqueIndex};
-use drop_helpers::{ica::Ica, version::version_to_u32};
+use cw_storage_plus::{Index, IndexList, IndexedMap, Item, Map, MultiIndex, UniqueIndex};
+use drop_helpers::{
+    ica::{Ica, IcaRecoveryConfig},
+    version::version_to_u32,
+};
 use neutron_sdk::{
     interchain_queries::v045::{
         helpers::deconstruct_account_denom_balance_key,
@@ -19,7 +22,10 @@ use serde::{de::DeserializeOwned, Serialize};
 use std::ops::Div;
 use std::str::FromStr;
 
-use crate::{msg::Transaction, r#trait::PuppeteerReconstruct};
+use crate::{
+    msg::{ResponseAnswer, Transaction},
+    r#trait::PuppeteerReconstruct,
+};
 
 pub struct PuppeteerBase<'a, T, U>
 where
@@ -30,12 +36,20 @@ where
     pub ica: Ica<'a>,
     pub recipient_transfers: Item<'a, Vec<Transfer>>,
     pub transfer_channel_id: Item<'a, String>,
-    pub tx_state: Item<'a, TxState>,
+    /// Transaction submitted in the current block, waiting for the submit reply
+    pub tx_in_progress: Item<'a, TxState>,
+    /// Transactions waiting for acknowledgement keyed by (channel_id, seq_id)
+    pub tx_queue: Map<'a, (String, u64), TxState>,
+    /// Latest submitted transactions with their results keyed by the history id
+    pub tx_history: IndexedMap<'a, u64, TxHistoryEntry, TxHistoryIndexes<'a>>,
+    pub tx_history_next_id: Item<'a, u64>,
     pub kv_queries: Map<'a, u64, U>,
     pub last_complete_delegations_and_balances_key: Item<'a, u64>,
     pub delegations_and_balances:
         Map<'a, &'a u64, BalancesAndDelegationsState<BalancesAndDelegations>>,
     pub delegations_and_balances_query_id_chunk: Map<'a, u64, u16>, // Map <query_id, chunk_id>
+    /// Remote height of the last result received for every delegations chunk
+    pub delegations_and_balances_chunk_heights: Map<'a, u16, u64>,
     pub unbonding_delegations:
         IndexedMap<'a, &'a str, UnbondingDelegation, UnbondingDelegationIndexes<'a>>,
     pub unbonding_delegations_reply_id_storage: Map<'a, u16, UnbondingDelegation>,
@@ -61,7 +75,23 @@ where
             config: Item::new("config"),
             ica: Ica::new("ica"),
             recipient_transfers: Item::new("transfers"),
-            tx_state: Item::new("sudo_payload"),
+            tx_in_progress: Item::new("tx_in_progress"),
+            tx_queue: Map::new("tx_queue"),
+            tx_history: IndexedMap::new(
+                "tx_history",
+                TxHistoryIndexes {
+                    kind: MultiIndex::new(
+                        |_pk, e: &TxHistoryEntry| e.transaction.kind() as u8,
+                        "tx_history",
+                        "tx_history__kind",
+                    ),
+                    tx: UniqueIndex::new(
+                        |e: &TxHistoryEntry| (e.channel_id.clone(), e.seq_id),
+                        "tx_history__tx",
+                    ),
+                },
+            ),
+            tx_history_next_id: Item::new("tx_history_next_id"),
             transfer_channel_id: Item::new("transfer_channel_id"),
             kv_queries: Map::new("kv_queries"),
             last_complete_delegations_and_balances_key: Item::new(
@@ -71,6 +101,9 @@ where
             delegations_and_balances_query_id_chunk: Map::new(
                 "delegations_and_balance_reply_id_storage",
             ),
+            delegations_and_balances_chunk_heights: Map::new(
+                "delegations_and_balances_chunk_heights",
+            ),
             unbonding_delegations: IndexedMap::new(
                 "unbonding_delegations",
                 UnbondingDelegationIndexes {
@@ -90,6 +123,9 @@ where
 pub trait BaseConfig {
     fn connection_id(&self) -> String;
     fn update_period(&self) -> u64;
+    fn ica_recovery(&self) -> Option<IcaRecoveryConfig> {
+        None
+    }
 }
 
 #[cw_serde]
@@ -167,6 +203,37 @@ pub struct TxState {
     pub reply_to: Option<String>,
 }
 
+#[cw_serde]
+pub enum TxResult {
+    Pending,
+    Success { answers: Vec<ResponseAnswer> },
+    Error { details: String },
+    Timeout,
+}
+
+#[cw_serde]
+pub struct TxHistoryEntry {
+    pub id: u64,
+    pub channel_id: String,
+    pub seq_id: u64,
+    pub transaction: Transaction,
+    pub submit_height: u64,
+    pub result: TxResult,
+    pub result_height: Option<u64>,
+}
+
+pub struct TxHistoryIndexes<'a> {
+    pub kind: MultiIndex<'a, u8, TxHistoryEntry, u64>,
+    pub tx: UniqueIndex<'a, (String, u64), TxHistoryEntry, u64>,
+}
+
+impl<'a> IndexList<TxHistoryEntry> for TxHistoryIndexes<'a> {
+    fn get_indexes(&'_ self) -> Box<dyn Iterator<Item = &'_ dyn Index<TxHistoryEntry>> + '_> {
+        let v: Vec<&dyn Index<TxHistoryEntry>> = vec![&self.kind, &self.tx];
+        Box::new(v.into_iter())
+    }
+}
+
 #[cw_serde]
 pub struct UnbondingDelegation {
     pub validator_address: String,
@@ -303,6 +370,11 @@ impl PuppeteerReconstruct for BalancesAndDelegations {
 }
 
 pub type Recipient = str;
+pub const TX_QUEUE_MAX_SIZE: usize = 10;
+pub const TX_HISTORY_DEFAULT_LIMIT: u32 = 100;
+pub const TX_HISTORY_MAX_LIMIT: u32 = 100;
+/// Number of latest transactions kept in the history, older entries are pruned
+pub const TX_HISTORY_MAX_SIZE: u64 = 1000;
 pub const LOCAL_DENOM: &str = "untrn";
 pub const ICA_ID: &str = "DROP";
 
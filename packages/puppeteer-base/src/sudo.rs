@@ -3,14 +3,18 @@ use crate::{
     r#trait::PuppeteerReconstruct,
     state::{
         BalancesAndDelegations, BalancesAndDelegationsState, BaseConfig, PuppeteerBase, Transfer,
+        ICA_ID,
     },
 };
 use cosmos_sdk_proto::cosmos::{
     bank::v1beta1::MsgSend,
     tx::v1beta1::{TxBody, TxRaw},
 };
-use cosmwasm_std::{Binary, DepsMut, Env, Order, Response, StdError};
-use cw_storage_plus::Index;
+use cosmwasm_std::{
+    attr, Binary, CosmosMsg, DepsMut, Env, Event, Order, Response, StdError, StdResult,
+};
+use cw_storage_plus::{Bound, Index};
+use drop_helpers::ica::IcaRecoveredHookMsg;
 use neutron_sdk::{
     bindings::{
         msg::NeutronMsg,
@@ -96,12 +100,15 @@ where
         Ok(deposits)
     }
 
+    /// Merges a delegations chunk into the pending snapshot whose remote height differs
+    /// by at most `max_height_skew`, snapshots which can't be completed anymore are abandoned
     pub fn sudo_delegations_and_balance_kv_query_result(
         &self,
         deps: DepsMut<NeutronQuery>,
         env: Env,
         query_id: u64,
         version: &str,
+        max_height_skew: u64,
     ) -> NeutronResult<Response<NeutronMsg>> {
         let chunks_len = self
             .delegations_and_balances_query_id_chunk
@@ -118,42 +125,82 @@ where
             )
         };
         let data: BalancesAndDelegations = PuppeteerReconstruct::reconstruct(&kv_results, version)?;
-        let new_state = match self
+        self.delegations_and_balances_chunk_heights
+            .save(deps.storage, chunk_id, &remote_height)?;
+        let prev_key = self
+            .last_complete_delegations_and_balances_key
+            .may_load(deps.storage)?;
+        if prev_key.map_or(false, |key| remote_height <= key) {
+            // a newer snapshot is already complete
+            return Ok(Response::default());
+        }
+        // only snapshots newer than the last complete one are still pending
+        let pending = self
             .delegations_and_balances
-            .may_load(deps.storage, &remote_height)?
-        {
-            Some(mut state) => {
-                if !state.collected_chunks.contains(&chunk_id) {
-                    state
-                        .data
-                        .delegations
-                        .delegations
-                        .extend(data.delegations.delegations);
-                    state.collected_chunks.push(chunk_id);
+            .range(
+                deps.storage,
+                prev_key.map(|key| Bound::ExclusiveRaw(key.to_be_bytes().to_vec())),
+                None,
+                Order::Descending,
+            )
+            .collect::<StdResult<Vec<_>>>()?;
+        let mut events = vec![];
+        let mut target = None;
+        for (height, state) in pending {
+            if state.collected_chunks.contains(&chunk_id) {
+                if height == remote_height {
+                    // this chunk is already collected at this height
+                    return Ok(Response::default());
+                }
+                continue;
+            }
+            if height.abs_diff(remote_height) <= max_height_skew {
+                if target.is_none() {
+                    target = Some((height, state));
                 }
+            } else if height + max_height_skew < remote_height {
+                // the chunk has moved past this snapshot, so it can't be completed anymore
+                self.delegations_and_balances.remove(deps.storage, &height);
+                events.push(
+                    Event::new("puppeteer-base-delegations_snapshot_abandoned").add_attributes(
+                        vec![
+                            attr("remote_height", height.to_string()),
+                            attr("chunk_id", chunk_id.to_string()),
+                            attr("chunk_remote_height", remote_height.to_string()),
+                            attr("collected_chunks", format!("{:?}", state.collected_chunks)),
+                        ],
+                    ),
+                );
+            }
+        }
+        let (key, new_state) = match target {
+            Some((height, mut state)) => {
                 state
+                    .data
+                    .delegations
+                    .delegations
+                    .extend(data.delegations.delegations);
+                state.collected_chunks.push(chunk_id);
+                (height, state)
             }
-            None => BalancesAndDelegationsState {
-                data,
+            None => (
                 remote_height,
-                local_height: env.block.height,
-                timestamp: env.block.time,
-                collected_chunks: vec![chunk_id],
-            },
+                BalancesAndDelegationsState {
+                    data,
+                    remote_height,
+                    local_height: env.block.height,
+                    timestamp: env.block.time,
+                    collected_chunks: vec![chunk_id],
+                },
+            ),
         };
-        if new_state.collected_chunks.len() == chunks_len {
-            let prev_key = self
-                .last_complete_delegations_and_balances_key
-                .load(deps.storage)
-                .unwrap_or_default();
-            if prev_key < remote_height {
-                self.last_complete_delegations_and_balances_key
-                    .save(deps.storage, &remote_height)?;
-            }
+        if new_state.collected_chunks.len() == chunks_len && prev_key.unwrap_or_default() < key {
+            self.last_complete_delegations_and_balances_key
+                .save(deps.storage, &key)?;
         }
         self.delegations_and_balances
-            .save(deps.storage, &remote_height, &new_state)?;
-        Ok(Response::default())
+            .save(deps.storage, &key, &new_state)?;
+        Ok(Response::default().add_events(events))
     }
 
     pub fn sudo_kv_query_result<
@@ -218,6 +265,30 @@ where
         Ok(Response::default())
     }
 
+    /// Re-registers the timed out ICA when the recovery is configured
+    pub fn ica_recovery_msgs(
+        &self,
+        deps: DepsMut<NeutronQuery>,
+        env: &Env,
+    ) -> StdResult<Vec<CosmosMsg<NeutronMsg>>> {
+        let config = self.config.load(deps.storage)?;
+        Ok(match config.ica_recovery() {
+            Some(recovery) => self
+                .ica
+                .recover(
+                    deps.storage,
+                    &deps.querier,
+                    &env.contract.address,
+                    config.connection_id(),
+                    ICA_ID,
+                    &recovery,
+                )?
+                .into_iter()
+                .collect(),
+            None => vec![],
+        })
+    }
+
     pub fn sudo_open_ack(
         &self,
         deps: DepsMut<NeutronQuery>,
@@ -230,9 +301,24 @@ where
         let parsed_version: Result<OpenAckVersion, _> =
             serde_json_wasm::from_str(counterparty_version.as_str());
         if let Ok(parsed_version) = parsed_version {
-            self.ica
-                .set_address(deps.storage, parsed_version.address, port_id, channel_id)?;
-            Ok(Response::default())
+            let recovered = self.ica.set_address(
+                deps.storage,
+                parsed_version.address.clone(),
+                port_id.clone(),
+                channel_id.clone(),
+            )?;
+            let mut msgs = vec![];
+            if recovered {
+                if let Some(recovery) = self.config.load(deps.storage)?.ica_recovery() {
+                    msgs.extend(recovery.hook_msg(IcaRecoveredHookMsg {
+                        ica_id: ICA_ID.to_string(),
+                        ica_address: parsed_version.address,
+                        port_id,
+                        channel_id,
+                    })?);
+                }
+            }
+            Ok(Response::default().add_messages(msgs))
         } else {
             Err(NeutronError::Std(StdError::generic_err(
                 "can't parse version",